@@ -0,0 +1,75 @@
+//! # Mock Price Feed
+//!
+//! A minimal contract exposing the read side of the SEP-40 price feed
+//! interface (`lastprice`, `decimals`) so PIFP tests can exercise
+//! quote-denominated goals without a live oracle such as Reflector.
+//!
+//! Prices are set directly with `set_price`; there is no access control.
+//! **Never deploy this contract to a real network.**
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol};
+
+/// Asset identifier, as defined by SEP-40.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    /// A Stellar asset, identified by its token contract address.
+    Stellar(Address),
+    /// An off-chain asset, identified by its ticker.
+    Other(Symbol),
+}
+
+/// A price observation, as defined by SEP-40.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// Price of one unit of the asset in the quote currency, scaled by `decimals()`.
+    pub price: i128,
+    /// Ledger timestamp of the observation.
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum DataKey {
+    Decimals,
+    Price(Asset),
+}
+
+#[contract]
+pub struct MockPriceFeed;
+
+#[contractimpl]
+impl MockPriceFeed {
+    /// Set the number of decimals prices are scaled by.
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage().instance().set(&DataKey::Decimals, &decimals);
+    }
+
+    /// Record `price` for `asset` as observed at `timestamp`.
+    pub fn set_price(env: Env, asset: Asset, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Price(asset), &PriceData { price, timestamp });
+    }
+
+    /// Forget any price recorded for `asset`.
+    pub fn clear_price(env: Env, asset: Asset) {
+        env.storage().instance().remove(&DataKey::Price(asset));
+    }
+
+    /// SEP-40: most recent price for `asset`, or `None` if never set.
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        env.storage().instance().get(&DataKey::Price(asset))
+    }
+
+    /// SEP-40: number of decimals prices are scaled by (defaults to 14).
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or(14)
+    }
+}
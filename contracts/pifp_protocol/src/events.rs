@@ -1,4 +1,10 @@
-use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::types::{
+    ContentId, EvidenceEntry, ExtensionProposal, ExtensionStatus, FundingMode, MatchPledge,
+    ProofSubmission, ProtocolParams, ReleaseVote, Round, Subscription, SubscriptionStatus,
+    TokenMetadata,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -7,6 +13,9 @@ pub struct ProjectCreated {
     pub creator: Address,
     pub token: Address,
     pub goal: i128,
+    pub funding_mode: FundingMode,
+    /// Registered proof commitment.
+    pub proof: ContentId,
 }
 
 #[contracttype]
@@ -22,7 +31,14 @@ pub struct ProjectFunded {
 pub struct ProjectVerified {
     pub project_id: u64,
     pub oracle: Address,
-    pub proof_hash: BytesN<32>,
+    /// Proof identifier revealed by the oracle.
+    pub proof: ContentId,
+    /// Salt revealed with it; zero for projects without a commitment.
+    pub salt: BytesN<32>,
+    /// The project's registered proof the reveal was checked against.
+    pub commitment: ContentId,
+    /// Evidence log entry the oracle cited, if any.
+    pub evidence_index: Option<u32>,
 }
 
 #[contracttype]
@@ -33,12 +49,187 @@ pub struct FundsReleased {
     pub amount: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptedTokenChanged {
+    pub project_id: u64,
+    pub token: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationWithdrawn {
+    pub project_id: u64,
+    pub donator: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationCommitted {
+    pub project_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub commitment: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationRevealed {
+    pub project_id: u64,
+    pub commitment: BytesN<32>,
+    pub donor: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionCharged {
+    pub sub_id: u64,
+    pub project_id: u64,
+    pub subscriber: Address,
+    pub amount: i128,
+    /// 1-based number of this payment.
+    pub period: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionEnded {
+    pub sub_id: u64,
+    pub project_id: u64,
+    pub status: SubscriptionStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundMatched {
+    pub round_id: u64,
+    pub project_id: u64,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationMatched {
+    pub match_id: u64,
+    pub project_id: u64,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchReleased {
+    pub match_id: u64,
+    pub project_id: u64,
+    /// Unused funds returned to the sponsor.
+    pub returned: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundClosed {
+    pub round_id: u64,
+    /// Total matching credited to projects.
+    pub distributed: i128,
+    /// Unallocated remainder returned to the funder.
+    pub returned: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundMatchReclaimed {
+    pub round_id: u64,
+    pub project_id: u64,
+    /// Matching taken back from the cancelled project and returned to the
+    /// funder.
+    pub returned: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectCancelled {
+    pub project_id: u64,
+    pub caller: Address,
+    pub reason_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationRefunded {
+    pub project_id: u64,
+    pub donator: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionVoted {
+    pub project_id: u64,
+    pub donor: Address,
+    pub approve: bool,
+    pub weight: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionResolved {
+    pub project_id: u64,
+    pub status: ExtensionStatus,
+    /// The project's deadline after the decision.
+    pub deadline: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseVoted {
+    pub project_id: u64,
+    pub donor: Address,
+    pub approve: bool,
+    pub weight: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EvidenceAdded {
+    pub project_id: u64,
+    pub entry: EvidenceEntry,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofSubmitted {
+    pub project_id: u64,
+    pub submission: ProofSubmission,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofAccepted {
+    pub project_id: u64,
+    pub oracle: Address,
+    pub submission: ProofSubmission,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofRejected {
+    pub project_id: u64,
+    pub oracle: Address,
+    pub reason_hash: BytesN<32>,
+}
+
 pub fn emit_project_created(
     env: &Env,
     project_id: u64,
     creator: Address,
     token: Address,
     goal: i128,
+    funding_mode: FundingMode,
+    proof: ContentId,
 ) {
     let topics = (symbol_short!("created"), project_id);
     let data = ProjectCreated {
@@ -46,6 +237,8 @@ pub fn emit_project_created(
         creator,
         token,
         goal,
+        funding_mode,
+        proof,
     };
     env.events().publish(topics, data);
 }
@@ -60,12 +253,23 @@ pub fn emit_project_funded(env: &Env, project_id: u64, donator: Address, amount:
     env.events().publish(topics, data);
 }
 
-pub fn emit_project_verified(env: &Env, project_id: u64, oracle: Address, proof_hash: BytesN<32>) {
+pub fn emit_project_verified(
+    env: &Env,
+    project_id: u64,
+    oracle: Address,
+    proof: ContentId,
+    salt: BytesN<32>,
+    commitment: ContentId,
+    evidence_index: Option<u32>,
+) {
     let topics = (symbol_short!("verified"), project_id);
     let data = ProjectVerified {
         project_id,
         oracle,
-        proof_hash,
+        proof,
+        salt,
+        commitment,
+        evidence_index,
     };
     env.events().publish(topics, data);
 }
@@ -87,3 +291,312 @@ pub fn emit_protocol_paused(env: &Env, admin: Address) {
 pub fn emit_protocol_unpaused(env: &Env, admin: Address) {
     env.events().publish((symbol_short!("unpaused"), admin), ());
 }
+
+pub fn emit_token_allowed(env: &Env, token: Address, metadata: Option<TokenMetadata>) {
+    env.events()
+        .publish((symbol_short!("tok_set"), token), metadata);
+}
+
+pub fn emit_token_disallowed(env: &Env, token: Address) {
+    env.events().publish((symbol_short!("tok_del"), token), ());
+}
+
+pub fn emit_params_updated(env: &Env, admin: Address, version: u32, params: ProtocolParams) {
+    env.events()
+        .publish((symbol_short!("params"), version, admin), params);
+}
+
+pub fn emit_accepted_token_added(env: &Env, project_id: u64, token: Address) {
+    let topics = (symbol_short!("tok_added"), project_id);
+    env.events()
+        .publish(topics, AcceptedTokenChanged { project_id, token });
+}
+
+pub fn emit_accepted_token_removed(env: &Env, project_id: u64, token: Address) {
+    let topics = (symbol_short!("tok_rmvd"), project_id);
+    env.events()
+        .publish(topics, AcceptedTokenChanged { project_id, token });
+}
+
+pub fn emit_goal_reached(env: &Env, project_id: u64) {
+    env.events()
+        .publish((symbol_short!("goal_met"), project_id), ());
+}
+
+pub fn emit_donation_withdrawn(
+    env: &Env,
+    project_id: u64,
+    donator: Address,
+    token: Address,
+    amount: i128,
+) {
+    // Longer than `symbol_short!` allows; named as the event is documented.
+    let topics = (Symbol::new(env, "donation_withdrawn"), project_id);
+    let data = DonationWithdrawn {
+        project_id,
+        donator,
+        token,
+        amount,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_donation_committed(
+    env: &Env,
+    project_id: u64,
+    token: Address,
+    amount: i128,
+    commitment: BytesN<32>,
+) {
+    let topics = (symbol_short!("committed"), project_id);
+    let data = DonationCommitted {
+        project_id,
+        token,
+        amount,
+        commitment,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_donation_revealed(env: &Env, project_id: u64, commitment: BytesN<32>, donor: Address) {
+    let topics = (symbol_short!("revealed"), project_id);
+    let data = DonationRevealed {
+        project_id,
+        commitment,
+        donor,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_keeper_set(env: &Env, donator: Address, keeper: Address, approved: bool) {
+    env.events()
+        .publish((symbol_short!("keeper"), donator, keeper), approved);
+}
+
+pub fn emit_subscription_created(env: &Env, sub: Subscription) {
+    let topics = (symbol_short!("sub_new"), sub.project_id);
+    env.events().publish(topics, sub);
+}
+
+pub fn emit_subscription_charged(env: &Env, sub: &Subscription, amount: i128) {
+    let topics = (symbol_short!("sub_paid"), sub.project_id);
+    let data = SubscriptionCharged {
+        sub_id: sub.id,
+        project_id: sub.project_id,
+        subscriber: sub.subscriber.clone(),
+        amount,
+        period: sub.periods_charged,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_subscription_ended(env: &Env, sub: &Subscription) {
+    let topics = (symbol_short!("sub_end"), sub.project_id);
+    let data = SubscriptionEnded {
+        sub_id: sub.id,
+        project_id: sub.project_id,
+        status: sub.status,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_round_created(env: &Env, round: Round) {
+    env.events()
+        .publish((symbol_short!("rnd_new"), round.id), round);
+}
+
+pub fn emit_round_joined(env: &Env, round_id: u64, project_id: u64) {
+    env.events()
+        .publish((symbol_short!("rnd_join"), round_id), project_id);
+}
+
+pub fn emit_round_matched(env: &Env, round_id: u64, project_id: u64, token: Address, amount: i128) {
+    let topics = (symbol_short!("qf_match"), project_id);
+    let data = RoundMatched {
+        round_id,
+        project_id,
+        token,
+        amount,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_round_match_reclaimed(env: &Env, round_id: u64, project_id: u64, returned: i128) {
+    let topics = (symbol_short!("qf_reclm"), project_id);
+    let data = RoundMatchReclaimed {
+        round_id,
+        project_id,
+        returned,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_round_closed(env: &Env, round_id: u64, distributed: i128, returned: i128) {
+    let topics = (symbol_short!("rnd_close"), round_id);
+    let data = RoundClosed {
+        round_id,
+        distributed,
+        returned,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_match_created(env: &Env, pledge: MatchPledge) {
+    let topics = (symbol_short!("match_new"), pledge.project_id);
+    env.events().publish(topics, pledge);
+}
+
+pub fn emit_donation_matched(
+    env: &Env,
+    match_id: u64,
+    project_id: u64,
+    token: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("matched"), project_id);
+    let data = DonationMatched {
+        match_id,
+        project_id,
+        token,
+        amount,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_match_released(env: &Env, match_id: u64, project_id: u64, returned: i128) {
+    let topics = (symbol_short!("match_end"), project_id);
+    let data = MatchReleased {
+        match_id,
+        project_id,
+        returned,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_project_cancelled(
+    env: &Env,
+    project_id: u64,
+    caller: Address,
+    reason_hash: BytesN<32>,
+) {
+    let topics = (symbol_short!("cancelled"), project_id);
+    let data = ProjectCancelled {
+        project_id,
+        caller,
+        reason_hash,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_donation_refunded(
+    env: &Env,
+    project_id: u64,
+    donator: Address,
+    token: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("refunded"), project_id, token.clone());
+    let data = DonationRefunded {
+        project_id,
+        donator,
+        token,
+        amount,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_extension_proposed(env: &Env, proposal: ExtensionProposal) {
+    let topics = (symbol_short!("ext_new"), proposal.project_id);
+    env.events().publish(topics, proposal);
+}
+
+pub fn emit_extension_voted(
+    env: &Env,
+    project_id: u64,
+    donor: Address,
+    approve: bool,
+    weight: i128,
+) {
+    let topics = (symbol_short!("ext_vote"), project_id);
+    let data = ExtensionVoted {
+        project_id,
+        donor,
+        approve,
+        weight,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_extension_resolved(env: &Env, project_id: u64, status: ExtensionStatus, deadline: u64) {
+    let topics = (symbol_short!("ext_end"), project_id);
+    let data = ExtensionResolved {
+        project_id,
+        status,
+        deadline,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_release_vote_started(env: &Env, vote: ReleaseVote) {
+    let topics = (symbol_short!("vote_new"), vote.project_id);
+    env.events().publish(topics, vote);
+}
+
+pub fn emit_release_voted(env: &Env, project_id: u64, donor: Address, approve: bool, weight: i128) {
+    let topics = (symbol_short!("vote_cast"), project_id);
+    let data = ReleaseVoted {
+        project_id,
+        donor,
+        approve,
+        weight,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_release_vote_closed(env: &Env, vote: ReleaseVote) {
+    let topics = (symbol_short!("vote_end"), vote.project_id);
+    env.events().publish(topics, vote);
+}
+
+pub fn emit_proof_submitted(env: &Env, project_id: u64, submission: ProofSubmission) {
+    let topics = (symbol_short!("proof_sub"), project_id);
+    let data = ProofSubmitted {
+        project_id,
+        submission,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_proof_accepted(
+    env: &Env,
+    project_id: u64,
+    oracle: Address,
+    submission: ProofSubmission,
+) {
+    let topics = (symbol_short!("proof_ok"), project_id);
+    let data = ProofAccepted {
+        project_id,
+        oracle,
+        submission,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_proof_rejected(env: &Env, project_id: u64, oracle: Address, reason_hash: BytesN<32>) {
+    let topics = (symbol_short!("proof_rej"), project_id);
+    let data = ProofRejected {
+        project_id,
+        oracle,
+        reason_hash,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_evidence_added(env: &Env, entry: EvidenceEntry) {
+    let topics = (symbol_short!("evidence"), entry.project_id);
+    let data = EvidenceAdded {
+        project_id: entry.project_id,
+        entry,
+    };
+    env.events().publish(topics, data);
+}
@@ -2,12 +2,12 @@ extern crate std;
 use std::vec::Vec;
 
 use proptest::prelude::*;
-use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, Vec as SorobanVec};
+use soroban_sdk::{testutils::Address as _, token, Address, Bytes, BytesN, Env, Vec as SorobanVec};
 
 use crate::invariants::*;
 pub use crate::types::ProjectStatus;
 pub use crate::Role;
-use crate::{PifpProtocol, PifpProtocolClient};
+use crate::{ContentId, PifpProtocol, PifpProtocolClient};
 
 // ── Helpers ─────────────────────────────────────────────────────────
 
@@ -26,6 +26,19 @@ fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
     token::Client::new(env, &addr.address())
 }
 
+fn allow_tokens(client: &PifpProtocolClient, admin: &Address, tokens: &SorobanVec<Address>) {
+    for token in tokens.iter() {
+        client.allow_token(admin, &token, &None);
+    }
+}
+
+/// Salted commitment to `proof`, as passed to `register_project`.
+fn commit_proof(env: &Env, proof: &ContentId, salt: &BytesN<32>) -> ContentId {
+    let mut preimage = proof.digest.clone();
+    preimage.append(&Bytes::from_array(env, &salt.to_array()));
+    ContentId::sha2_256(env.crypto().sha256(&preimage).into())
+}
+
 // ── 1. Registration Fuzz Tests ──────────────────────────────────────
 
 proptest! {
@@ -39,17 +52,18 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &[7u8; 32]);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &[7u8; 32]));
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let project = client.register_project(
             &creator,
             &tokens,
             &goal,
-            &proof_hash,
+            &proof,
             &deadline,
         );
 
@@ -66,17 +80,18 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &[8u8; 32]);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &[8u8; 32]));
         let deadline = env.ledger().timestamp() + offset;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let project = client.register_project(
             &creator,
             &tokens,
             &100,
-            &proof_hash,
+            &proof,
             &deadline,
         );
 
@@ -92,22 +107,23 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &hash_bytes);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &hash_bytes));
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let project = client.register_project(
             &creator,
             &tokens,
             &1000,
-            &proof_hash,
+            &proof,
             &deadline,
         );
 
         assert_all_project_invariants(&project);
-        assert_eq!(project.proof_hash, proof_hash);
+        assert_eq!(project.proof, proof);
     }
 }
 
@@ -124,17 +140,18 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token_client = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &[1u8; 32]);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &[1u8; 32]));
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token_client.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let project = client.register_project(
             &creator,
             &tokens,
             &100_000,
-            &proof_hash,
+            &proof,
             &deadline,
         );
 
@@ -162,17 +179,18 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token_client = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &[2u8; 32]);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &[2u8; 32]));
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token_client.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let project = client.register_project(
             &creator,
             &tokens,
             &1_000_000,
-            &proof_hash,
+            &proof,
             &deadline,
         );
 
@@ -218,25 +236,27 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &stored_bytes);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &stored_bytes));
+        let salt = BytesN::from_array(&env, &[0x5au8; 32]);
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let project = client.register_project(
             &creator,
             &tokens,
             &500,
-            &proof_hash,
+            &commit_proof(&env, &proof, &salt),
             &deadline,
         );
 
         let oracle = Address::generate(&env);
         client.set_oracle(&admin, &oracle);
 
-        let wrong_hash = BytesN::from_array(&env, &submitted_bytes);
-        let result = client.try_verify_and_release(&oracle, &project.id, &wrong_hash);
+        let wrong_hash = ContentId::sha2_256(BytesN::from_array(&env, &submitted_bytes));
+        let result = client.try_verify_and_release(&oracle, &project.id, &wrong_hash, &salt);
         prop_assert!(result.is_err(), "verify_and_release should fail with wrong hash");
     }
 
@@ -250,24 +270,26 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &hash_bytes);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &hash_bytes));
+        let salt = BytesN::from_array(&env, &[0x5au8; 32]);
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let project = client.register_project(
             &creator,
             &tokens,
             &500,
-            &proof_hash,
+            &commit_proof(&env, &proof, &salt),
             &deadline,
         );
 
         let oracle = Address::generate(&env);
         client.set_oracle(&admin, &oracle);
 
-        client.verify_and_release(&oracle, &project.id, &proof_hash);
+        client.verify_and_release(&oracle, &project.id, &proof, &salt);
 
         let updated = client.get_project(&project.id);
         assert_valid_status_transition(&ProjectStatus::Funding, &updated.status);
@@ -285,12 +307,14 @@ proptest! {
         let (env, client, admin) = setup_env();
         let token_admin = Address::generate(&env);
         let token = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &[1u8; 32]);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &[1u8; 32]));
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
+
         let mut projects = Vec::new();
         for _ in 0..n {
             let creator = Address::generate(&env);
@@ -300,7 +324,7 @@ proptest! {
                 &creator,
                 &tokens,
                 &1000,
-                &proof_hash,
+                &proof,
                 &deadline,
             );
             projects.push(p);
@@ -323,17 +347,18 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token_client = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &[5u8; 32]);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &[5u8; 32]));
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token_client.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let original = client.register_project(
             &creator,
             &tokens,
             &100_000,
-            &proof_hash,
+            &proof,
             &deadline,
         );
 
@@ -356,23 +381,25 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &hash_bytes);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &hash_bytes));
+        let salt = BytesN::from_array(&env, &[0x5au8; 32]);
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token.address.clone());
 
+        allow_tokens(&client, &admin, &tokens);
         let original = client.register_project(
             &creator,
             &tokens,
             &500,
-            &proof_hash,
+            &commit_proof(&env, &proof, &salt),
             &deadline,
         );
 
         let oracle = Address::generate(&env);
         client.set_oracle(&admin, &oracle);
-        client.verify_and_release(&oracle, &original.id, &proof_hash);
+        client.verify_and_release(&oracle, &original.id, &proof, &salt);
 
         let after = client.get_project(&original.id);
         assert_project_immutable_fields(&original, &after);
@@ -397,18 +424,20 @@ proptest! {
 
         let token_admin = Address::generate(&env);
         let token_client = create_token(&env, &token_admin);
-        let proof_hash = BytesN::from_array(&env, &hash_bytes);
+        let proof = ContentId::sha2_256(BytesN::from_array(&env, &hash_bytes));
+        let salt = BytesN::from_array(&env, &[0x5au8; 32]);
         let deadline = env.ledger().timestamp() + deadline_offset;
 
         let mut tokens = SorobanVec::new(&env);
         tokens.push_back(token_client.address.clone());
 
         // Phase 1: Register project.
+        allow_tokens(&client, &admin, &tokens);
         let project = client.register_project(
             &creator,
             &tokens,
             &goal,
-            &proof_hash,
+            &commit_proof(&env, &proof, &salt),
             &deadline,
         );
         assert_all_project_invariants(&project);
@@ -441,7 +470,7 @@ proptest! {
         // Phase 3: Oracle verification.
         let oracle = Address::generate(&env);
         client.set_oracle(&admin, &oracle);
-        client.verify_and_release(&oracle, &project.id, &proof_hash);
+        client.verify_and_release(&oracle, &project.id, &proof, &salt);
 
         let final_project = client.get_project(&project.id);
         assert_valid_status_transition(&ProjectStatus::Funding, &final_project.status);
@@ -453,7 +482,7 @@ proptest! {
         assert_eq!(post_verify_balance, 0);
 
         // Phase 4: Double-verify should fail.
-        let result = client.try_verify_and_release(&oracle, &project.id, &proof_hash);
+        let result = client.try_verify_and_release(&oracle, &project.id, &proof, &salt);
         prop_assert!(result.is_err(), "double verification should fail");
     }
 }
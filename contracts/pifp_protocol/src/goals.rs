@@ -0,0 +1,61 @@
+//! # Goals
+//!
+//! Decides whether a project has reached its funding goal.
+//!
+//! A project carries a headline `goal`, an optional map of per-token goals
+//! and a [`GoalRule`] chosen at registration. The rule is evaluated against
+//! the escrowed per-token balances after every deposit, and by
+//! `get_project_balances` for progress reporting.
+//!
+//! [`GoalRule::QuoteValue`] values every accepted token's balance through a
+//! SEP-40 price feed (see [`crate::price_feed`]) at the time of the check.
+
+use soroban_sdk::{Address, Env, Map};
+
+use crate::price_feed;
+use crate::storage;
+use crate::types::{GoalRule, QuoteGoal};
+
+/// Basis-point denominator used by [`GoalRule::WeightedSum`] weights.
+pub const WEIGHT_DENOMINATOR: i128 = 10_000;
+
+/// Return true if the goal of `project_id` is met by its current balances.
+pub fn is_goal_reached(
+    env: &Env,
+    project_id: u64,
+    goal: i128,
+    token_goals: &Map<Address, i128>,
+    rule: &GoalRule,
+) -> bool {
+    match rule {
+        GoalRule::AnyToken => token_goals
+            .iter()
+            .any(|(token, target)| storage::get_token_balance(env, project_id, &token) >= target),
+        GoalRule::AllTokens => {
+            !token_goals.is_empty()
+                && token_goals.iter().all(|(token, target)| {
+                    storage::get_token_balance(env, project_id, &token) >= target
+                })
+        }
+        GoalRule::WeightedSum(weights) => {
+            let mut total: i128 = 0;
+            for (token, weight) in weights.iter() {
+                let raised = storage::get_token_balance(env, project_id, &token);
+                total = total
+                    .saturating_add(raised.saturating_mul(weight as i128) / WEIGHT_DENOMINATOR);
+            }
+            total >= goal
+        }
+        GoalRule::QuoteValue(quote) => quote_value(env, project_id, quote) >= goal,
+    }
+}
+
+/// Value of all escrowed balances of `project_id` in the quote currency.
+pub fn quote_value(env: &Env, project_id: u64, quote: &QuoteGoal) -> i128 {
+    let mut total: i128 = 0;
+    for token in storage::get_accepted_tokens(env, project_id).iter() {
+        let raised = storage::get_token_balance(env, project_id, &token);
+        total = total.saturating_add(price_feed::quote_value(env, quote, &token, raised));
+    }
+    total
+}
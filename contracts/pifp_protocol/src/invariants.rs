@@ -67,10 +67,14 @@ pub fn assert_sequential_ids(projects: &[Project]) {
 }
 
 /// INV-7: Status transition validity. Only forward transitions are allowed:
-///   Funding -> Active | Completed | Expired
-///   Active  -> Completed | Expired
+///   Funding -> Active | Completed | Expired | Cancelled | Voting
+///   Active  -> Completed | Expired | Cancelled | Voting
+///   Voting  -> Completed | Cancelled
+///   Funding | Active -> UnderReview -> Completed | Cancelled
+///   UnderReview -> Funding | Active (proof rejected)
+///   Expired   -> Cancelled
 ///   Completed -> (none)
-///   Expired   -> (none)
+///   Cancelled -> (none)
 pub fn assert_valid_status_transition(from: &ProjectStatus, to: &ProjectStatus) {
     let valid = matches!(
         (from, to),
@@ -79,6 +83,19 @@ pub fn assert_valid_status_transition(from: &ProjectStatus, to: &ProjectStatus)
             | (ProjectStatus::Funding, ProjectStatus::Expired)
             | (ProjectStatus::Active, ProjectStatus::Completed)
             | (ProjectStatus::Active, ProjectStatus::Expired)
+            | (ProjectStatus::Funding, ProjectStatus::Cancelled)
+            | (ProjectStatus::Active, ProjectStatus::Cancelled)
+            | (ProjectStatus::Expired, ProjectStatus::Cancelled)
+            | (ProjectStatus::Funding, ProjectStatus::Voting)
+            | (ProjectStatus::Active, ProjectStatus::Voting)
+            | (ProjectStatus::Voting, ProjectStatus::Completed)
+            | (ProjectStatus::Voting, ProjectStatus::Cancelled)
+            | (ProjectStatus::Funding, ProjectStatus::UnderReview)
+            | (ProjectStatus::Active, ProjectStatus::UnderReview)
+            | (ProjectStatus::UnderReview, ProjectStatus::Completed)
+            | (ProjectStatus::UnderReview, ProjectStatus::Cancelled)
+            | (ProjectStatus::UnderReview, ProjectStatus::Funding)
+            | (ProjectStatus::UnderReview, ProjectStatus::Active)
     );
 
     assert!(
@@ -89,7 +106,10 @@ pub fn assert_valid_status_transition(from: &ProjectStatus, to: &ProjectStatus)
 }
 
 /// INV-8: Project data immutability — fields that should not change after
-/// creation (creator, token, goal, proof_hash, deadline) remain unchanged.
+/// creation (creator, goal, proof, deadline) remain unchanged. The
+/// deadline may only move through an approved `propose_extension`. The token
+/// list may only change through `add_accepted_token` / `remove_accepted_token`,
+/// so it must also be unchanged across deposits and verification.
 pub fn assert_project_immutable_fields(original: &Project, current: &Project) {
     assert_eq!(
         original.id, current.id,
@@ -108,8 +128,8 @@ pub fn assert_project_immutable_fields(original: &Project, current: &Project) {
         "INV-8 violated: project goal changed"
     );
     assert_eq!(
-        original.proof_hash, current.proof_hash,
-        "INV-8 violated: project proof_hash changed"
+        original.proof, current.proof,
+        "INV-8 violated: project proof changed"
     );
     assert_eq!(
         original.deadline, current.deadline,
@@ -130,14 +150,28 @@ pub fn assert_donation_count_monotonic(count_before: u32, count_after: u32) {
 
 /// INV-10: donation_count must be non-negative (this is enforced by u32 type,
 /// but we include it for documentation completeness).
-pub fn assert_donation_count_non_negative(project: &Project) {
-    // donation_count is u32, so it's always >= 0 by type definition
-    // This assertion is a no-op but documents the invariant
+pub fn assert_donation_count_non_negative(_project: &Project) {
+    // donation_count is u32, so it's always >= 0 by type definition.
+    // This check is a no-op but documents the invariant.
+}
+
+/// INV-11: donor metrics are ordered: every unique donor has at least one
+/// (donor, token) pair, and every pair has at least one deposit.
+pub fn assert_donor_metrics_ordered(project: &Project) {
     assert!(
-        true,
-        "INV-10: donation_count is {} (always non-negative by type)",
+        project.unique_donors <= project.donation_count,
+        "INV-11 violated: project {} has {} unique donors but {} donor-token pairs",
+        project.id,
+        project.unique_donors,
         project.donation_count
     );
+    assert!(
+        project.donation_count <= project.deposit_count,
+        "INV-11 violated: project {} has {} donor-token pairs but {} deposits",
+        project.id,
+        project.donation_count,
+        project.deposit_count
+    );
 }
 
 /// Run all stateless project invariants.
@@ -147,4 +181,5 @@ pub fn assert_all_project_invariants(project: &Project) {
     assert_deadline_positive(project);
     assert_completed_has_valid_state(project);
     assert_donation_count_non_negative(project);
+    assert_donor_metrics_ordered(project);
 }
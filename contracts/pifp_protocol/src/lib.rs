@@ -8,16 +8,26 @@
 //! |--------------|---------------------------------------------|
 //! | Bootstrap    | [`PifpProtocol::init`]                      |
 //! | Role admin   | `grant_role`, `revoke_role`, `transfer_super_admin`, `set_oracle` |
-//! | Registration | [`PifpProtocol::register_project`]          |
-//! | Funding      | [`PifpProtocol::deposit`]                   |
-//! | Verification | [`PifpProtocol::verify_and_release`]        |
-//! | Queries      | `get_project`, `get_project_balances`, `role_of`, `has_role` |
+//! | Emergency    | `pause`, `unpause`                              |
+//! | Token policy | `allow_token`, `disallow_token`                 |
+//! | Parameters   | `set_params`, `get_params`                      |
+//! | Registration | [`PifpProtocol::register_project`], `register_project_with_options`, `register_projects`, `add_accepted_token`, `remove_accepted_token` |
+//! | Funding      | [`PifpProtocol::deposit`], `deposit_batch`, `set_keeper`, `deposit_from`, `donate_committed`, `reveal_donation`, `withdraw_donation`, `refresh_goal` |
+//! | Subscriptions | `subscribe`, `execute_subscription`, `cancel_subscription` |
+//! | Matching     | `create_match`, `release_match`, `create_round`, `join_round`, `close_round`, `reclaim_round_match` |
+//! | Verification | [`PifpProtocol::verify_and_release`], `verify_with_evidence`, `submit_proof`, `reject_proof`, `add_evidence` |
+//! | Donor votes  | `propose_extension`, `vote_extension`, `finalize_extension`, `start_release_vote`, `vote_release`, `finalize_release_vote` |
+//! | Cancellation | `cancel_project`, `claim_refund`                |
+//! | Queries      | `get_project`, `get_project_balances`, `get_balance`, `get_contribution`, `list_donors`, `list_tokens`, `get_token_metadata`, `role_of`, `has_role`, `is_paused`, `get_subscription`, `get_match`, `get_round`, `get_round_project`, `get_committed_donation`, `get_evidence`, `list_evidence`, `get_extension`, `get_release_vote` |
 //!
 //! ## Architecture
 //!
 //! Authorization is fully delegated to [`rbac`].  Storage access is fully
-//! delegated to [`storage`].  This file contains **only** the public entry
-//! points and event emissions — no business logic lives here directly.
+//! delegated to [`storage`].  This file holds the public entry points, their
+//! validation and state transitions, and event emission; self-contained
+//! calculations live in their own modules — goal evaluation in `goals`,
+//! matching pledges in `pledges`, quadratic rounds in `rounds`, donor votes
+//! in `votes` and proof commitments in [`proofs`].
 //!
 //! See [`ARCHITECTURE.md`](../../../../ARCHITECTURE.md) for the full system
 //! architecture and threat model.
@@ -25,13 +35,20 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, panic_with_error, token, Address, BytesN, Env, Vec,
+    contract, contracterror, contractimpl, panic_with_error, token, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Vec,
 };
 
 pub mod events;
+mod goals;
+mod pledges;
+pub mod price_feed;
+pub mod proofs;
 pub mod rbac;
+mod rounds;
 mod storage;
 mod types;
+mod votes;
 
 #[cfg(test)]
 mod fuzz_test;
@@ -42,9 +59,57 @@ mod rbac_test;
 #[cfg(test)]
 mod test;
 #[cfg(test)]
-mod test_events;
+mod test_accepted_tokens;
+#[cfg(test)]
+mod test_cancellation;
+#[cfg(test)]
+mod test_committed_donations;
+#[cfg(test)]
+mod test_contributions;
+#[cfg(test)]
+mod test_deposit_batch;
+#[cfg(test)]
+mod test_deposit_from;
 #[cfg(test)]
 mod test_donation_count;
+#[cfg(test)]
+mod test_events;
+#[cfg(test)]
+mod test_evidence;
+#[cfg(test)]
+mod test_extensions;
+#[cfg(test)]
+mod test_funding_mode;
+#[cfg(test)]
+mod test_hard_cap;
+#[cfg(test)]
+mod test_matching;
+#[cfg(test)]
+mod test_price_goals;
+#[cfg(test)]
+mod test_proof_commitment;
+#[cfg(test)]
+mod test_proof_review;
+#[cfg(test)]
+mod test_protocol_params;
+#[cfg(test)]
+mod test_reentrancy;
+#[cfg(test)]
+mod test_register_batch;
+#[cfg(test)]
+mod test_release_votes;
+#[cfg(test)]
+mod test_rounds;
+#[cfg(test)]
+mod test_subscriptions;
+#[cfg(test)]
+mod test_token_allowlist;
+#[cfg(test)]
+mod test_token_goals;
+#[cfg(test)]
+mod test_util;
+#[cfg(test)]
+mod test_withdrawals;
 
 pub use events::emit_funds_released;
 pub use rbac::Role;
@@ -52,7 +117,14 @@ use storage::{
     drain_token_balance, get_all_balances, get_and_increment_project_id, load_project,
     load_project_pair, maybe_load_project, save_project, save_project_state,
 };
-pub use types::{Project, ProjectBalances, ProjectStatus};
+pub use types::{
+    CommittedDonation, ContentId, Contribution, EvidenceEntry, EvidenceKind, ExcessPolicy,
+    ExtensionProposal, ExtensionStatus, FundingMode, GoalRule, MatchPledge, Project,
+    ProjectBalances, ProjectInput, ProjectOptions, ProjectStatus, ProofReview, ProofSubmission,
+    ProtocolParams, QuoteGoal, ReleaseVote, ReleaseVoteStatus, Round, RoundProject, RoundStatus,
+    Subscription, SubscriptionStatus, TokenMetadata, VerificationMode,
+};
+use types::{ProjectConfig, ProjectState};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -78,8 +150,74 @@ pub enum Error {
     Overflow = 18,
     ProtocolPaused = 19,
     GoalMismatch = 20,
+    Reentrancy = 21,
+    TokenNotAllowed = 22,
+    BelowMinDeposit = 23,
+    InvalidParams = 24,
+    TokenBalanceNotZero = 25,
+    TokenNotAccepted = 26,
+    TokenHasGoal = 27,
+    GoalNotReached = 28,
+    CapExceeded = 29,
+    CoolingOffExceeded = 30,
+    CommitmentExists = 31,
+    CommitmentNotFound = 32,
+    AlreadyRevealed = 33,
+    InvalidBatch = 34,
+    InvalidSubscription = 35,
+    SubscriptionNotFound = 36,
+    SubscriptionInactive = 37,
+    PaymentNotDue = 38,
+    InvalidRound = 39,
+    RoundNotFound = 40,
+    RoundClosed = 41,
+    AlreadyInRound = 42,
+    RoundFull = 43,
+    RoundNotEnded = 44,
+    InvalidMatch = 45,
+    MatchNotFound = 46,
+    MatchActive = 47,
+    MatchSettled = 48,
+    TooManyMatches = 49,
+    NotRefundable = 50,
+}
+
+/// Errors added once [`Error`] reached the 50 cases a contract error enum
+/// may hold. Codes continue from 51 so that every code stays unique.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    ExtensionPending = 51,
+    ExtensionNotFound = 52,
+    VotingClosed = 53,
+    VotingOpen = 54,
+    NoVotingPower = 55,
+    WrongVerificationMode = 56,
+    VoteNotFound = 57,
+    NotUnderReview = 58,
+    EvidenceNotFound = 59,
+    InvalidContentId = 60,
+    ProofAlgorithmMismatch = 61,
 }
 
+/// Hard upper bound for `ProtocolParams::max_accepted_tokens`.
+///
+/// Several entry points iterate over a project's token list, so this keeps
+/// their cost bounded whatever the governed limit is set to.
+pub const MAX_ACCEPTED_TOKENS_CEILING: u32 = 20;
+
+/// Maximum number of allocations in one `deposit_batch` call.
+pub const MAX_BATCH_SIZE: u32 = 20;
+
+/// Maximum number of projects in one matching round, which bounds the work
+/// done by `close_round`.
+pub const MAX_ROUND_PROJECTS: u32 = 50;
+
+/// Maximum number of unsettled matching pledges per project; every deposit
+/// walks this list.
+pub const MAX_PROJECT_MATCHES: u32 = 10;
+
 #[contract]
 pub struct PifpProtocol;
 
@@ -167,6 +305,108 @@ impl PifpProtocol {
         storage::is_paused(&env)
     }
 
+    // ─────────────────────────────────────────────────────────
+    // Protocol parameters
+    // ─────────────────────────────────────────────────────────
+
+    /// Return the protocol parameters applied to new registrations.
+    pub fn get_params(env: Env) -> ProtocolParams {
+        storage::get_params(&env)
+    }
+
+    /// Replace the protocol parameters.
+    ///
+    /// The new values apply to projects registered afterwards; existing
+    /// projects keep the version they were created under. TTL values take
+    /// effect immediately for every storage entry.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - Panics with `Error::InvalidParams` if any limit is inconsistent.
+    pub fn set_params(env: Env, caller: Address, params: ProtocolParams) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+
+        let valid = params.max_accepted_tokens >= 1
+            && params.max_accepted_tokens <= MAX_ACCEPTED_TOKENS_CEILING
+            && params.max_goal > 0
+            && params.max_deadline_duration > 0
+            && params.min_deadline_duration < params.max_deadline_duration
+            && params.instance_lifetime_threshold <= params.instance_bump_amount
+            && params.persistent_lifetime_threshold <= params.persistent_bump_amount
+            && params.instance_bump_amount > 0
+            && params.persistent_bump_amount > 0
+            && params.cooling_off_period <= params.max_deadline_duration
+            && params.extension_window > 0
+            && params.extension_objection_bps as i128 <= votes::BPS_DENOMINATOR
+            && params.release_vote_window > 0
+            && params.release_quorum_bps as i128 <= votes::BPS_DENOMINATOR
+            && params.release_majority_bps >= 5_000
+            && (params.release_majority_bps as i128) < votes::BPS_DENOMINATOR;
+        if !valid {
+            panic_with_error!(&env, Error::InvalidParams);
+        }
+
+        let version = storage::set_params(&env, &params);
+        events::emit_params_updated(&env, caller, version, params);
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Token allowlist
+    // ─────────────────────────────────────────────────────────
+
+    /// Add `token` to the protocol allowlist, or update its metadata.
+    ///
+    /// Only allowlisted tokens may be passed to `register_project` or
+    /// deposited. `metadata.min_deposit`, when non-zero, is enforced by
+    /// `deposit`.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn allow_token(env: Env, caller: Address, token: Address, metadata: Option<TokenMetadata>) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        if let Some(ref m) = metadata {
+            if m.min_deposit < 0 {
+                panic_with_error!(&env, Error::InvalidAmount);
+            }
+        }
+        storage::allow_token(&env, &token, &metadata);
+        events::emit_token_allowed(&env, token, metadata);
+    }
+
+    /// Remove `token` from the protocol allowlist.
+    ///
+    /// Delisting blocks new registrations and deposits in `token`, but funds
+    /// already escrowed in it are still released by `verify_and_release`.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - Panics with `Error::TokenNotAllowed` if `token` is not listed.
+    pub fn disallow_token(env: Env, caller: Address, token: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        if !storage::disallow_token(&env, &token) {
+            panic_with_error!(&env, Error::TokenNotAllowed);
+        }
+        events::emit_token_disallowed(&env, token);
+    }
+
+    /// Return true if `token` is on the protocol allowlist.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        storage::is_token_allowed(&env, &token)
+    }
+
+    /// Return the metadata registered for an allowlisted token, if any.
+    pub fn get_token_metadata(env: Env, token: Address) -> Option<TokenMetadata> {
+        storage::get_token_metadata(&env, &token)
+    }
+
+    /// Return up to `limit` allowlisted tokens starting at index `cursor`.
+    ///
+    /// `limit` is capped at 50. Ordering is stable except that delisting a
+    /// token moves the last entry into its slot.
+    pub fn list_tokens(env: Env, cursor: u32, limit: u32) -> Vec<Address> {
+        storage::list_allowed_tokens(&env, cursor, limit)
+    }
+
     // ─────────────────────────────────────────────────────────
     // Project lifecycle
     // ─────────────────────────────────────────────────────────
@@ -174,72 +414,194 @@ impl PifpProtocol {
     /// Register a new funding project.
     ///
     /// `creator` must hold the `ProjectManager`, `Admin`, or `SuperAdmin` role.
+    /// Every entry of `accepted_tokens` must be on the protocol allowlist.
+    /// `goal` is the goal of the first accepted token; use
+    /// `register_project_with_options` for per-token goals.
+    ///
+    /// `proof.code` is the multihash code of the expected proof artifact's
+    /// content identifier, and `proof.digest` the 32-byte commitment
+    /// `sha256(artifact_digest || salt)`, where `salt` is a secret 32-byte
+    /// value. The digest and salt stay off-chain until verification, so the
+    /// expected proof cannot be read from the project. Panics with
+    /// `GovernanceError::InvalidContentId` for an unknown code or a digest
+    /// that is not 32 bytes.
     pub fn register_project(
         env: Env,
         creator: Address,
         accepted_tokens: Vec<Address>,
         goal: i128,
-        proof_hash: BytesN<32>,
+        proof: ContentId,
+        deadline: u64,
+    ) -> Project {
+        let options = ProjectOptions::default_for(&env);
+        Self::register_project_with_options(
+            env,
+            creator,
+            accepted_tokens,
+            goal,
+            proof,
+            deadline,
+            options,
+        )
+    }
+
+    /// Register a new funding project with optional settings.
+    ///
+    /// Behaves like `register_project`, and additionally:
+    ///
+    /// - `options.token_goals` sets a goal per accepted token. Each key must
+    ///   be an accepted token and each goal must be within `(0, max_goal]`.
+    ///   When empty, `goal` is used as the first accepted token's goal,
+    ///   except under `WeightedSum` and `QuoteValue`, whose `goal` is not a
+    ///   token amount.
+    /// - `options.goal_rule` combines per-token progress into completion.
+    ///   `WeightedSum` weights must be positive and keyed by accepted tokens.
+    ///   `QuoteValue` needs a non-zero `max_price_age`; its `goal` is in the
+    ///   feed's quote currency, and its `decimals` must match the feed's.
+    /// - `options.funding_mode` decides whether funds below the goal can be
+    ///   released. `register_project` uses `Flexible`.
+    /// - `options.max_raise` caps the escrowed balance per accepted token.
+    ///   Each cap must be positive and at least that token's goal.
+    ///
+    /// Panics with `Error::InvalidGoal` if the goal settings are inconsistent.
+    pub fn register_project_with_options(
+        env: Env,
+        creator: Address,
+        accepted_tokens: Vec<Address>,
+        goal: i128,
+        proof: ContentId,
         deadline: u64,
+        options: ProjectOptions,
     ) -> Project {
         Self::require_not_paused(&env);
         creator.require_auth();
         // RBAC gate: only authorised roles may create projects.
         rbac::require_can_register(&env, &creator);
 
-        if accepted_tokens.is_empty() {
-            panic_with_error!(&env, Error::EmptyAcceptedTokens);
-        }
-        if accepted_tokens.len() > 10 {
-            panic_with_error!(&env, Error::TooManyTokens);
+        let params = storage::get_params(&env);
+        let id = get_and_increment_project_id(&env, 1);
+        let input = ProjectInput {
+            accepted_tokens,
+            goal,
+            proof,
+            deadline,
+            options,
+        };
+        let project = Self::new_project(&env, &params, id, &creator, input);
+        Self::store_new_project(&env, &project);
+        project
+    }
+
+    /// Register several projects for `creator` in one call.
+    ///
+    /// Each entry is validated exactly as by `register_project_with_options`.
+    /// Auth, pause and role checks run once, and the projects get contiguous
+    /// IDs reserved with a single counter write. The call is atomic: one
+    /// invalid entry reverts the whole batch.
+    ///
+    /// - Panics with `Error::InvalidBatch` if `inputs` is empty or longer
+    ///   than [`MAX_BATCH_SIZE`].
+    pub fn register_projects(
+        env: Env,
+        creator: Address,
+        inputs: Vec<ProjectInput>,
+    ) -> Vec<Project> {
+        Self::require_not_paused(&env);
+        creator.require_auth();
+        rbac::require_can_register(&env, &creator);
+
+        if inputs.is_empty() || inputs.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, Error::InvalidBatch);
         }
 
-        // Check for duplicate tokens
-        for i in 0..accepted_tokens.len() {
-            let t_i = accepted_tokens.get(i).unwrap();
-            for j in (i + 1)..accepted_tokens.len() {
-                if t_i == accepted_tokens.get(j).unwrap() {
-                    panic_with_error!(&env, Error::DuplicateToken);
-                }
-            }
+        let params = storage::get_params(&env);
+        let first_id = get_and_increment_project_id(&env, inputs.len() as u64);
+        let mut projects = Vec::new(&env);
+        for (offset, input) in inputs.iter().enumerate() {
+            let project =
+                Self::new_project(&env, &params, first_id + offset as u64, &creator, input);
+            projects.push_back(project);
+        }
+        for project in projects.iter() {
+            Self::store_new_project(&env, &project);
         }
+        projects
+    }
+
+    pub fn get_project(env: Env, id: u64) -> Project {
+        load_project(&env, id)
+    }
+
+    /// Add `token` to the project's accepted tokens.
+    ///
+    /// - Must be authorised by the project creator.
+    /// - The project must be in `Funding`.
+    /// - `token` must be allowlisted and not already accepted, and the list
+    ///   may not exceed the `max_accepted_tokens` limit the project was
+    ///   registered under.
+    pub fn add_accepted_token(env: Env, project_id: u64, token: Address) {
+        Self::require_not_paused(&env);
+        let (config, state) = load_project_pair(&env, project_id);
+        config.creator.require_auth();
 
-        if goal <= 0 || goal > 1_000_000_000_000_000_000_000_000_000_000i128 {
-            // 10^30
-            panic_with_error!(&env, Error::InvalidGoal);
+        if state.status != ProjectStatus::Funding {
+            panic_with_error!(&env, Error::ProjectNotActive);
         }
 
-        let now = env.ledger().timestamp();
-        // Max 5 years deadline (5 * 365 * 24 * 60 * 60)
-        let max_deadline = now + 157_680_000;
-        if deadline <= now || deadline > max_deadline {
-            panic_with_error!(&env, Error::InvalidDeadline);
+        let mut tokens = storage::get_accepted_tokens(&env, project_id);
+        let params = storage::get_params_at(&env, config.params_version);
+        if tokens.len() >= params.max_accepted_tokens {
+            panic_with_error!(&env, Error::TooManyTokens);
+        }
+        if tokens.contains(&token) {
+            panic_with_error!(&env, Error::DuplicateToken);
+        }
+        if !storage::is_token_allowed(&env, &token) {
+            panic_with_error!(&env, Error::TokenNotAllowed);
         }
 
-        let id = get_and_increment_project_id(&env);
-        let project = Project {
-            id,
-            creator: creator.clone(),
-            accepted_tokens: accepted_tokens.clone(),
-            goal,
-            proof_hash,
-            deadline,
-            status: ProjectStatus::Funding,
-            donation_count: 0,
-        };
+        tokens.push_back(token.clone());
+        storage::set_accepted_tokens(&env, project_id, &tokens);
+        storage::set_token_balance(&env, project_id, &token, 0);
 
-        save_project(&env, &project);
+        events::emit_accepted_token_added(&env, project_id, token);
+    }
 
-        // Standardized event emission
-        if let Some(token) = accepted_tokens.get(0) {
-            events::emit_project_created(&env, id, creator, token, goal);
+    /// Remove `token` from the project's accepted tokens.
+    ///
+    /// - Must be authorised by the project creator.
+    /// - The project must be in `Funding`.
+    /// - The project's escrowed balance of `token` must be zero, so no funds
+    ///   can be stranded, and at least one token must remain.
+    pub fn remove_accepted_token(env: Env, project_id: u64, token: Address) {
+        Self::require_not_paused(&env);
+        let (config, state) = load_project_pair(&env, project_id);
+        config.creator.require_auth();
+
+        if state.status != ProjectStatus::Funding {
+            panic_with_error!(&env, Error::ProjectNotActive);
         }
 
-        project
-    }
+        let mut tokens = storage::get_accepted_tokens(&env, project_id);
+        let index = match tokens.first_index_of(&token) {
+            Some(i) => i,
+            None => panic_with_error!(&env, Error::TokenNotAccepted),
+        };
+        if tokens.len() == 1 {
+            panic_with_error!(&env, Error::EmptyAcceptedTokens);
+        }
+        if storage::get_token_balance(&env, project_id, &token) != 0 {
+            panic_with_error!(&env, Error::TokenBalanceNotZero);
+        }
+        if config.token_goals.contains_key(token.clone()) {
+            panic_with_error!(&env, Error::TokenHasGoal);
+        }
 
-    pub fn get_project(env: Env, id: u64) -> Project {
-        load_project(&env, id)
+        tokens.remove(index);
+        storage::set_accepted_tokens(&env, project_id, &tokens);
+        storage::remove_token_balance(&env, project_id, &token);
+
+        events::emit_accepted_token_removed(&env, project_id, token);
     }
 
     /// Return the balance of `token` for `project_id`.
@@ -247,10 +609,33 @@ impl PifpProtocol {
         storage::get_token_balance(&env, project_id, &token)
     }
 
-    /// Return the current per-token balances for a project.
+    /// Move a `Funding` project to `Active` if its goal is met now.
+    ///
+    /// Goals are otherwise only checked on deposit, but a `QuoteValue` goal
+    /// can be met by prices rising alone. Callable by anyone.
+    ///
+    /// Returns true if this call reached the goal.
+    pub fn refresh_goal(env: Env, project_id: u64) -> bool {
+        Self::require_not_paused(&env);
+        let (config, mut state) = load_project_pair(&env, project_id);
+
+        // The price feed is an external contract.
+        Self::acquire_lock(&env);
+        let goal_reached = Self::update_goal_status(&env, &config, &mut state);
+        Self::release_lock(&env);
+
+        if goal_reached {
+            save_project_state(&env, project_id, &state);
+            events::emit_goal_reached(&env, project_id);
+        }
+        goal_reached
+    }
+
+    /// Return the current per-token balances and goal progress for a project.
     ///
     /// Reconstructs the balance snapshot from persistent storage for every
-    /// token that was accepted at registration time.
+    /// accepted token, pairing each balance with that token's goal (`0` when
+    /// it has none), and reports whether the project's `GoalRule` is met.
     ///
     /// # Errors
     /// Panics with `Error::ProjectNotFound` if `project_id` does not exist.
@@ -262,147 +647,2075 @@ impl PifpProtocol {
         get_all_balances(&env, &project)
     }
 
+    /// Return the net contributions of `donor` to a project, per accepted token.
+    ///
+    /// Amounts are net of cooling-off withdrawals and are kept after funds
+    /// are released. Tokens the donor never gave in are omitted.
+    pub fn get_contribution(env: Env, project_id: u64, donor: Address) -> Vec<Contribution> {
+        if !storage::project_exists(&env, project_id) {
+            panic_with_error!(&env, Error::ProjectNotFound);
+        }
+        let tokens = storage::get_accepted_tokens(&env, project_id);
+        storage::get_contributions(&env, project_id, &donor, &tokens)
+    }
+
+    /// Return up to `limit` donors of a project starting at index `cursor`.
+    ///
+    /// Donors are listed in order of their first deposit; `limit` is capped
+    /// at 50. A donor who withdrew everything stays listed.
+    ///
+    /// # Errors
+    /// Panics with `Error::ProjectNotFound` if `project_id` does not exist.
+    pub fn list_donors(env: Env, project_id: u64, cursor: u32, limit: u32) -> Vec<Address> {
+        if !storage::project_exists(&env, project_id) {
+            panic_with_error!(&env, Error::ProjectNotFound);
+        }
+        storage::list_donors(&env, project_id, cursor, limit)
+    }
+
     /// Deposit funds into a project.
     ///
-    /// The `token` must be one of the project's accepted tokens.
-    pub fn deposit(env: Env, project_id: u64, donator: Address, token: Address, amount: i128) {
+    /// The `token` must be one of the project's accepted tokens and must
+    /// still be on the protocol allowlist.
+    ///
+    /// If `token` has a `max_raise` cap, only the remaining headroom can be
+    /// deposited. Under `ExcessPolicy::Reject` a deposit above the headroom
+    /// panics with `Error::CapExceeded`; under `AcceptPartial` it is trimmed
+    /// to the headroom. A token at its cap refuses all further deposits.
+    ///
+    /// Returns the amount actually transferred from `donator`.
+    pub fn deposit(
+        env: Env,
+        project_id: u64,
+        donator: Address,
+        token: Address,
+        amount: i128,
+    ) -> i128 {
         Self::require_not_paused(&env);
         donator.require_auth();
 
-        if amount <= 0 {
-            panic_with_error!(&env, Error::InvalidAmount);
-        }
-
         // Read both config and state with a single helper that bumps TTLs
         // atomically. This is the optimized retrieval pattern; it also returns
         // the state needed for the subsequent checks.
         let (config, mut state) = load_project_pair(&env, project_id);
+        let amount = Self::check_deposit(&env, &config, &state, &token, amount);
 
-        // Check expiration
-        if env.ledger().timestamp() >= config.deadline {
-            panic_with_error!(&env, Error::ProjectExpired);
-        }
-
-        // Basic status check: must be Funding or Active.
-        match state.status {
-            ProjectStatus::Funding | ProjectStatus::Active => {}
-            _ => panic_with_error!(&env, Error::ProjectNotActive),
-        }
-
-        // Verify token is accepted.
-        let mut found = false;
-        for t in config.accepted_tokens.iter() {
-            if t == token {
-                found = true;
-                break;
-            }
-        }
-        if !found {
-            panic_with_error!(&env, Error::NotAuthorized);
-        }
+        // Checks-effects-interactions: every storage write happens before the
+        // token contract is called, and the lock rejects any callback into
+        // the protocol while the transfer is in flight.
+        Self::acquire_lock(&env);
 
-        // Check if this is a new unique (donator, token) pair.
-        let is_new_donor = !storage::has_donator_seen(&env, project_id, &donator, &token);
-        if is_new_donor {
-            // Increment donation count and mark as seen.
-            state.donation_count += 1;
-            storage::mark_donator_seen(&env, project_id, &donator, &token);
-            // Save the updated state.
-            save_project_state(&env, project_id, &state);
-        }
+        let goal_reached =
+            Self::credit_deposit(&env, &config, &mut state, Some(&donator), &token, amount);
 
         // Transfer tokens from donator to contract.
         let token_client = token::Client::new(&env, &token);
         token_client.transfer(&donator, &env.current_contract_address(), &amount);
 
-        // Update the per-token balance.
-        storage::add_to_token_balance(&env, project_id, &token, amount);
+        Self::release_lock(&env);
 
         // Standardized event emission
         events::emit_project_funded(&env, project_id, donator, amount);
+        if goal_reached {
+            events::emit_goal_reached(&env, project_id);
+        }
+        amount
     }
 
-    /// Grant the Oracle role to `oracle`.
+    /// Authorise or revoke `keeper` to pull deposits for `donator`.
     ///
-    /// Replaces the original `set_oracle(admin, oracle)`.
-    /// - `caller` must hold `SuperAdmin` or `Admin`.
-    pub fn set_oracle(env: Env, caller: Address, oracle: Address) {
-        caller.require_auth();
-        rbac::require_admin_or_above(&env, &caller);
-        rbac::grant_role(&env, &caller, &oracle, Role::Oracle);
+    /// An approved keeper may call `deposit_from` to move funds from the
+    /// allowance `donator` has granted this contract on a token. The
+    /// allowance itself is managed on the token with `approve`.
+    pub fn set_keeper(env: Env, donator: Address, keeper: Address, approved: bool) {
+        donator.require_auth();
+        storage::set_keeper(&env, &donator, &keeper, approved);
+        events::emit_keeper_set(&env, donator, keeper, approved);
     }
 
-    /// Verify proof of impact and release funds to the creator.
+    /// Deposit on behalf of `donator` from an allowance they granted this
+    /// contract.
     ///
-    /// The registered oracle submits a proof hash. If it matches the project's
-    /// stored `proof_hash`, the project status transitions to `Completed`.
+    /// `spender_keeper` must be authorised by `donator` through `set_keeper`
+    /// and signs instead of the donor. The funds are pulled with the token's
+    /// `transfer_from`, so `donator` must have approved at least `amount` to
+    /// this contract. Otherwise behaves exactly like `deposit`: the
+    /// contribution is credited to `donator`, caps apply, and the `funded`
+    /// event names `donator`.
     ///
-    /// NOTE: This is a mocked verification (hash equality).
-    /// The structure is prepared for future ZK-STARK verification.
+    /// - Panics with `Error::NotAuthorized` if `spender_keeper` is not an
+    ///   approved keeper of `donator`.
     ///
-    /// Reads the immutable config (for proof_hash) and mutable state (for status),
-    /// then writes back only the small state entry.
-    pub fn verify_and_release(
+    /// Returns the amount actually transferred from `donator`.
+    pub fn deposit_from(
         env: Env,
-        oracle: Address,
+        spender_keeper: Address,
         project_id: u64,
-        submitted_proof_hash: BytesN<32>,
-    ) {
+        donator: Address,
+        token: Address,
+        amount: i128,
+    ) -> i128 {
         Self::require_not_paused(&env);
-        oracle.require_auth();
-        // RBAC gate: caller must hold the Oracle role.
-        rbac::require_oracle(&env, &oracle);
+        spender_keeper.require_auth();
+        if !storage::is_keeper(&env, &donator, &spender_keeper) {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
 
-        // Optimised dual-read helper
         let (config, mut state) = load_project_pair(&env, project_id);
+        let amount = Self::check_deposit(&env, &config, &state, &token, amount);
 
-        // Ensure the project is in a verifiable state.
-        match state.status {
-            ProjectStatus::Funding | ProjectStatus::Active => {}
-            ProjectStatus::Completed => panic_with_error!(&env, Error::MilestoneAlreadyReleased),
-            ProjectStatus::Expired => panic_with_error!(&env, Error::ProjectNotFound),
-        }
-
-        // Mocked ZK verification: compare submitted hash to stored hash.
-        if submitted_proof_hash != config.proof_hash {
-            panic_with_error!(&env, Error::VerificationFailed);
-        }
+        Self::acquire_lock(&env);
 
-        // Transition to Completed — only write the state entry.
-        state.status = ProjectStatus::Completed;
+        let goal_reached =
+            Self::credit_deposit(&env, &config, &mut state, Some(&donator), &token, amount);
 
-        // Transfer all deposited tokens to the creator.
-        // If any transfer fails, panic to revert the entire transaction.
-        let contract_address = env.current_contract_address();
-        for token in config.accepted_tokens.iter() {
-            // Drain the token balance (gets balance and zeros it).
-            let balance = drain_token_balance(&env, project_id, &token);
+        // The contract is the approved spender; as the direct caller of the
+        // token it satisfies the spender's auth check itself.
+        let contract = env.current_contract_address();
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer_from(&contract, &donator, &contract, &amount);
 
-            // Only transfer if there's a non-zero balance.
-            if balance > 0 {
-                // Create token client and transfer to creator.
-                let token_client = token::Client::new(&env, &token);
-                token_client.transfer(&contract_address, &config.creator, &balance);
+        Self::release_lock(&env);
 
-                // Emit funds_released event for this token.
-                events::emit_funds_released(&env, project_id, token, balance);
-            }
+        events::emit_project_funded(&env, project_id, donator, amount);
+        if goal_reached {
+            events::emit_goal_reached(&env, project_id);
         }
-
-        // Save the updated state (now marked as Completed).
-        save_project_state(&env, project_id, &state);
-
-        // Standardized event emission
-        events::emit_project_verified(&env, project_id, oracle.clone(), submitted_proof_hash);
+        amount
     }
 
-    // ─────────────────────────────────────────────────────────
-    // Internal Helpers
-    // ─────────────────────────────────────────────────────────
+    /// Set up a recurring donation of `amount_per_period` in `token`.
+    ///
+    /// Payments are pulled with `transfer_from` from the allowance
+    /// `subscriber` grants this contract; the allowance is checked at each
+    /// charge rather than up front. The first payment is due immediately and each
+    /// later one `period` seconds after the previous charge; anyone may
+    /// trigger a due payment with `execute_subscription`.
+    ///
+    /// - Panics with `Error::InvalidSubscription` if the amount, `period` or
+    ///   `max_periods` is zero or negative.
+    /// - Panics with `Error::ProjectNotActive` unless the project is
+    ///   `Funding` or `Active`, and with `Error::TokenNotAccepted` if it does
+    ///   not accept `token`.
+    ///
+    /// Returns the subscription ID.
+    pub fn subscribe(
+        env: Env,
+        project_id: u64,
+        subscriber: Address,
+        token: Address,
+        amount_per_period: i128,
+        period: u64,
+        max_periods: u32,
+    ) -> u64 {
+        Self::require_not_paused(&env);
+        subscriber.require_auth();
 
-    fn require_not_paused(env: &Env) {
-        if storage::is_paused(env) {
-            panic_with_error!(env, Error::ProtocolPaused);
+        if amount_per_period <= 0 || period == 0 || max_periods == 0 {
+            panic_with_error!(&env, Error::InvalidSubscription);
+        }
+        let (_, state) = load_project_pair(&env, project_id);
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => panic_with_error!(&env, Error::ProjectNotActive),
+        }
+        if !storage::get_accepted_tokens(&env, project_id).contains(&token) {
+            panic_with_error!(&env, Error::TokenNotAccepted);
+        }
+
+        let sub = Subscription {
+            id: storage::get_and_increment_subscription_id(&env),
+            project_id,
+            subscriber,
+            token,
+            amount_per_period,
+            period,
+            max_periods,
+            periods_charged: 0,
+            next_due: env.ledger().timestamp(),
+            status: SubscriptionStatus::Active,
+        };
+        storage::save_subscription(&env, &sub);
+        events::emit_subscription_created(&env, sub.clone());
+        sub.id
+    }
+
+    /// Charge the next due payment of a subscription. Callable by anyone.
+    ///
+    /// The payment is deposited exactly like `deposit` on behalf of the
+    /// subscriber. Instead of charging, the subscription ends with
+    /// `ProjectClosed` once the project has left `Funding`/`Active` or passed
+    /// its deadline, and with `InsufficientFunds` once the subscriber's
+    /// allowance or balance no longer covers a payment, and with
+    /// `PaymentRefused` once the project would reject the payment, e.g.
+    /// because the token was removed or delisted, the payment is below its
+    /// minimum deposit, or the token's `max_raise` cap is reached. It ends
+    /// with `Completed` after the last of `max_periods` payments.
+    ///
+    /// Payments fall due every `period` from the first one, however late
+    /// each is executed, so missed periods can be caught up.
+    ///
+    /// - Panics with `Error::SubscriptionInactive` if it has already ended.
+    /// - Panics with `Error::PaymentNotDue` before `next_due`.
+    ///
+    /// Returns the amount charged, `0` if the subscription ended instead.
+    pub fn execute_subscription(env: Env, sub_id: u64) -> i128 {
+        Self::require_not_paused(&env);
+        let mut sub = Self::load_subscription(&env, sub_id);
+        if sub.status != SubscriptionStatus::Active {
+            panic_with_error!(&env, Error::SubscriptionInactive);
+        }
+        let now = env.ledger().timestamp();
+        if now < sub.next_due {
+            panic_with_error!(&env, Error::PaymentNotDue);
+        }
+
+        let (config, mut state) = load_project_pair(&env, sub.project_id);
+        let open = matches!(state.status, ProjectStatus::Funding | ProjectStatus::Active);
+        if !open || now >= config.deadline {
+            return Self::end_subscription(&env, sub, SubscriptionStatus::ProjectClosed);
+        }
+
+        // Held from here: the allowance and balance checks call the token.
+        Self::acquire_lock(&env);
+
+        let contract = env.current_contract_address();
+        let token_client = token::Client::new(&env, &sub.token);
+        if token_client.allowance(&sub.subscriber, &contract) < sub.amount_per_period
+            || token_client.balance(&sub.subscriber) < sub.amount_per_period
+        {
+            Self::release_lock(&env);
+            return Self::end_subscription(&env, sub, SubscriptionStatus::InsufficientFunds);
+        }
+        let amount =
+            match Self::accepted_amount(&env, &config, &state, &sub.token, sub.amount_per_period) {
+                Ok(amount) => amount,
+                Err(_) => {
+                    Self::release_lock(&env);
+                    return Self::end_subscription(&env, sub, SubscriptionStatus::PaymentRefused);
+                }
+            };
+
+        let goal_reached = Self::credit_deposit(
+            &env,
+            &config,
+            &mut state,
+            Some(&sub.subscriber),
+            &sub.token,
+            amount,
+        );
+        sub.periods_charged += 1;
+        // Late payments do not shift the schedule.
+        sub.next_due = sub.next_due.saturating_add(sub.period);
+        if sub.periods_charged >= sub.max_periods {
+            sub.status = SubscriptionStatus::Completed;
+        }
+        storage::save_subscription(&env, &sub);
+
+        token_client.transfer_from(&contract, &sub.subscriber, &contract, &amount);
+
+        Self::release_lock(&env);
+
+        events::emit_project_funded(&env, sub.project_id, sub.subscriber.clone(), amount);
+        events::emit_subscription_charged(&env, &sub, amount);
+        if goal_reached {
+            events::emit_goal_reached(&env, sub.project_id);
+        }
+        if sub.status == SubscriptionStatus::Completed {
+            events::emit_subscription_ended(&env, &sub);
+        }
+        amount
+    }
+
+    /// Stop a subscription. Must be authorised by the subscriber.
+    ///
+    /// - Panics with `Error::SubscriptionInactive` if it has already ended.
+    pub fn cancel_subscription(env: Env, sub_id: u64) {
+        let sub = Self::load_subscription(&env, sub_id);
+        sub.subscriber.require_auth();
+        if sub.status != SubscriptionStatus::Active {
+            panic_with_error!(&env, Error::SubscriptionInactive);
+        }
+        Self::end_subscription(&env, sub, SubscriptionStatus::Cancelled);
+    }
+
+    /// Panics with `Error::SubscriptionNotFound` if `sub_id` does not exist.
+    pub fn get_subscription(env: Env, sub_id: u64) -> Subscription {
+        Self::load_subscription(&env, sub_id)
+    }
+
+    /// Pledge to match donations to `project_id` in `token`.
+    ///
+    /// `cap` is transferred from `sponsor` into escrow now. Until
+    /// `expires_at`, every deposit in `token` (from `deposit`, batches,
+    /// keepers, subscriptions or committed donations) is matched with
+    /// `amount × ratio_bps / 10_000` from the escrow, credited directly to
+    /// the project's balance and reported in a `matched` event. Matching
+    /// stops once the escrow is used up and never exceeds the project's
+    /// `max_raise`. `release_match` returns what is left.
+    ///
+    /// - Panics with `Error::InvalidMatch` if `ratio_bps` or `cap` is zero,
+    ///   or `expires_at` is not in the future.
+    /// - Panics with `Error::ProjectNotActive` unless the project is
+    ///   `Funding` or `Active`, and with `Error::TokenNotAccepted` if it does
+    ///   not accept `token`.
+    /// - Panics with `Error::TooManyMatches` at [`MAX_PROJECT_MATCHES`].
+    ///
+    /// Returns the pledge ID.
+    pub fn create_match(
+        env: Env,
+        sponsor: Address,
+        project_id: u64,
+        token: Address,
+        ratio_bps: u32,
+        cap: i128,
+        expires_at: u64,
+    ) -> u64 {
+        Self::require_not_paused(&env);
+        sponsor.require_auth();
+
+        if ratio_bps == 0 || cap <= 0 || expires_at <= env.ledger().timestamp() {
+            panic_with_error!(&env, Error::InvalidMatch);
+        }
+        let (_, state) = load_project_pair(&env, project_id);
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => panic_with_error!(&env, Error::ProjectNotActive),
+        }
+        if !storage::get_accepted_tokens(&env, project_id).contains(&token) {
+            panic_with_error!(&env, Error::TokenNotAccepted);
+        }
+        let mut ids = storage::get_project_matches(&env, project_id);
+        if ids.len() >= MAX_PROJECT_MATCHES {
+            panic_with_error!(&env, Error::TooManyMatches);
+        }
+
+        let pledge = MatchPledge {
+            id: storage::get_and_increment_match_id(&env),
+            sponsor: sponsor.clone(),
+            project_id,
+            token: token.clone(),
+            ratio_bps,
+            cap,
+            remaining: cap,
+            matched: 0,
+            expires_at,
+            settled: false,
+        };
+        storage::save_match(&env, &pledge);
+        ids.push_back(pledge.id);
+        storage::set_project_matches(&env, project_id, &ids);
+
+        Self::acquire_lock(&env);
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&sponsor, &env.current_contract_address(), &cap);
+        Self::release_lock(&env);
+
+        events::emit_match_created(&env, pledge.clone());
+        pledge.id
+    }
+
+    /// Return the unused part of a pledge to its sponsor. Callable by anyone
+    /// once the pledge has expired or the project has left `Funding` and
+    /// `Active`. If the project was cancelled, the funds the pledge already
+    /// matched are taken back from the project's balance and returned too.
+    ///
+    /// - Panics with `Error::MatchActive` while the pledge can still match.
+    /// - Panics with `Error::MatchSettled` if already released.
+    ///
+    /// Returns the amount returned.
+    pub fn release_match(env: Env, match_id: u64) -> i128 {
+        Self::require_not_paused(&env);
+        let mut pledge = Self::load_match(&env, match_id);
+        if pledge.settled {
+            panic_with_error!(&env, Error::MatchSettled);
+        }
+        let (_, state) = load_project_pair(&env, pledge.project_id);
+        let open = matches!(
+            state.status,
+            ProjectStatus::Funding
+                | ProjectStatus::Active
+                | ProjectStatus::Voting
+                | ProjectStatus::UnderReview
+        );
+        if open && env.ledger().timestamp() < pledge.expires_at {
+            panic_with_error!(&env, Error::MatchActive);
+        }
+
+        let mut returned = pledge.remaining;
+        if state.status == ProjectStatus::Cancelled {
+            let balance = storage::get_token_balance(&env, pledge.project_id, &pledge.token);
+            let clawback = pledge.matched.min(balance);
+            if clawback > 0 {
+                storage::set_token_balance(
+                    &env,
+                    pledge.project_id,
+                    &pledge.token,
+                    balance - clawback,
+                );
+                storage::add_total_raised(&env, pledge.project_id, &pledge.token, -clawback);
+                storage::add_matched_total(&env, pledge.project_id, &pledge.token, -clawback);
+                pledge.matched -= clawback;
+                returned += clawback;
+            }
+        }
+        pledge.remaining = 0;
+        pledge.settled = true;
+        storage::save_match(&env, &pledge);
+        let mut ids = storage::get_project_matches(&env, pledge.project_id);
+        if let Some(i) = ids.first_index_of(match_id) {
+            ids.remove(i);
+        }
+        storage::set_project_matches(&env, pledge.project_id, &ids);
+
+        if returned > 0 {
+            Self::acquire_lock(&env);
+            let token_client = token::Client::new(&env, &pledge.token);
+            token_client.transfer(&env.current_contract_address(), &pledge.sponsor, &returned);
+            Self::release_lock(&env);
+        }
+
+        events::emit_match_released(&env, match_id, pledge.project_id, returned);
+        returned
+    }
+
+    /// Panics with `Error::MatchNotFound` if `match_id` does not exist.
+    pub fn get_match(env: Env, match_id: u64) -> MatchPledge {
+        Self::load_match(&env, match_id)
+    }
+
+    /// Open a quadratic funding round and escrow its matching `pool`.
+    ///
+    /// - `admin` must hold `SuperAdmin` or `Admin` and pays the pool.
+    /// - `matching_token` must be allowlisted.
+    /// - Donations count between `starts_at` (inclusive) and `ends_at`
+    ///   (exclusive); `ends_at` must be in the future.
+    /// - `max_share_bps` caps any one project's match, in basis points of
+    ///   the pool.
+    ///
+    /// Panics with `Error::InvalidRound` if the pool, window or share cap is
+    /// invalid. Returns the round ID.
+    pub fn create_round(
+        env: Env,
+        admin: Address,
+        matching_token: Address,
+        pool: i128,
+        starts_at: u64,
+        ends_at: u64,
+        max_share_bps: u32,
+    ) -> u64 {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        rbac::require_admin_or_above(&env, &admin);
+
+        if !storage::is_token_allowed(&env, &matching_token) {
+            panic_with_error!(&env, Error::TokenNotAllowed);
+        }
+        if pool <= 0
+            || starts_at >= ends_at
+            || ends_at <= env.ledger().timestamp()
+            || max_share_bps == 0
+            || max_share_bps as i128 > rounds::SHARE_DENOMINATOR
+        {
+            panic_with_error!(&env, Error::InvalidRound);
+        }
+
+        let round = Round {
+            id: storage::get_and_increment_round_id(&env),
+            funder: admin.clone(),
+            matching_token: matching_token.clone(),
+            pool,
+            starts_at,
+            ends_at,
+            max_share_bps,
+            projects: Vec::new(&env),
+            status: RoundStatus::Open,
+        };
+        storage::save_round(&env, &round);
+
+        Self::acquire_lock(&env);
+        let token_client = token::Client::new(&env, &matching_token);
+        token_client.transfer(&admin, &env.current_contract_address(), &pool);
+        Self::release_lock(&env);
+
+        events::emit_round_created(&env, round.clone());
+        round.id
+    }
+
+    /// Enter `project_id` into an open round. Must be authorised by the
+    /// project creator.
+    ///
+    /// The project must be `Funding` or `Active`, accept the round's
+    /// matching token, and not already be in an open round. Only donations
+    /// made after joining are counted.
+    ///
+    /// - Panics with `Error::RoundClosed` once the round has ended.
+    /// - Panics with `Error::AlreadyInRound` if the project is in a round.
+    /// - Panics with `Error::RoundFull` at [`MAX_ROUND_PROJECTS`].
+    pub fn join_round(env: Env, round_id: u64, project_id: u64) {
+        Self::require_not_paused(&env);
+        let mut round = Self::load_round(&env, round_id);
+        let (config, state) = load_project_pair(&env, project_id);
+        config.creator.require_auth();
+
+        if round.status != RoundStatus::Open || env.ledger().timestamp() >= round.ends_at {
+            panic_with_error!(&env, Error::RoundClosed);
+        }
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => panic_with_error!(&env, Error::ProjectNotActive),
+        }
+        if !storage::get_accepted_tokens(&env, project_id).contains(&round.matching_token) {
+            panic_with_error!(&env, Error::TokenNotAccepted);
+        }
+        if storage::get_project_round(&env, project_id).is_some() {
+            panic_with_error!(&env, Error::AlreadyInRound);
+        }
+        if round.projects.len() >= MAX_ROUND_PROJECTS {
+            panic_with_error!(&env, Error::RoundFull);
+        }
+
+        round.projects.push_back(project_id);
+        storage::save_round(&env, &round);
+        storage::set_project_round(&env, project_id, Some(round_id));
+        events::emit_round_joined(&env, round_id, project_id);
+    }
+
+    /// Compute and pay out the matching of a round. Callable by anyone once
+    /// `ends_at` has passed.
+    ///
+    /// Each participating project that is still open — `Funding`, `Active`,
+    /// `Voting` or `UnderReview` — and still accepts the matching token has
+    /// its match credited to its balance in that token, which can complete
+    /// its goal. Other projects, including cancelled ones, get nothing and
+    /// are left out of the split. A match is also trimmed to the headroom
+    /// left under the project's `max_raise` cap for the token. The
+    /// unallocated remainder, including any amount above the per-project
+    /// share or `max_raise` cap, is returned to the funder.
+    ///
+    /// - Panics with `Error::RoundNotEnded` before `ends_at`.
+    /// - Panics with `Error::RoundClosed` if already closed.
+    /// - Panics with `Error::Overflow` if the tallies are too large to match.
+    ///
+    /// Returns the match of each project, in `Round::projects` order.
+    pub fn close_round(env: Env, round_id: u64) -> Vec<i128> {
+        Self::require_not_paused(&env);
+        let mut round = Self::load_round(&env, round_id);
+        if round.status != RoundStatus::Open {
+            panic_with_error!(&env, Error::RoundClosed);
+        }
+        if env.ledger().timestamp() < round.ends_at {
+            panic_with_error!(&env, Error::RoundNotEnded);
+        }
+
+        let token = round.matching_token.clone();
+        let mut eligible = Vec::new(&env);
+        for project_id in round.projects.iter() {
+            let (_, state) = load_project_pair(&env, project_id);
+            let open = matches!(
+                state.status,
+                ProjectStatus::Funding
+                    | ProjectStatus::Active
+                    | ProjectStatus::Voting
+                    | ProjectStatus::UnderReview
+            );
+            eligible
+                .push_back(open && storage::get_accepted_tokens(&env, project_id).contains(&token));
+        }
+        let ideal = match rounds::compute_matches(&env, &round, &eligible) {
+            Some(matches) => matches,
+            None => panic_with_error!(&env, Error::Overflow),
+        };
+
+        let mut matches = Vec::new(&env);
+        let mut distributed: i128 = 0;
+        let mut goals_reached = Vec::new(&env);
+        for (i, project_id) in round.projects.iter().enumerate() {
+            let (config, mut state) = load_project_pair(&env, project_id);
+            let mut matched = ideal.get(i as u32).unwrap();
+            if let Some(cap) = config.max_raise.get(token.clone()) {
+                let balance = storage::get_token_balance(&env, project_id, &token);
+                matched = matched.min(cap.saturating_sub(balance).max(0));
+            }
+            matches.push_back(matched);
+            let mut tally = storage::get_round_project(&env, round_id, project_id);
+            tally.matched = matched;
+            storage::set_round_project(&env, round_id, project_id, &tally);
+            storage::set_project_round(&env, project_id, None);
+            if matched == 0 {
+                continue;
+            }
+
+            storage::add_to_token_balance(&env, project_id, &token, matched);
+            storage::add_total_raised(&env, project_id, &token, matched);
+            if Self::update_goal_status(&env, &config, &mut state) {
+                save_project_state(&env, project_id, &state);
+                goals_reached.push_back(project_id);
+            }
+            distributed += matched;
+        }
+        round.status = RoundStatus::Closed;
+        storage::save_round(&env, &round);
+
+        let returned = round.pool - distributed;
+        if returned > 0 {
+            Self::acquire_lock(&env);
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&env.current_contract_address(), &round.funder, &returned);
+            Self::release_lock(&env);
+        }
+
+        for (i, project_id) in round.projects.iter().enumerate() {
+            let matched = matches.get(i as u32).unwrap();
+            if matched > 0 {
+                events::emit_round_matched(&env, round_id, project_id, token.clone(), matched);
+            }
+        }
+        for project_id in goals_reached.iter() {
+            events::emit_goal_reached(&env, project_id);
+        }
+        events::emit_round_closed(&env, round_id, distributed, returned);
+        matches
+    }
+
+    /// Return a refundable project's match from a closed round to the
+    /// round's funder. Callable by anyone.
+    ///
+    /// Refunds only return donors' own contributions, so without this the
+    /// match would stay in escrow. Only what is left of the project's
+    /// balance in the matching token is taken back.
+    ///
+    /// - Panics with `Error::RoundNotEnded` while the round is open.
+    /// - Panics with `Error::NotRefundable` unless the project is
+    ///   `Cancelled` or, like `claim_refund`, an `AllOrNothing` project that
+    ///   missed its goal by its deadline.
+    /// - Panics with `Error::InsufficientBalance` if there is no match left
+    ///   to return.
+    ///
+    /// Returns the amount returned.
+    pub fn reclaim_round_match(env: Env, round_id: u64, project_id: u64) -> i128 {
+        Self::require_not_paused(&env);
+        let round = Self::load_round(&env, round_id);
+        if round.status != RoundStatus::Closed {
+            panic_with_error!(&env, Error::RoundNotEnded);
+        }
+        let (config, state) = load_project_pair(&env, project_id);
+
+        // Held from here: the goal check may call a price feed.
+        Self::acquire_lock(&env);
+        let refundable =
+            state.status == ProjectStatus::Cancelled || Self::goal_missed(&env, &config, &state);
+        if !refundable {
+            panic_with_error!(&env, Error::NotRefundable);
+        }
+
+        let token = round.matching_token.clone();
+        let mut tally = storage::get_round_project(&env, round_id, project_id);
+        let balance = storage::get_token_balance(&env, project_id, &token);
+        let returned = tally.matched.min(balance);
+        if returned <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+        storage::set_token_balance(&env, project_id, &token, balance - returned);
+        storage::add_total_raised(&env, project_id, &token, -returned);
+        tally.matched -= returned;
+        storage::set_round_project(&env, round_id, project_id, &tally);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &round.funder, &returned);
+        Self::release_lock(&env);
+
+        events::emit_round_match_reclaimed(&env, round_id, project_id, returned);
+        returned
+    }
+
+    /// Panics with `Error::RoundNotFound` if `round_id` does not exist.
+    pub fn get_round(env: Env, round_id: u64) -> Round {
+        Self::load_round(&env, round_id)
+    }
+
+    /// Return the tally of `project_id` in a round; all zero if it has
+    /// received no counted donations.
+    pub fn get_round_project(env: Env, round_id: u64, project_id: u64) -> RoundProject {
+        Self::load_round(&env, round_id);
+        storage::get_round_project(&env, round_id, project_id)
+    }
+
+    /// Split one donation across several projects atomically.
+    ///
+    /// Each allocation is `(project_id, token, amount)` and is handled like a
+    /// `deposit`, including `max_raise` trimming. Every allocation is
+    /// validated before any funds move, `donator` authorises once, and a
+    /// `funded` event is emitted per allocation. If any allocation fails the
+    /// whole batch reverts.
+    ///
+    /// - Panics with `Error::InvalidBatch` if the batch is empty, longer than
+    ///   [`MAX_BATCH_SIZE`], or repeats a `(project_id, token)` pair.
+    ///
+    /// Returns the accepted amount of each allocation, in order.
+    pub fn deposit_batch(
+        env: Env,
+        donator: Address,
+        allocations: Vec<(u64, Address, i128)>,
+    ) -> Vec<i128> {
+        Self::require_not_paused(&env);
+        donator.require_auth();
+
+        if allocations.is_empty() || allocations.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, Error::InvalidBatch);
+        }
+
+        // Validate everything up front. Distinct (project, token) pairs keep
+        // each allocation's cap headroom independent of the others.
+        let mut accepted: Vec<i128> = Vec::new(&env);
+        for (i, (project_id, token, amount)) in allocations.iter().enumerate() {
+            for (other_id, other_token, _) in allocations.iter().take(i) {
+                if other_id == project_id && other_token == token {
+                    panic_with_error!(&env, Error::InvalidBatch);
+                }
+            }
+            let (config, state) = load_project_pair(&env, project_id);
+            accepted.push_back(Self::check_deposit(&env, &config, &state, &token, amount));
+        }
+
+        Self::acquire_lock(&env);
+
+        let mut reached: Vec<bool> = Vec::new(&env);
+        let contract_address = env.current_contract_address();
+        for (i, (project_id, token, _)) in allocations.iter().enumerate() {
+            let amount = accepted.get_unchecked(i as u32);
+            // Reload state: an earlier allocation may have updated this project.
+            let (config, mut state) = load_project_pair(&env, project_id);
+            reached.push_back(Self::credit_deposit(
+                &env,
+                &config,
+                &mut state,
+                Some(&donator),
+                &token,
+                amount,
+            ));
+            token::Client::new(&env, &token).transfer(&donator, &contract_address, &amount);
+        }
+
+        Self::release_lock(&env);
+
+        for (i, (project_id, _, _)) in allocations.iter().enumerate() {
+            let i = i as u32;
+            let amount = accepted.get_unchecked(i);
+            events::emit_project_funded(&env, project_id, donator.clone(), amount);
+            if reached.get_unchecked(i) {
+                events::emit_goal_reached(&env, project_id);
+            }
+        }
+        accepted
+    }
+
+    /// Deposit on behalf of an anonymous donor identified only by `commitment`.
+    ///
+    /// `payer` (typically a relayer or shielding address) funds the deposit;
+    /// only the commitment is stored and emitted. The donor computes
+    /// `commitment = sha256(donor.to_xdr() || secret)` off-chain and may
+    /// later prove authorship with `reveal_donation`.
+    ///
+    /// The same validation as `deposit` applies. Committed donations count
+    /// as a deposit and towards totals raised, but not towards donor
+    /// metrics until revealed, and cannot be withdrawn during cooling-off.
+    ///
+    /// - Must be authorised by `payer`.
+    /// - Panics with `Error::CommitmentExists` if `commitment` was already used
+    ///   for this project.
+    ///
+    /// Returns the amount actually transferred from `payer`.
+    pub fn donate_committed(
+        env: Env,
+        project_id: u64,
+        payer: Address,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+    ) -> i128 {
+        Self::require_not_paused(&env);
+        payer.require_auth();
+
+        let (config, mut state) = load_project_pair(&env, project_id);
+        let amount = Self::check_deposit(&env, &config, &state, &token, amount);
+        if storage::get_committed_donation(&env, project_id, &commitment).is_some() {
+            panic_with_error!(&env, Error::CommitmentExists);
+        }
+
+        Self::acquire_lock(&env);
+
+        storage::set_committed_donation(
+            &env,
+            project_id,
+            &commitment,
+            &CommittedDonation {
+                token: token.clone(),
+                amount,
+                revealed: false,
+            },
+        );
+        let goal_reached = Self::credit_deposit(&env, &config, &mut state, None, &token, amount);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&payer, &env.current_contract_address(), &amount);
+
+        Self::release_lock(&env);
+
+        events::emit_donation_committed(&env, project_id, token, amount, commitment);
+        if goal_reached {
+            events::emit_goal_reached(&env, project_id);
+        }
+        amount
+    }
+
+    /// Prove authorship of a committed donation.
+    ///
+    /// Recomputes `sha256(donor.to_xdr() || secret)` and, if it matches a
+    /// committed donation to `project_id`, credits it to `donor`'s
+    /// contribution record and donor metrics. Binding the donor address into
+    /// the commitment stops anyone who sees `secret` from claiming it.
+    ///
+    /// - Must be authorised by `donor`.
+    /// - Panics with `Error::CommitmentNotFound` if nothing matches, or
+    ///   `Error::AlreadyRevealed` if the donation was already revealed.
+    /// - Panics with `GovernanceError::VotingOpen` while a release vote is
+    ///   open, since revealing adds voting power.
+    pub fn reveal_donation(env: Env, project_id: u64, donor: Address, secret: Bytes) {
+        Self::require_not_paused(&env);
+        donor.require_auth();
+
+        let mut preimage = donor.clone().to_xdr(&env);
+        preimage.append(&secret);
+        let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        let mut donation = match storage::get_committed_donation(&env, project_id, &commitment) {
+            Some(d) => d,
+            None => panic_with_error!(&env, Error::CommitmentNotFound),
+        };
+        if donation.revealed {
+            panic_with_error!(&env, Error::AlreadyRevealed);
+        }
+        // Revealing adds voting power, which must not change mid-vote.
+        let (_, mut state) = load_project_pair(&env, project_id);
+        if state.status == ProjectStatus::Voting {
+            panic_with_error!(&env, GovernanceError::VotingOpen);
+        }
+        donation.revealed = true;
+        storage::set_committed_donation(&env, project_id, &commitment, &donation);
+
+        Self::count_donor(&env, project_id, &mut state, &donor, &donation.token);
+        save_project_state(&env, project_id, &state);
+        storage::add_contribution(&env, project_id, &donor, &donation.token, donation.amount);
+
+        events::emit_donation_revealed(&env, project_id, commitment, donor);
+    }
+
+    /// Return the committed donation stored under `commitment`, if any.
+    pub fn get_committed_donation(
+        env: Env,
+        project_id: u64,
+        commitment: BytesN<32>,
+    ) -> Option<CommittedDonation> {
+        storage::get_committed_donation(&env, project_id, &commitment)
+    }
+
+    /// Take back `amount` of `token` deposited by `donator` within the
+    /// cooling-off window.
+    ///
+    /// Only deposits made less than `cooling_off_period` seconds ago (under
+    /// the parameters the project was registered with) can be withdrawn;
+    /// the most recent deposits are withdrawn first.
+    ///
+    /// - Must be authorised by `donator`.
+    /// - The project must still be `Funding`.
+    /// - Panics with `Error::CoolingOffExceeded` if `amount` is more than
+    ///   the donor's deposits still inside the window.
+    /// - Panics with `Error::InsufficientBalance` if `amount` is more than
+    ///   the donor's recorded contribution or the project's balance, e.g.
+    ///   after a refund.
+    pub fn withdraw_donation(
+        env: Env,
+        project_id: u64,
+        donator: Address,
+        token: Address,
+        amount: i128,
+    ) {
+        Self::require_not_paused(&env);
+        donator.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        let (config, state) = load_project_pair(&env, project_id);
+        if state.status != ProjectStatus::Funding {
+            panic_with_error!(&env, Error::ProjectNotActive);
+        }
+
+        let window = storage::get_params_at(&env, config.params_version).cooling_off_period;
+        let mut log =
+            storage::get_withdrawable_deposits(&env, project_id, &donator, &token, window);
+        let mut withdrawable: i128 = 0;
+        for record in log.iter() {
+            withdrawable = withdrawable.saturating_add(record.amount);
+        }
+        if amount > withdrawable {
+            panic_with_error!(&env, Error::CoolingOffExceeded);
+        }
+        // The log only says when the donor gave; never pay out more than the
+        // donor still has recorded or the project still holds.
+        let balance = storage::get_token_balance(&env, project_id, &token);
+        if amount > balance
+            || amount > storage::get_contribution(&env, project_id, &donator, &token)
+        {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+
+        Self::acquire_lock(&env);
+
+        // Consume the newest deposits first.
+        let mut remaining = amount;
+        while remaining > 0 {
+            let mut record = log.pop_back().unwrap();
+            if record.amount > remaining {
+                record.amount -= remaining;
+                log.push_back(record);
+                remaining = 0;
+            } else {
+                remaining -= record.amount;
+            }
+        }
+        storage::set_deposit_log(&env, project_id, &donator, &token, &log);
+
+        storage::set_token_balance(&env, project_id, &token, balance - amount);
+        storage::record_contribution(&env, project_id, &donator, &token, -amount);
+        rounds::record_contribution(&env, project_id, &donator, &token, -amount);
+        pledges::reverse(&env, project_id, &donator, &token, amount);
+        votes::sync_extension_vote(&env, project_id, &donator);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &donator, &amount);
+
+        Self::release_lock(&env);
+
+        events::emit_donation_withdrawn(&env, project_id, donator, token, amount);
+    }
+
+    /// Grant the Oracle role to `oracle`.
+    ///
+    /// Replaces the original `set_oracle(admin, oracle)`.
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn set_oracle(env: Env, caller: Address, oracle: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        rbac::grant_role(&env, &caller, &oracle, Role::Oracle);
+    }
+
+    /// Verify proof of impact and release funds to the creator.
+    ///
+    /// The registered oracle reveals the proof artifact's content identifier
+    /// and the salt. If `proof` uses the registered hash algorithm and
+    /// `sha256(proof.digest || salt)` matches the project's stored
+    /// commitment, the project status transitions to `Completed`. Projects
+    /// registered before commitments compare digests directly and ignore
+    /// `salt`. A `proof` under another algorithm panics with
+    /// `GovernanceError::ProofAlgorithmMismatch`.
+    ///
+    /// NOTE: This is a mocked verification (hash equality).
+    /// The structure is prepared for future ZK-STARK verification.
+    ///
+    /// Reads the immutable config (for the proof) and mutable state (for status),
+    /// then writes back only the small state entry.
+    ///
+    /// `AllOrNothing` projects must have reached their goal (`Active`);
+    /// otherwise this panics with `Error::GoalNotReached`. Projects released
+    /// by donor vote panic with `GovernanceError::WrongVerificationMode`.
+    ///
+    /// If the creator has submitted proof with `submit_proof`, verification
+    /// accepts that submission; projects without one can still be verified
+    /// directly from `Funding` or `Active`.
+    pub fn verify_and_release(
+        env: Env,
+        oracle: Address,
+        project_id: u64,
+        proof: ContentId,
+        salt: BytesN<32>,
+    ) {
+        Self::verify(&env, oracle, project_id, proof, salt, None);
+    }
+
+    /// Verify proof of impact against an entry of the project's evidence
+    /// log and release funds to the creator.
+    ///
+    /// Behaves like `verify_and_release`, and additionally records
+    /// `evidence_index` as the entry the release was based on. Panics with
+    /// `GovernanceError::EvidenceNotFound` if the log has no such entry.
+    pub fn verify_with_evidence(
+        env: Env,
+        oracle: Address,
+        project_id: u64,
+        proof: ContentId,
+        salt: BytesN<32>,
+        evidence_index: u32,
+    ) {
+        Self::verify(&env, oracle, project_id, proof, salt, Some(evidence_index));
+    }
+
+    fn verify(
+        env: &Env,
+        oracle: Address,
+        project_id: u64,
+        proof: ContentId,
+        salt: BytesN<32>,
+        evidence_index: Option<u32>,
+    ) {
+        Self::require_not_paused(env);
+        oracle.require_auth();
+        // RBAC gate: caller must hold the Oracle role.
+        rbac::require_oracle(env, &oracle);
+
+        // Optimised dual-read helper
+        let (config, mut state) = load_project_pair(env, project_id);
+        if config.verification_mode != VerificationMode::Oracle {
+            panic_with_error!(env, GovernanceError::WrongVerificationMode);
+        }
+
+        // Ensure the project is in a verifiable state.
+        let mut submission = None;
+        let goal_status = match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => state.status.clone(),
+            ProjectStatus::UnderReview => match storage::get_proof_review(env, project_id) {
+                ProofReview::Pending(pending) => {
+                    submission = Some(pending.clone());
+                    pending.prior_status
+                }
+                _ => panic_with_error!(env, GovernanceError::NotUnderReview),
+            },
+            ProjectStatus::Completed => panic_with_error!(env, Error::MilestoneAlreadyReleased),
+            ProjectStatus::Expired => panic_with_error!(env, Error::ProjectNotFound),
+            ProjectStatus::Cancelled | ProjectStatus::Voting => {
+                panic_with_error!(env, Error::ProjectNotActive)
+            }
+        };
+
+        // All-or-nothing projects only release once the goal has been met.
+        if config.funding_mode == FundingMode::AllOrNothing && goal_status != ProjectStatus::Active
+        {
+            panic_with_error!(env, Error::GoalNotReached);
+        }
+
+        // Mocked ZK verification: check the revealed proof against the commitment.
+        Self::require_proof(env, &config, &proof, &salt);
+        if let Some(index) = evidence_index {
+            if storage::get_evidence(env, project_id, index).is_none() {
+                panic_with_error!(env, GovernanceError::EvidenceNotFound);
+            }
+            storage::set_verified_evidence(env, project_id, index);
+        }
+
+        if let Some(submission) = &submission {
+            storage::set_proof_review(env, project_id, &ProofReview::Accepted(submission.clone()));
+        }
+        Self::release_funds(env, project_id, &config.creator, &mut state);
+
+        if let Some(submission) = submission {
+            events::emit_proof_accepted(env, project_id, oracle.clone(), submission);
+        }
+        // Standardized event emission
+        events::emit_project_verified(
+            env,
+            project_id,
+            oracle,
+            proof,
+            salt,
+            config.proof,
+            evidence_index,
+        );
+    }
+
+    /// Append an entry to a project's evidence log.
+    ///
+    /// The log is append-only: entries are never edited or removed, and
+    /// entries can be added in any status, including after release.
+    ///
+    /// - `caller` must be the project creator or hold the Auditor role.
+    /// - `content_hash` identifies the evidence artifact, e.g. by the
+    ///   multihash of its IPFS CID.
+    /// - Panics with `GovernanceError::InvalidContentId` if `content_hash`
+    ///   uses an unknown multihash code or a digest of the wrong length.
+    pub fn add_evidence(
+        env: Env,
+        caller: Address,
+        project_id: u64,
+        content_hash: ContentId,
+        kind: EvidenceKind,
+    ) -> EvidenceEntry {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+
+        let (config, _) = load_project_pair(&env, project_id);
+        if caller != config.creator {
+            rbac::require_role(&env, &caller, &Role::Auditor);
+        }
+        if !proofs::is_known(&content_hash) {
+            panic_with_error!(&env, GovernanceError::InvalidContentId);
+        }
+
+        let entry = EvidenceEntry {
+            project_id,
+            index: storage::evidence_count(&env, project_id),
+            author: caller,
+            content_hash,
+            kind,
+            timestamp: env.ledger().timestamp(),
+        };
+        storage::append_evidence(&env, &entry);
+
+        events::emit_evidence_added(&env, entry.clone());
+        entry
+    }
+
+    /// Return the evidence entry at `index`.
+    ///
+    /// Panics with `GovernanceError::EvidenceNotFound` if there is none.
+    pub fn get_evidence(env: Env, project_id: u64, index: u32) -> EvidenceEntry {
+        match storage::get_evidence(&env, project_id, index) {
+            Some(entry) => entry,
+            None => panic_with_error!(&env, GovernanceError::EvidenceNotFound),
+        }
+    }
+
+    /// Return up to `limit` evidence entries of a project starting at index
+    /// `cursor`, oldest first; `limit` is capped at 50.
+    pub fn list_evidence(env: Env, project_id: u64, cursor: u32, limit: u32) -> Vec<EvidenceEntry> {
+        if !storage::project_exists(&env, project_id) {
+            panic_with_error!(&env, Error::ProjectNotFound);
+        }
+        storage::list_evidence(&env, project_id, cursor, limit)
+    }
+
+    /// Submit proof of completed work for the oracle to review.
+    ///
+    /// Moves the project to `UnderReview`, closing deposits, until the
+    /// oracle accepts the proof with `verify_and_release` or sends it back
+    /// with `reject_proof`.
+    ///
+    /// - Must be authorised by the project creator.
+    /// - Panics with `GovernanceError::WrongVerificationMode` for projects
+    ///   released by donor vote.
+    /// - Panics with `Error::ProjectNotActive` unless the project is `Funding` or `Active`.
+    /// - Panics with `Error::GoalNotReached` if an `AllOrNothing` project is not `Active`.
+    /// - Panics with `GovernanceError::InvalidContentId` if `evidence_hash`
+    ///   uses an unknown multihash code or a digest of the wrong length.
+    pub fn submit_proof(
+        env: Env,
+        project_id: u64,
+        evidence_hash: ContentId,
+        uri_hash: BytesN<32>,
+    ) -> ProofSubmission {
+        Self::require_not_paused(&env);
+        let (config, mut state) = load_project_pair(&env, project_id);
+        config.creator.require_auth();
+        if !proofs::is_known(&evidence_hash) {
+            panic_with_error!(&env, GovernanceError::InvalidContentId);
+        }
+
+        if config.verification_mode != VerificationMode::Oracle {
+            panic_with_error!(&env, GovernanceError::WrongVerificationMode);
+        }
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => panic_with_error!(&env, Error::ProjectNotActive),
+        }
+        if config.funding_mode == FundingMode::AllOrNothing && state.status != ProjectStatus::Active
+        {
+            panic_with_error!(&env, Error::GoalNotReached);
+        }
+
+        let submission = ProofSubmission {
+            evidence_hash,
+            uri_hash,
+            submitted_at: env.ledger().timestamp(),
+            prior_status: state.status.clone(),
+        };
+        storage::set_proof_review(&env, project_id, &ProofReview::Pending(submission.clone()));
+        state.status = ProjectStatus::UnderReview;
+        save_project_state(&env, project_id, &state);
+
+        events::emit_proof_submitted(&env, project_id, submission.clone());
+        submission
+    }
+
+    /// Send a submitted proof back to the creator, returning the project to
+    /// the status it was submitted from.
+    ///
+    /// - `oracle` must hold the Oracle role.
+    /// - `reason_hash` is the hash of the oracle's off-chain explanation.
+    /// - Panics with `GovernanceError::NotUnderReview` unless a proof is
+    ///   awaiting review.
+    pub fn reject_proof(env: Env, oracle: Address, project_id: u64, reason_hash: BytesN<32>) {
+        Self::require_not_paused(&env);
+        oracle.require_auth();
+        rbac::require_oracle(&env, &oracle);
+
+        let (_, mut state) = load_project_pair(&env, project_id);
+        let submission = match storage::get_proof_review(&env, project_id) {
+            ProofReview::Pending(submission) if state.status == ProjectStatus::UnderReview => {
+                submission
+            }
+            _ => panic_with_error!(&env, GovernanceError::NotUnderReview),
+        };
+
+        state.status = submission.prior_status.clone();
+        save_project_state(&env, project_id, &state);
+        let review = ProofReview::Rejected(submission, reason_hash.clone());
+        storage::set_proof_review(&env, project_id, &review);
+
+        events::emit_proof_rejected(&env, project_id, oracle, reason_hash);
+    }
+
+    /// Cancel a project and open refunds to its donors.
+    ///
+    /// - `caller` must be the project's creator or hold `SuperAdmin` or
+    ///   `Admin`.
+    /// - `reason_hash` is the hash of an off-chain statement of the reason,
+    ///   carried in the `cancelled` event.
+    /// - Panics with `Error::MilestoneAlreadyReleased` if the project is
+    ///   `Completed`, or `Error::ProjectNotActive` if already `Cancelled`.
+    ///
+    /// A cancelled project accepts no further deposits or verification;
+    /// donors reclaim their contributions with `claim_refund`. Anonymous
+    /// committed donations can still be revealed and then refunded; one
+    /// whose secret is lost stays in escrow. Sponsor funds go back through
+    /// `release_match` and `reclaim_round_match`.
+    pub fn cancel_project(env: Env, caller: Address, project_id: u64, reason_hash: BytesN<32>) {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+
+        let (config, mut state) = load_project_pair(&env, project_id);
+        if caller != config.creator {
+            rbac::require_admin_or_above(&env, &caller);
+        }
+        match state.status {
+            ProjectStatus::Funding
+            | ProjectStatus::Active
+            | ProjectStatus::Expired
+            | ProjectStatus::Voting
+            | ProjectStatus::UnderReview => {}
+            ProjectStatus::Completed => panic_with_error!(&env, Error::MilestoneAlreadyReleased),
+            ProjectStatus::Cancelled => panic_with_error!(&env, Error::ProjectNotActive),
+        }
+
+        if state.status == ProjectStatus::Voting {
+            if let Some(mut vote) = storage::get_release_vote(&env, project_id) {
+                vote.status = ReleaseVoteStatus::Failed;
+                storage::save_release_vote(&env, &vote);
+            }
+        }
+        if let ProofReview::Pending(submission) = storage::get_proof_review(&env, project_id) {
+            let review = ProofReview::Rejected(submission, reason_hash.clone());
+            storage::set_proof_review(&env, project_id, &review);
+        }
+        state.status = ProjectStatus::Cancelled;
+        save_project_state(&env, project_id, &state);
+
+        events::emit_project_cancelled(&env, project_id, caller, reason_hash);
+    }
+
+    /// Return `donator`'s contributions to a project, in every accepted
+    /// token, once it is cancelled, once an `AllOrNothing` project has
+    /// passed its deadline short of its goal, or while a deadline extension
+    /// is being voted on.
+    ///
+    /// - Must be authorised by `donator`.
+    /// - Panics with `Error::NotRefundable` unless the project is `Cancelled`,
+    ///   is an `AllOrNothing` project still `Funding` at its deadline whose
+    ///   goal is not met at current prices, or is
+    ///   `Funding` inside the window of a pending extension.
+    /// - Panics with `Error::InsufficientBalance` if the donor has no
+    ///   contribution left to reclaim.
+    ///
+    /// Anonymous committed donations are refundable once revealed. Returns
+    /// the amounts refunded.
+    pub fn claim_refund(env: Env, project_id: u64, donator: Address) -> Vec<Contribution> {
+        Self::require_not_paused(&env);
+        donator.require_auth();
+
+        let (config, state) = load_project_pair(&env, project_id);
+        // Held from here: the goal check may call a price feed.
+        Self::acquire_lock(&env);
+
+        let now = env.ledger().timestamp();
+        let extension_open = state.status == ProjectStatus::Funding
+            && storage::get_extension(&env, project_id)
+                .is_some_and(|p| p.status == ExtensionStatus::Pending && now < p.ends_at);
+        if state.status != ProjectStatus::Cancelled
+            && !extension_open
+            && !Self::goal_missed(&env, &config, &state)
+        {
+            panic_with_error!(&env, Error::NotRefundable);
+        }
+
+        // Zero every contribution and debit the balances before any token
+        // call, so a hostile token cannot claim the same refund twice.
+        let tokens = storage::get_accepted_tokens(&env, project_id);
+        let mut refunds = Vec::new(&env);
+        for contribution in storage::get_contributions(&env, project_id, &donator, &tokens).iter() {
+            let balance = storage::get_token_balance(&env, project_id, &contribution.token);
+            let amount = contribution.amount.min(balance);
+            if amount <= 0 {
+                continue;
+            }
+            storage::set_token_balance(&env, project_id, &contribution.token, balance - amount);
+            storage::record_contribution(&env, project_id, &donator, &contribution.token, -amount);
+            rounds::record_contribution(&env, project_id, &donator, &contribution.token, -amount);
+            // The refund covers every deposit, so none is left to withdraw,
+            // and the match those deposits drew goes back to its pledges.
+            storage::set_deposit_log(
+                &env,
+                project_id,
+                &donator,
+                &contribution.token,
+                &Vec::new(&env),
+            );
+            pledges::reverse(&env, project_id, &donator, &contribution.token, amount);
+            refunds.push_back(Contribution {
+                token: contribution.token,
+                amount,
+            });
+        }
+        if refunds.is_empty() {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+        votes::sync_extension_vote(&env, project_id, &donator);
+
+        let contract_address = env.current_contract_address();
+        for refund in refunds.iter() {
+            let token_client = token::Client::new(&env, &refund.token);
+            token_client.transfer(&contract_address, &donator, &refund.amount);
+        }
+
+        Self::release_lock(&env);
+
+        for refund in refunds.iter() {
+            events::emit_donation_refunded(
+                &env,
+                project_id,
+                donator.clone(),
+                refund.token,
+                refund.amount,
+            );
+        }
+        refunds
+    }
+
+    /// Propose moving a `Funding` project's deadline to `new_deadline`.
+    ///
+    /// Opens a window of the project's `extension_window` seconds in which
+    /// donors vote with `vote_extension` and may leave with `claim_refund`.
+    /// The new deadline only takes effect through `finalize_extension` once
+    /// the window has ended.
+    ///
+    /// - Must be authorised by the project creator.
+    /// - Panics with `Error::ProjectExpired` once the current deadline has
+    ///   passed.
+    /// - Panics with `Error::InvalidDeadline` unless `new_deadline` is later
+    ///   than the current deadline and within `max_deadline_duration` of now.
+    /// - Panics with `GovernanceError::ExtensionPending` while another
+    ///   proposal is undecided.
+    pub fn propose_extension(env: Env, project_id: u64, new_deadline: u64) -> ExtensionProposal {
+        Self::require_not_paused(&env);
+        let (config, state) = load_project_pair(&env, project_id);
+        config.creator.require_auth();
+
+        if state.status != ProjectStatus::Funding {
+            panic_with_error!(&env, Error::ProjectNotActive);
+        }
+        let now = env.ledger().timestamp();
+        if now >= config.deadline {
+            panic_with_error!(&env, Error::ProjectExpired);
+        }
+        let previous = storage::get_extension(&env, project_id);
+        if let Some(p) = &previous {
+            if p.status == ExtensionStatus::Pending {
+                panic_with_error!(&env, GovernanceError::ExtensionPending);
+            }
+        }
+        let params = storage::get_params_at(&env, config.params_version);
+        if new_deadline <= config.deadline
+            || new_deadline > now.saturating_add(params.max_deadline_duration)
+        {
+            panic_with_error!(&env, Error::InvalidDeadline);
+        }
+
+        let proposal = ExtensionProposal {
+            project_id,
+            seq: previous.map(|p| p.seq + 1).unwrap_or(0),
+            old_deadline: config.deadline,
+            new_deadline,
+            ends_at: now.saturating_add(params.extension_window),
+            objection_bps: params.extension_objection_bps,
+            approve_weight: 0,
+            object_weight: 0,
+            status: ExtensionStatus::Pending,
+        };
+        storage::save_extension(&env, &proposal);
+
+        events::emit_extension_proposed(&env, proposal.clone());
+        proposal
+    }
+
+    /// Approve or object to the project's pending deadline extension.
+    ///
+    /// The vote weighs `donor`'s current contribution; voting again replaces
+    /// the earlier vote.
+    ///
+    /// - Must be authorised by `donor`.
+    /// - Panics with `GovernanceError::VotingClosed` outside the window.
+    /// - Panics with `GovernanceError::NoVotingPower` if `donor` has no
+    ///   contribution to the project.
+    pub fn vote_extension(env: Env, project_id: u64, donor: Address, approve: bool) {
+        Self::require_not_paused(&env);
+        donor.require_auth();
+
+        let mut proposal = Self::load_extension(&env, project_id);
+        if proposal.status != ExtensionStatus::Pending
+            || env.ledger().timestamp() >= proposal.ends_at
+        {
+            panic_with_error!(&env, GovernanceError::VotingClosed);
+        }
+        let weight = votes::voting_power(&env, project_id, &donor);
+        if weight <= 0 {
+            panic_with_error!(&env, GovernanceError::NoVotingPower);
+        }
+
+        votes::cast_extension_vote(&env, &mut proposal, &donor, approve, weight);
+        storage::save_extension(&env, &proposal);
+
+        events::emit_extension_voted(&env, project_id, donor, approve, weight);
+    }
+
+    /// Decide the project's pending extension once its window has ended,
+    /// and apply the new deadline if approved. Callable by anyone.
+    ///
+    /// A project that has meanwhile left `Funding` and `Active` keeps its
+    /// deadline, as does an `AllOrNothing` project still `Funding` once its
+    /// original deadline has passed.
+    ///
+    /// - Panics with `GovernanceError::VotingOpen` before `ends_at`.
+    /// - Panics with `GovernanceError::VotingClosed` if already decided.
+    pub fn finalize_extension(env: Env, project_id: u64) -> ExtensionStatus {
+        Self::require_not_paused(&env);
+        let mut proposal = Self::load_extension(&env, project_id);
+        if proposal.status != ExtensionStatus::Pending {
+            panic_with_error!(&env, GovernanceError::VotingClosed);
+        }
+        if env.ledger().timestamp() < proposal.ends_at {
+            panic_with_error!(&env, GovernanceError::VotingOpen);
+        }
+
+        let (mut config, state) = load_project_pair(&env, project_id);
+        let open = matches!(
+            state.status,
+            ProjectStatus::Funding | ProjectStatus::Active | ProjectStatus::UnderReview
+        );
+        // Refunds of an all-or-nothing project open at its deadline and
+        // cannot be taken back by a late approval.
+        let refunds_open = state.status == ProjectStatus::Funding
+            && config.funding_mode == FundingMode::AllOrNothing
+            && env.ledger().timestamp() >= config.deadline;
+        proposal.status = if open && !refunds_open {
+            votes::extension_outcome(&proposal, votes::total_voting_power(&env, project_id))
+        } else {
+            ExtensionStatus::Rejected
+        };
+        storage::save_extension(&env, &proposal);
+        if proposal.status == ExtensionStatus::Approved {
+            config.deadline = proposal.new_deadline;
+            storage::save_project_config(&env, &config);
+        }
+
+        events::emit_extension_resolved(&env, project_id, proposal.status.clone(), config.deadline);
+        proposal.status
+    }
+
+    /// Latest deadline extension proposal of a project.
+    ///
+    /// Panics with `GovernanceError::ExtensionNotFound` if none was made.
+    pub fn get_extension(env: Env, project_id: u64) -> ExtensionProposal {
+        Self::load_extension(&env, project_id)
+    }
+
+    /// Submit the proof of a `DonorVote` project and open the donor vote on
+    /// releasing its funds.
+    ///
+    /// Deposits and withdrawals close while the project is `Voting`, so
+    /// every donor's voting power is fixed for the vote.
+    ///
+    /// - Must be authorised by the project creator.
+    /// - Panics with `GovernanceError::WrongVerificationMode` for oracle-verified projects.
+    /// - Panics with `Error::ProjectNotActive` unless the project is `Funding` or `Active`.
+    /// - Panics with `Error::GoalNotReached` if an `AllOrNothing` project is not `Active`.
+    /// - Panics with `Error::VerificationFailed` if `proof` and `salt`
+    ///   do not open the registered commitment.
+    pub fn start_release_vote(
+        env: Env,
+        project_id: u64,
+        proof: ContentId,
+        salt: BytesN<32>,
+    ) -> ReleaseVote {
+        Self::require_not_paused(&env);
+        let (config, mut state) = load_project_pair(&env, project_id);
+        config.creator.require_auth();
+
+        if config.verification_mode != VerificationMode::DonorVote {
+            panic_with_error!(&env, GovernanceError::WrongVerificationMode);
+        }
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => panic_with_error!(&env, Error::ProjectNotActive),
+        }
+        if config.funding_mode == FundingMode::AllOrNothing && state.status != ProjectStatus::Active
+        {
+            panic_with_error!(&env, Error::GoalNotReached);
+        }
+        Self::require_proof(&env, &config, &proof, &salt);
+
+        let params = storage::get_params_at(&env, config.params_version);
+        let vote = ReleaseVote {
+            project_id,
+            proof,
+            ends_at: env
+                .ledger()
+                .timestamp()
+                .saturating_add(params.release_vote_window),
+            quorum_bps: params.release_quorum_bps,
+            majority_bps: params.release_majority_bps,
+            approve_weight: 0,
+            reject_weight: 0,
+            status: ReleaseVoteStatus::Open,
+        };
+        storage::save_release_vote(&env, &vote);
+        state.status = ProjectStatus::Voting;
+        save_project_state(&env, project_id, &state);
+
+        events::emit_release_vote_started(&env, vote.clone());
+        vote
+    }
+
+    /// Approve or reject the release of a `Voting` project's funds.
+    ///
+    /// The ballot weighs `donor`'s contribution; voting again replaces the
+    /// earlier ballot.
+    ///
+    /// - Must be authorised by `donor`.
+    /// - Panics with `GovernanceError::VotingClosed` outside the window.
+    /// - Panics with `GovernanceError::NoVotingPower` if `donor` has no
+    ///   contribution to the project.
+    pub fn vote_release(env: Env, project_id: u64, donor: Address, approve: bool) {
+        Self::require_not_paused(&env);
+        donor.require_auth();
+
+        let mut vote = Self::load_release_vote(&env, project_id);
+        if vote.status != ReleaseVoteStatus::Open || env.ledger().timestamp() >= vote.ends_at {
+            panic_with_error!(&env, GovernanceError::VotingClosed);
+        }
+        let weight = votes::voting_power(&env, project_id, &donor);
+        if weight <= 0 {
+            panic_with_error!(&env, GovernanceError::NoVotingPower);
+        }
+
+        votes::cast_release_vote(&env, &mut vote, &donor, approve, weight);
+        storage::save_release_vote(&env, &vote);
+
+        events::emit_release_voted(&env, project_id, donor, approve, weight);
+    }
+
+    /// Close a release vote once its window has ended. Callable by anyone.
+    ///
+    /// If the votes cast reach the quorum and approvals the majority, funds
+    /// are released to the creator and the project is `Completed`;
+    /// otherwise it is `Cancelled` and donors may claim refunds.
+    ///
+    /// - Panics with `GovernanceError::VotingOpen` before `ends_at`.
+    /// - Panics with `GovernanceError::VotingClosed` if already closed.
+    pub fn finalize_release_vote(env: Env, project_id: u64) -> ReleaseVoteStatus {
+        Self::require_not_paused(&env);
+        let mut vote = Self::load_release_vote(&env, project_id);
+        if vote.status != ReleaseVoteStatus::Open {
+            panic_with_error!(&env, GovernanceError::VotingClosed);
+        }
+        if env.ledger().timestamp() < vote.ends_at {
+            panic_with_error!(&env, GovernanceError::VotingOpen);
+        }
+
+        let (config, mut state) = load_project_pair(&env, project_id);
+        let passed = votes::release_passed(&vote, votes::total_voting_power(&env, project_id));
+        vote.status = if passed {
+            ReleaseVoteStatus::Passed
+        } else {
+            ReleaseVoteStatus::Failed
+        };
+        storage::save_release_vote(&env, &vote);
+
+        if passed {
+            Self::release_funds(&env, project_id, &config.creator, &mut state);
+        } else {
+            state.status = ProjectStatus::Cancelled;
+            save_project_state(&env, project_id, &state);
+        }
+
+        events::emit_release_vote_closed(&env, vote.clone());
+        vote.status
+    }
+
+    /// Release vote of a `DonorVote` project.
+    ///
+    /// Panics with `GovernanceError::VoteNotFound` if none was started.
+    pub fn get_release_vote(env: Env, project_id: u64) -> ReleaseVote {
+        Self::load_release_vote(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Internal Helpers
+    // ─────────────────────────────────────────────────────────
+
+    fn require_not_paused(env: &Env) {
+        if storage::is_paused(env) {
+            panic_with_error!(env, Error::ProtocolPaused);
+        }
+    }
+
+    /// Validate one registration and build its `Project` under `id`.
+    ///
+    /// Shared by `register_project_with_options` and `register_projects`;
+    /// nothing is written to storage.
+    fn new_project(
+        env: &Env,
+        params: &ProtocolParams,
+        id: u64,
+        creator: &Address,
+        input: ProjectInput,
+    ) -> Project {
+        let ProjectInput {
+            accepted_tokens,
+            goal,
+            proof,
+            deadline,
+            options,
+        } = input;
+
+        if accepted_tokens.is_empty() {
+            panic_with_error!(env, Error::EmptyAcceptedTokens);
+        }
+        if !proofs::is_commitment(&proof) {
+            panic_with_error!(env, GovernanceError::InvalidContentId);
+        }
+        if accepted_tokens.len() > params.max_accepted_tokens {
+            panic_with_error!(env, Error::TooManyTokens);
+        }
+
+        // Check for duplicate tokens
+        for i in 0..accepted_tokens.len() {
+            let t_i = accepted_tokens.get(i).unwrap();
+            for j in (i + 1)..accepted_tokens.len() {
+                if t_i == accepted_tokens.get(j).unwrap() {
+                    panic_with_error!(env, Error::DuplicateToken);
+                }
+            }
+            if !storage::is_token_allowed(env, &t_i) {
+                panic_with_error!(env, Error::TokenNotAllowed);
+            }
+        }
+
+        if goal <= 0 || goal > params.max_goal {
+            panic_with_error!(env, Error::InvalidGoal);
+        }
+
+        // `goal` is only a token amount under the per-token rules; weighted
+        // and quoted totals are measured in their own units.
+        let mut token_goals = options.token_goals;
+        let per_token = matches!(options.goal_rule, GoalRule::AnyToken | GoalRule::AllTokens);
+        if token_goals.is_empty() && per_token {
+            token_goals.set(accepted_tokens.get(0).unwrap(), goal);
+        }
+        for (token, token_goal) in token_goals.iter() {
+            if !accepted_tokens.contains(&token) || token_goal <= 0 || token_goal > params.max_goal
+            {
+                panic_with_error!(env, Error::InvalidGoal);
+            }
+        }
+        // A cap below a token's own goal would make that goal unreachable.
+        for (token, cap) in options.max_raise.iter() {
+            if !accepted_tokens.contains(&token)
+                || cap <= 0
+                || cap < token_goals.get(token.clone()).unwrap_or(0)
+            {
+                panic_with_error!(env, Error::InvalidGoal);
+            }
+        }
+        match options.goal_rule {
+            GoalRule::WeightedSum(ref weights) => {
+                if weights.is_empty() {
+                    panic_with_error!(env, Error::InvalidGoal);
+                }
+                for (token, weight) in weights.iter() {
+                    if !accepted_tokens.contains(&token) || weight == 0 {
+                        panic_with_error!(env, Error::InvalidGoal);
+                    }
+                }
+            }
+            GoalRule::QuoteValue(ref quote) => {
+                if quote.max_price_age == 0 {
+                    panic_with_error!(env, Error::InvalidGoal);
+                }
+                Self::acquire_lock(env);
+                let feed_decimals = price_feed::PriceFeedClient::new(env, &quote.feed).decimals();
+                Self::release_lock(env);
+                if feed_decimals != quote.decimals {
+                    panic_with_error!(env, Error::InvalidGoal);
+                }
+            }
+            GoalRule::AnyToken | GoalRule::AllTokens => {}
+        }
+
+        let now = env.ledger().timestamp();
+        let min_deadline = now.saturating_add(params.min_deadline_duration);
+        let max_deadline = now.saturating_add(params.max_deadline_duration);
+        if deadline <= min_deadline || deadline > max_deadline {
+            panic_with_error!(env, Error::InvalidDeadline);
+        }
+
+        Project {
+            id,
+            creator: creator.clone(),
+            accepted_tokens,
+            goal,
+            proof,
+            deadline,
+            token_goals,
+            goal_rule: options.goal_rule,
+            funding_mode: options.funding_mode,
+            max_raise: options.max_raise,
+            excess_policy: options.excess_policy,
+            verification_mode: options.verification_mode,
+            proof_salted: true,
+            proof_review: ProofReview::None,
+            evidence_count: 0,
+            verified_evidence: None,
+            status: ProjectStatus::Funding,
+            donation_count: 0,
+            unique_donors: 0,
+            deposit_count: 0,
+        }
+    }
+
+    /// Check a revealed proof against the project's registered one.
+    ///
+    /// - Panics with `GovernanceError::InvalidContentId` if `proof` uses an
+    ///   unknown multihash code or a digest of the wrong length.
+    /// - Panics with `GovernanceError::ProofAlgorithmMismatch` if `proof`
+    ///   uses a different hash algorithm than the one registered.
+    /// - Panics with `Error::VerificationFailed` unless `proof` and `salt`
+    ///   open the commitment, or, for a project registered before
+    ///   commitments, unless the digests are equal.
+    fn require_proof(env: &Env, config: &ProjectConfig, proof: &ContentId, salt: &BytesN<32>) {
+        if !proofs::is_known(proof) {
+            panic_with_error!(env, GovernanceError::InvalidContentId);
+        }
+        if proof.code != config.proof.code {
+            panic_with_error!(env, GovernanceError::ProofAlgorithmMismatch);
+        }
+        let salted = storage::is_proof_salted(env, config.id);
+        if !proofs::opens(env, &config.proof, proof, salt, salted) {
+            panic_with_error!(env, Error::VerificationFailed);
+        }
+    }
+
+    /// Persist a freshly registered project and emit its `created` event.
+    fn store_new_project(env: &Env, project: &Project) {
+        save_project(env, project);
+
+        // Standardized event emission
+        if let Some(token) = project.accepted_tokens.get(0) {
+            events::emit_project_created(
+                env,
+                project.id,
+                project.creator.clone(),
+                token,
+                project.goal,
+                project.funding_mode,
+                project.proof.clone(),
+            );
+        }
+    }
+
+    fn load_subscription(env: &Env, sub_id: u64) -> Subscription {
+        match storage::get_subscription(env, sub_id) {
+            Some(sub) => sub,
+            None => panic_with_error!(env, Error::SubscriptionNotFound),
+        }
+    }
+
+    /// Mark `sub` as ended with `status`, save it and emit `sub_end`.
+    /// Returns the `0` charged, for `execute_subscription`.
+    fn end_subscription(env: &Env, mut sub: Subscription, status: SubscriptionStatus) -> i128 {
+        sub.status = status;
+        storage::save_subscription(env, &sub);
+        events::emit_subscription_ended(env, &sub);
+        0
+    }
+
+    fn load_extension(env: &Env, project_id: u64) -> ExtensionProposal {
+        match storage::get_extension(env, project_id) {
+            Some(proposal) => proposal,
+            None => panic_with_error!(env, GovernanceError::ExtensionNotFound),
+        }
+    }
+
+    fn load_release_vote(env: &Env, project_id: u64) -> ReleaseVote {
+        match storage::get_release_vote(env, project_id) {
+            Some(vote) => vote,
+            None => panic_with_error!(env, GovernanceError::VoteNotFound),
+        }
+    }
+
+    fn load_match(env: &Env, match_id: u64) -> MatchPledge {
+        match storage::get_match(env, match_id) {
+            Some(pledge) => pledge,
+            None => panic_with_error!(env, Error::MatchNotFound),
+        }
+    }
+
+    fn load_round(env: &Env, round_id: u64) -> Round {
+        match storage::get_round(env, round_id) {
+            Some(round) => round,
+            None => panic_with_error!(env, Error::RoundNotFound),
+        }
+    }
+
+    /// Mark the project `Completed` and pay every accepted token's balance
+    /// out to `creator`.
+    fn release_funds(env: &Env, project_id: u64, creator: &Address, state: &mut ProjectState) {
+        Self::acquire_lock(env);
+
+        // Transition to Completed — only write the state entry. The status
+        // and every drained balance are persisted before any token call so a
+        // hostile token cannot observe (or re-release) a half-settled project.
+        state.status = ProjectStatus::Completed;
+        save_project_state(env, project_id, state);
+
+        let mut payouts: Vec<(Address, i128)> = Vec::new(env);
+        for token in storage::get_accepted_tokens(env, project_id).iter() {
+            // Drain the token balance (gets balance and zeros it).
+            let balance = drain_token_balance(env, project_id, &token);
+            if balance > 0 {
+                payouts.push_back((token, balance));
+            }
+        }
+
+        // Transfer all deposited tokens to the creator.
+        // If any transfer fails, panic to revert the entire transaction.
+        let contract_address = env.current_contract_address();
+        for (token, balance) in payouts.iter() {
+            let token_client = token::Client::new(env, &token);
+            token_client.transfer(&contract_address, creator, &balance);
+
+            // Emit funds_released event for this token.
+            events::emit_funds_released(env, project_id, token, balance);
+        }
+
+        Self::release_lock(env);
+    }
+
+    /// Take the reentrancy lock before calling out to a token contract.
+    ///
+    /// Accepted tokens are arbitrary creator-supplied contracts, so any
+    /// entry point that transfers tokens must hold this lock across the call.
+    fn acquire_lock(env: &Env) {
+        if storage::is_reentrancy_locked(env) {
+            panic_with_error!(env, Error::Reentrancy);
+        }
+        storage::set_reentrancy_locked(env, true);
+    }
+
+    fn release_lock(env: &Env) {
+        storage::set_reentrancy_locked(env, false);
+    }
+
+    /// Validate a deposit of `amount` in `token` and return the amount that
+    /// will be accepted after applying the project's `max_raise` cap.
+    fn check_deposit(
+        env: &Env,
+        config: &ProjectConfig,
+        state: &ProjectState,
+        token: &Address,
+        amount: i128,
+    ) -> i128 {
+        match Self::accepted_amount(env, config, state, token, amount) {
+            Ok(amount) => amount,
+            Err(error) => panic_with_error!(env, error),
+        }
+    }
+
+    /// The checks behind [`Self::check_deposit`], returning the error
+    /// instead of panicking so that subscriptions can end cleanly.
+    fn accepted_amount(
+        env: &Env,
+        config: &ProjectConfig,
+        state: &ProjectState,
+        token: &Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Check expiration
+        if env.ledger().timestamp() >= config.deadline {
+            return Err(Error::ProjectExpired);
+        }
+
+        // Basic status check: must be Funding or Active.
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => return Err(Error::ProjectNotActive),
+        }
+
+        // Verify token is accepted.
+        if !storage::get_accepted_tokens(env, config.id).contains(token) {
+            return Err(Error::NotAuthorized);
+        }
+
+        // The token must still be allowlisted; delisted tokens stop accepting
+        // new funds but their existing escrow remains releasable.
+        if !storage::is_token_allowed(env, token) {
+            return Err(Error::TokenNotAllowed);
+        }
+        if let Some(metadata) = storage::get_token_metadata(env, token) {
+            if amount < metadata.min_deposit {
+                return Err(Error::BelowMinDeposit);
+            }
+        }
+        Self::capped_amount(env, config, token, amount)
+    }
+
+    /// Record an already validated deposit and save the project state.
+    ///
+    /// `donator` is `None` for committed (anonymous) donations, which only
+    /// count as a deposit transaction and towards the totals raised.
+    /// Returns true if this deposit moved the project from `Funding` to
+    /// `Active`. Must be called with the reentrancy lock held.
+    fn credit_deposit(
+        env: &Env,
+        config: &ProjectConfig,
+        state: &mut ProjectState,
+        donator: Option<&Address>,
+        token: &Address,
+        amount: i128,
+    ) -> bool {
+        let project_id = config.id;
+
+        // Donor metrics: unique (donator, token) pairs, unique donors and
+        // deposit transactions are counted independently.
+        if let Some(donator) = donator {
+            Self::count_donor(env, project_id, state, donator, token);
+        }
+        state.deposit_count += 1;
+
+        // Update the per-token balance.
+        storage::add_to_token_balance(env, project_id, token, amount);
+        storage::add_total_raised(env, project_id, token, amount);
+        pledges::apply(env, config, donator, token, amount);
+
+        if let Some(donator) = donator {
+            storage::add_contribution(env, project_id, donator, token, amount);
+            rounds::record_contribution(env, project_id, donator, token, amount);
+
+            // Remember the deposit while it can still be withdrawn.
+            let window = storage::get_params_at(env, config.params_version).cooling_off_period;
+            if window > 0 {
+                storage::record_deposit(env, project_id, donator, token, amount, window);
+            }
+        }
+
+        let goal_reached = Self::update_goal_status(env, config, state);
+        save_project_state(env, project_id, state);
+        goal_reached
+    }
+
+    /// Move a `Funding` project to `Active` if its goal is now reached.
+    /// Returns true on that transition; the caller saves `state`.
+    fn update_goal_status(env: &Env, config: &ProjectConfig, state: &mut ProjectState) -> bool {
+        let goal_reached = state.status == ProjectStatus::Funding
+            && goals::is_goal_reached(
+                env,
+                config.id,
+                config.goal,
+                &config.token_goals,
+                &config.goal_rule,
+            );
+        if goal_reached {
+            state.status = ProjectStatus::Active;
+        }
+        goal_reached
+    }
+
+    /// Return true if an `AllOrNothing` project is still `Funding` at its
+    /// deadline and its goal is not met even at current prices.
+    fn goal_missed(env: &Env, config: &ProjectConfig, state: &ProjectState) -> bool {
+        state.status == ProjectStatus::Funding
+            && config.funding_mode == FundingMode::AllOrNothing
+            && env.ledger().timestamp() >= config.deadline
+            && !goals::is_goal_reached(
+                env,
+                config.id,
+                config.goal,
+                &config.token_goals,
+                &config.goal_rule,
+            )
+    }
+
+    /// Update the unique-pair and unique-donor metrics for `donator`.
+    fn count_donor(
+        env: &Env,
+        project_id: u64,
+        state: &mut ProjectState,
+        donator: &Address,
+        token: &Address,
+    ) {
+        if !storage::has_donator_seen(env, project_id, donator, token) {
+            state.donation_count += 1;
+            storage::mark_donator_seen(env, project_id, donator, token);
+        }
+        if storage::add_donor(env, project_id, donator) {
+            state.unique_donors += 1;
+        }
+    }
+
+    /// Trim `amount` to the headroom left under `token`'s `max_raise` cap,
+    /// or refuse it with `Error::CapExceeded` as the project's policy dictates.
+    fn capped_amount(
+        env: &Env,
+        config: &ProjectConfig,
+        token: &Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let Some(cap) = config.max_raise.get(token.clone()) else {
+            return Ok(amount);
+        };
+        let balance = storage::get_token_balance(env, config.id, token);
+        let headroom = match cap.checked_sub(balance) {
+            Some(h) if h > 0 => h,
+            _ => return Err(Error::CapExceeded),
+        };
+        if amount <= headroom {
+            return Ok(amount);
+        }
+        match config.excess_policy {
+            ExcessPolicy::Reject => Err(Error::CapExceeded),
+            ExcessPolicy::AcceptPartial => Ok(headroom),
         }
     }
 }
@@ -0,0 +1,91 @@
+//! # Matching Pledges
+//!
+//! A sponsor escrows up to `cap` to match donations to one project at
+//! `ratio_bps`. Every deposit in the pledge's token before it expires draws
+//! its matched portion from the escrow straight into the project's balance,
+//! within the project's `max_raise` headroom.
+//!
+//! Matching a known donor is recorded per donor, so a cooling-off
+//! withdrawal hands the corresponding match back to the pledge instead of
+//! leaving sponsor funds behind for a donation that no longer exists.
+
+use soroban_sdk::{Address, Env};
+
+use crate::events;
+use crate::storage;
+use crate::types::ProjectConfig;
+
+/// Basis-point denominator for `MatchPledge::ratio_bps`.
+pub const RATIO_DENOMINATOR: i128 = 10_000;
+
+/// Match a deposit of `amount` in `token` from every live pledge of the
+/// project. Returns the total credited to the project's balance.
+pub fn apply(
+    env: &Env,
+    config: &ProjectConfig,
+    donor: Option<&Address>,
+    token: &Address,
+    amount: i128,
+) -> i128 {
+    let project_id = config.id;
+    let now = env.ledger().timestamp();
+    let mut total: i128 = 0;
+    for id in storage::get_project_matches(env, project_id).iter() {
+        let Some(mut pledge) = storage::get_match(env, id) else {
+            continue;
+        };
+        if &pledge.token != token || pledge.remaining == 0 || now >= pledge.expires_at {
+            continue;
+        }
+        let mut credit = amount.saturating_mul(pledge.ratio_bps as i128) / RATIO_DENOMINATOR;
+        credit = credit.min(pledge.remaining);
+        if let Some(cap) = config.max_raise.get(token.clone()) {
+            let balance = storage::get_token_balance(env, project_id, token);
+            credit = credit.min(cap.saturating_sub(balance));
+        }
+        if credit <= 0 {
+            continue;
+        }
+
+        pledge.remaining -= credit;
+        pledge.matched += credit;
+        storage::save_match(env, &pledge);
+        storage::add_to_token_balance(env, project_id, token, credit);
+        storage::add_total_raised(env, project_id, token, credit);
+        storage::add_matched_total(env, project_id, token, credit);
+        if let Some(donor) = donor {
+            storage::add_matched_for(env, id, donor, credit);
+        }
+        events::emit_donation_matched(env, id, project_id, token.clone(), credit);
+        total += credit;
+    }
+    total
+}
+
+/// Return to their pledges the match of a withdrawal of `amount` by
+/// `donor`, up to what each pledge matched for that donor.
+pub fn reverse(env: &Env, project_id: u64, donor: &Address, token: &Address, amount: i128) {
+    for id in storage::get_project_matches(env, project_id).iter() {
+        let Some(mut pledge) = storage::get_match(env, id) else {
+            continue;
+        };
+        if &pledge.token != token {
+            continue;
+        }
+        let matched_for = storage::get_matched_for(env, id, donor);
+        let back =
+            (amount.saturating_mul(pledge.ratio_bps as i128) / RATIO_DENOMINATOR).min(matched_for);
+        if back <= 0 {
+            continue;
+        }
+
+        pledge.remaining += back;
+        pledge.matched -= back;
+        storage::save_match(env, &pledge);
+        let balance = storage::get_token_balance(env, project_id, token);
+        storage::set_token_balance(env, project_id, token, balance - back);
+        storage::add_total_raised(env, project_id, token, -back);
+        storage::add_matched_total(env, project_id, token, -back);
+        storage::add_matched_for(env, id, donor, -back);
+    }
+}
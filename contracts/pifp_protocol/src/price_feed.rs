@@ -0,0 +1,76 @@
+//! # Price Feed
+//!
+//! Client for SEP-40 style price oracles (e.g. Reflector), used to value
+//! escrowed balances in a quote currency for [`GoalRule::QuoteValue`] goals.
+//!
+//! Only the read side of SEP-40 is needed. [`Asset`] and [`PriceData`]
+//! mirror the SEP-40 definitions so values round-trip through any
+//! conforming feed.
+//!
+//! [`GoalRule::QuoteValue`]: crate::types::GoalRule::QuoteValue
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+use crate::storage;
+use crate::types::QuoteGoal;
+
+/// Decimals assumed for tokens with no allowlist metadata.
+/// Matches the 7 decimals used by Stellar Asset Contracts.
+pub const DEFAULT_TOKEN_DECIMALS: u32 = 7;
+
+/// Asset identifier, as defined by SEP-40.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// A price observation, as defined by SEP-40.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeed {
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+    fn decimals(env: Env) -> u32;
+}
+
+/// Current price of `token` from `quote.feed`, or `None` if the feed has no
+/// price, the price is not positive, it is older than `quote.max_price_age`,
+/// or it claims a timestamp in the future.
+pub fn fresh_price(env: &Env, quote: &QuoteGoal, token: &Address) -> Option<i128> {
+    let feed = PriceFeedClient::new(env, &quote.feed);
+    let data = feed.lastprice(&Asset::Stellar(token.clone()))?;
+    let now = env.ledger().timestamp();
+    if data.price <= 0
+        || data.timestamp > now
+        || data.timestamp.saturating_add(quote.max_price_age) < now
+    {
+        return None;
+    }
+    Some(data.price)
+}
+
+/// Value of `amount` base units of `token` in the quote currency, at the
+/// feed's price precision. Stale or missing prices value the balance at zero.
+pub fn quote_value(env: &Env, quote: &QuoteGoal, token: &Address, amount: i128) -> i128 {
+    if amount <= 0 {
+        return 0;
+    }
+    let Some(price) = fresh_price(env, quote, token) else {
+        return 0;
+    };
+    let decimals = storage::get_token_metadata(env, token)
+        .map(|m| m.decimals)
+        .unwrap_or(DEFAULT_TOKEN_DECIMALS);
+    let scale = 10i128.checked_pow(decimals).unwrap_or(i128::MAX);
+    amount
+        .checked_mul(price)
+        .map(|v| v / scale)
+        .unwrap_or_else(|| (amount / scale).saturating_mul(price))
+}
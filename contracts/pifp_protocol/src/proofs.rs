@@ -0,0 +1,78 @@
+//! # Proof Identifiers
+//!
+//! A project's proof is named by a [`ContentId`]: a multihash code and the
+//! digest bytes, as in an IPFS CIDv1. Only the codes below are accepted, and
+//! each fixes the digest length except BLAKE3, whose output length varies.
+//!
+//! At registration the creator gives the code of the proof artifact's
+//! identifier together with the salted commitment
+//! `sha256(artifact_digest || salt)` as the digest. Verification reveals the
+//! artifact's identifier and the salt. The code is public, so a reveal under
+//! a different hash algorithm is reported as such before the commitment is
+//! checked. Projects registered before commitments are migrated from their
+//! baseline config with the artifact digest itself as the proof, and are
+//! compared directly.
+
+use soroban_sdk::{Bytes, BytesN, Env};
+
+use crate::types::ContentId;
+
+/// Multihash code of SHA2-256.
+pub const SHA2_256: u32 = 0x12;
+/// Multihash code of SHA2-512.
+pub const SHA2_512: u32 = 0x13;
+/// Multihash code of SHA3-256.
+pub const SHA3_256: u32 = 0x16;
+/// Multihash code of Keccak-256.
+pub const KECCAK_256: u32 = 0x1b;
+/// Multihash code of BLAKE3.
+pub const BLAKE3: u32 = 0x1e;
+/// Multihash code of BLAKE2b-256.
+pub const BLAKE2B_256: u32 = 0xb220;
+
+/// Longest digest a [`ContentId`] may carry, in bytes.
+pub const MAX_DIGEST_LEN: u32 = 64;
+
+/// Length of a salted commitment digest, in bytes.
+pub const COMMITMENT_LEN: u32 = 32;
+
+/// Return true if `id` uses a known multihash code with a digest of the
+/// length that code produces.
+pub fn is_known(id: &ContentId) -> bool {
+    let len = id.digest.len();
+    match id.code {
+        SHA2_256 | SHA3_256 | KECCAK_256 | BLAKE2B_256 => len == 32,
+        SHA2_512 => len == 64,
+        BLAKE3 => len > 0 && len <= MAX_DIGEST_LEN,
+        _ => false,
+    }
+}
+
+/// Return true if `id` can be registered: a known code with a
+/// [`COMMITMENT_LEN`]-byte commitment as its digest.
+pub fn is_commitment(id: &ContentId) -> bool {
+    let known = matches!(
+        id.code,
+        SHA2_256 | SHA2_512 | SHA3_256 | KECCAK_256 | BLAKE3 | BLAKE2B_256
+    );
+    known && id.digest.len() == COMMITMENT_LEN
+}
+
+/// Return true if `revealed` and `salt` open the `registered` commitment,
+/// or, when `salted` is false, if the digests are equal. The caller has
+/// already checked that the codes match.
+pub fn opens(
+    env: &Env,
+    registered: &ContentId,
+    revealed: &ContentId,
+    salt: &BytesN<32>,
+    salted: bool,
+) -> bool {
+    if !salted {
+        return revealed.digest == registered.digest;
+    }
+    let mut preimage = revealed.digest.clone();
+    preimage.append(&Bytes::from_array(env, &salt.to_array()));
+    let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+    Bytes::from(commitment) == registered.digest
+}
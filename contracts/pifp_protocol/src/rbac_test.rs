@@ -1,12 +1,10 @@
 
 #![cfg(test)]
 
-use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, BytesN, Env, vec,
-};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Vec};
 
-use crate::{PifpProtocol, PifpProtocolClient, Role, Error};
+use crate::test_util::{dummy_commitment, dummy_proof, dummy_salt};
+use crate::{PifpProtocol, PifpProtocolClient, Role};
 
 // ─── Helpers ─────────────────────────────────────────────
 
@@ -25,14 +23,16 @@ fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
     (env, client, super_admin)
 }
 
-fn dummy_proof(env: &Env) -> BytesN<32> {
-    BytesN::from_array(env, &[0xabu8; 32])
-}
-
 fn future_deadline(env: &Env) -> u64 {
     env.ledger().timestamp() + 86_400
 }
 
+fn allow_tokens(client: &PifpProtocolClient, admin: &Address, tokens: &Vec<Address>) {
+    for token in tokens.iter() {
+        client.allow_token(admin, &token, &None);
+    }
+}
+
 // ─── 1. Initialisation ───────────────────────────────────
 
 #[test]
@@ -200,17 +200,31 @@ fn test_project_manager_can_register() {
     let pm       = Address::generate(&env);
     let tokens   = vec![&env, Address::generate(&env)];
     client.grant_role(&super_admin, &pm, &Role::ProjectManager);
-    let project = client.register_project(&pm, &tokens, &1000i128, &dummy_proof(&env), &future_deadline(&env));
+    allow_tokens(&client, &super_admin, &tokens);
+    let project = client.register_project(
+        &pm,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
     assert_eq!(project.creator, pm);
 }
 
 #[test]
 #[should_panic]
 fn test_no_role_cannot_register_project() {
-    let (env, client, _) = setup_with_init();
+    let (env, client, super_admin) = setup_with_init();
     let nobody = Address::generate(&env);
     let tokens = vec![&env, Address::generate(&env)];
-    client.register_project(&nobody, &tokens, &1000i128, &dummy_proof(&env), &future_deadline(&env));
+    allow_tokens(&client, &super_admin, &tokens);
+    client.register_project(
+        &nobody,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
 }
 
 // ─── 6. set_oracle + verify_and_release ─────────────────
@@ -226,8 +240,15 @@ fn test_oracle_can_verify() {
     client.set_oracle(&super_admin, &oracle);
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
     
-    let project = client.register_project(&creator, &tokens, &100i128, &proof, &future_deadline(&env));
-    client.verify_and_release(&oracle, &project.id, &proof);
+    allow_tokens(&client, &super_admin, &tokens);
+    let project = client.register_project(
+        &creator,
+        &tokens,
+        &100i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+    client.verify_and_release(&oracle, &project.id, &proof, &dummy_salt(&env));
     
     let completed = client.get_project(&project.id);
     assert_eq!(completed.status, crate::ProjectStatus::Completed);
@@ -243,6 +264,13 @@ fn test_non_oracle_cannot_verify() {
     let proof = dummy_proof(&env);
     
     client.grant_role(&super_admin, &pm, &Role::ProjectManager);
-    let project = client.register_project(&pm, &tokens, &100i128, &proof, &future_deadline(&env));
-    client.verify_and_release(&impersonator, &project.id, &proof);
+    allow_tokens(&client, &super_admin, &tokens);
+    let project = client.register_project(
+        &pm,
+        &tokens,
+        &100i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+    client.verify_and_release(&impersonator, &project.id, &proof, &dummy_salt(&env));
 }
\ No newline at end of file
@@ -0,0 +1,126 @@
+//! # Matching Rounds
+//!
+//! Quadratic funding: a sponsor pool is split across participating projects
+//! in proportion to `(Σ √cᵢ)² − Σ cᵢ`, where `cᵢ` is donor `i`'s counted
+//! contribution to the project. Many small donors therefore attract more
+//! matching than one large donor giving the same total.
+//!
+//! Only donations in the round's matching token, made by a known donor
+//! during the round window, are counted. Anonymous committed donations are
+//! not. A cooling-off withdrawal reduces the donor's counted contribution
+//! until the round is closed, so a deposit cannot be counted and then taken
+//! back.
+//!
+//! Square roots are integer square roots of token base units, updated
+//! incrementally as each donor's contribution changes.
+
+use soroban_sdk::{Address, Env, Vec, I256};
+
+use crate::storage;
+use crate::types::{Round, RoundStatus};
+
+/// Basis-point denominator for `Round::max_share_bps`.
+pub const SHARE_DENOMINATOR: i128 = 10_000;
+
+/// Integer square root: the largest `r` with `r * r <= n`, or `0` for `n <= 0`.
+pub fn isqrt(n: i128) -> i128 {
+    if n <= 0 {
+        return 0;
+    }
+    // Newton's method from an upper bound; converges monotonically.
+    let mut x = n;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Apply a change of `delta` to `donor`'s contribution to `project_id` in
+/// the round the project participates in, if that change counts.
+///
+/// Positive changes count during the round window; withdrawals count until
+/// the round is closed. The counted contribution never drops below zero.
+pub fn record_contribution(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+    delta: i128,
+) {
+    let Some(round_id) = storage::get_project_round(env, project_id) else {
+        return;
+    };
+    let Some(round) = storage::get_round(env, round_id) else {
+        return;
+    };
+    if round.status != RoundStatus::Open || &round.matching_token != token {
+        return;
+    }
+    let now = env.ledger().timestamp();
+    if delta > 0 && (now < round.starts_at || now >= round.ends_at) {
+        return;
+    }
+
+    let old = storage::get_round_contribution(env, round_id, project_id, donor);
+    let new = old.saturating_add(delta).max(0);
+    if new == old {
+        return;
+    }
+    let mut tally = storage::get_round_project(env, round_id, project_id);
+    tally.contributions += new - old;
+    tally.sum_sqrt += isqrt(new) - isqrt(old);
+    storage::set_round_project(env, round_id, project_id, &tally);
+    storage::set_round_contribution(env, round_id, project_id, donor, new);
+}
+
+/// `a * b / c` computed in 256 bits, so that large pools and tallies do not
+/// overflow the intermediate product. `None` if the result exceeds `i128`.
+fn mul_div(env: &Env, a: i128, b: i128, c: i128) -> Option<i128> {
+    I256::from_i128(env, a)
+        .mul(&I256::from_i128(env, b))
+        .div(&I256::from_i128(env, c))
+        .to_i128()
+}
+
+/// Compute the match of every project in `round`, in `round.projects` order.
+///
+/// Projects for which `eligible` is false get nothing and are left out of
+/// the split. Each match is capped at `max_share_bps` of the pool; capped
+/// excess is not redistributed. Returns `None` on arithmetic overflow.
+pub fn compute_matches(env: &Env, round: &Round, eligible: &Vec<bool>) -> Option<Vec<i128>> {
+    let mut ideals = Vec::new(env);
+    let mut total_ideal: i128 = 0;
+    for (i, project_id) in round.projects.iter().enumerate() {
+        let ideal = if eligible.get(i as u32).unwrap_or(false) {
+            let tally = storage::get_round_project(env, round.id, project_id);
+            tally
+                .sum_sqrt
+                .checked_mul(tally.sum_sqrt)?
+                .saturating_sub(tally.contributions)
+                .max(0)
+        } else {
+            0
+        };
+        total_ideal = total_ideal.checked_add(ideal)?;
+        ideals.push_back(ideal);
+    }
+
+    let cap = mul_div(
+        env,
+        round.pool,
+        round.max_share_bps as i128,
+        SHARE_DENOMINATOR,
+    )?;
+    let mut matches = Vec::new(env);
+    for ideal in ideals.iter() {
+        let share = if total_ideal == 0 {
+            0
+        } else {
+            mul_div(env, round.pool, ideal, total_ideal)?
+        };
+        matches.push_back(share.min(cap));
+    }
+    Some(matches)
+}
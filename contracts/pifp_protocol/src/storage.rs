@@ -8,6 +8,15 @@
 //! |------------------|-----------|------------------------------------|
 //! | `ProjectCount`   | `u64`     | Auto-increment project ID counter  |
 //! | `OracleKey`      | `Address` | Active trusted oracle address      |
+//! | `ReentrancyLock` | `bool`    | Held while external token calls run |
+//! | `AllowedTokenCount` | `u32`  | Number of allowlisted tokens       |
+//! | `Params`         | `ProtocolParams` | Current protocol parameters  |
+//! | `ParamsVersion`  | `u32`     | Version number of `Params`         |
+//! | `InstanceTtl`    | `(u32, u32)` | Instance TTL threshold and bump, copied out of `Params` |
+//! | `PersistentTtl`  | `(u32, u32)` | Persistent TTL threshold and bump, copied out of `Params` |
+//! | `SubscriptionCount` | `u64` | Auto-increment subscription ID counter |
+//! | `RoundCount`     | `u64`     | Auto-increment matching round ID counter |
+//! | `MatchCount`     | `u64`     | Auto-increment matching pledge ID counter |
 //!
 //! Instance TTL is bumped by **7 days** whenever it falls below 1 day remaining.
 //!
@@ -15,11 +24,47 @@
 //!
 //! | Key                | Type            | Description                      |
 //! |--------------------|-----------------|----------------------------------|
-//! | `ProjConfig(id)`   | `ProjectConfig` | Immutable project configuration  |
-//! | `ProjState(id)`    | `ProjectState`  | Mutable project state            |
+//! | `ProjConfigV2(id)` | `ProjectConfig` | Project configuration            |
+//! | `ProjConfig(id)`   | `LegacyProjectConfig` | Pre-options configuration, migrated on read |
+//! | `ProjStateV2(id)`  | `ProjectState`  | Mutable project state            |
+//! | `ProjState(id)`    | `LegacyProjectState` | Pre-metrics state, migrated on read |
+//! | `ProjTokens(id)`   | `Vec<Address>`  | Tokens the project accepts       |
+//! | `AllowedToken(addr)` | `Option<TokenMetadata>` | Allowlist entry for a token |
+//! | `AllowedTokenIdx(addr)` | `u32`        | Position of a token in the allowlist |
+//! | `AllowedTokenAt(i)` | `Address`      | Token at allowlist position `i`  |
+//! | `ParamsAt(v)`      | `ProtocolParams` | Historical protocol parameters |
+//! | `DepositLog(id, donor, token)` | `Vec<DepositRecord>` | Deposits still within the cooling-off window |
+//! | `Contribution(id, donor, token)` | `i128` | Net amount a donor gave in a token |
+//! | `TotalRaised(id, token)` | `i128`   | Net amount raised in a token; survives release |
+//! | `Commitment(id, c)` | `CommittedDonation` | Anonymous donation awaiting reveal |
+//! | `DonorCount(id)`   | `u32`           | Number of distinct donors        |
+//! | `DonorIdx(id, donor)` / `DonorAt(id, i)` | `u32` / `Address` | Dense donor index for `list_donors` |
+//! | `Keeper(donor, keeper)` | `bool` | Present while `keeper` may call `deposit_from` |
+//! | `Subscription(sub_id)` | `Subscription` | Recurring donation and its schedule |
+//! | `Round(round_id)` | `Round`       | Quadratic funding round          |
+//! | `ProjectRound(id)` | `u64`       | Open round the project participates in |
+//! | `RoundProject(round_id, id)` | `RoundProject` | Project's tally within a round |
+//! | `RoundContrib(round_id, id, donor)` | `i128` | Donor contribution counted by a round |
+//! | `Match(match_id)` | `MatchPledge` | Sponsor matching pledge          |
+//! | `ProjectMatches(id)` | `Vec<u64>` | Unsettled pledges of a project  |
+//! | `MatchedFor(match_id, donor)` | `i128` | Amount a pledge matched for a donor |
+//! | `MatchedTotal(id, token)` | `i128` | Part of `TotalRaised` credited by pledges |
+//! | `ContributedTotal(id, token)` | `i128` | Sum of donor contributions in a token |
+//! | `Extension(id)`   | `ExtensionProposal` | Latest deadline extension proposal |
+//! | `ExtensionVote(id, seq, donor)` | `Ballot` | Donor vote on proposal `seq` |
+//! | `ReleaseVote(id)` | `ReleaseVote`   | Donor vote on a `DonorVote` project's proof |
+//! | `ReleaseBallot(id, donor)` | `Ballot` | Donor ballot in the release vote |
+//! | `ProofReview(id)` | `ProofReview`   | Latest proof submission and its review |
+//! | `EvidenceCount(id)` | `u32`         | Number of evidence log entries   |
+//! | `Evidence(id, n)` | `EvidenceEntry` | Evidence log entry `n`           |
+//! | `VerifiedEvidence(id)` | `u32`      | Evidence entry cited at release  |
+//! | `SaltedProof(id)` | `bool`          | Present when `proof` is a salted commitment |
 //!
 //! Persistent TTL is bumped by **30 days** whenever it falls below 7 days remaining.
 //!
+//! All TTL figures above are the defaults; they are governed through
+//! [`ProtocolParams`] and may be changed by admins.
+//!
 //! ## Why split Config and State?
 //!
 //! Deposits are high-frequency writes. Writing the full `Project` struct (~150 bytes)
@@ -27,11 +72,17 @@
 //! ledger write costs by ~87% per deposit while keeping the public API clean via
 //! the reconstructed [`Project`] return type.
 
-use soroban_sdk::{contracttype, Address, Env, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec};
 
-use crate::types::{Project, ProjectBalances, ProjectConfig, ProjectState, TokenBalance};
+use crate::goals;
+use crate::types::{
+    Ballot, CommittedDonation, ContentId, Contribution, DepositRecord, EvidenceEntry, ExcessPolicy,
+    ExtensionProposal, FundingMode, GoalRule, LegacyProjectConfig, LegacyProjectState, MatchPledge,
+    Project, ProjectBalances, ProjectConfig, ProjectState, ProofReview, ProtocolParams,
+    ReleaseVote, Round, RoundProject, Subscription, TokenBalance, TokenMetadata, VerificationMode,
+};
 
-// ── TTL Constants ────────────────────────────────────────────────────
+// ── Default Parameters ───────────────────────────────────────────────
 
 /// Approximate ledgers per day (~5 seconds per ledger).
 const DAY_IN_LEDGERS: u32 = 17_280;
@@ -44,6 +95,53 @@ const INSTANCE_LIFETIME_THRESHOLD: u32 = DAY_IN_LEDGERS;
 const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
 
+/// At most 10 accepted tokens per project.
+const MAX_ACCEPTED_TOKENS: u32 = 10;
+
+/// Goal cap of 10^30 base units.
+const MAX_GOAL: i128 = 1_000_000_000_000_000_000_000_000_000_000;
+
+/// Max 5 years deadline (5 * 365 * 24 * 60 * 60).
+const MAX_DEADLINE_DURATION: u64 = 157_680_000;
+
+/// Donors may withdraw a deposit within 24 hours of making it.
+const COOLING_OFF_PERIOD: u64 = 86_400;
+
+/// Donors have 7 days to vote on a deadline extension.
+const EXTENSION_WINDOW: u64 = 7 * 86_400;
+
+/// Objections from 20% of donor voting power reject an extension.
+const EXTENSION_OBJECTION_BPS: u32 = 2_000;
+
+/// Donors have 7 days to vote on a proof; a fifth of the voting power must
+/// take part and a simple majority of the votes cast must approve.
+const RELEASE_VOTE_WINDOW: u64 = 7 * 86_400;
+const RELEASE_QUORUM_BPS: u32 = 2_000;
+const RELEASE_MAJORITY_BPS: u32 = 5_000;
+
+/// Parameters in force before any `set_params` call (version 0).
+pub fn default_params() -> ProtocolParams {
+    ProtocolParams {
+        max_accepted_tokens: MAX_ACCEPTED_TOKENS,
+        max_goal: MAX_GOAL,
+        min_deadline_duration: 0,
+        max_deadline_duration: MAX_DEADLINE_DURATION,
+        instance_bump_amount: INSTANCE_BUMP_AMOUNT,
+        instance_lifetime_threshold: INSTANCE_LIFETIME_THRESHOLD,
+        persistent_bump_amount: PERSISTENT_BUMP_AMOUNT,
+        persistent_lifetime_threshold: PERSISTENT_LIFETIME_THRESHOLD,
+        cooling_off_period: COOLING_OFF_PERIOD,
+        extension_window: EXTENSION_WINDOW,
+        extension_objection_bps: EXTENSION_OBJECTION_BPS,
+        release_vote_window: RELEASE_VOTE_WINDOW,
+        release_quorum_bps: RELEASE_QUORUM_BPS,
+        release_majority_bps: RELEASE_MAJORITY_BPS,
+    }
+}
+
+/// Upper bound on the number of entries returned by paginated queries.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
 // ── Storage Keys ─────────────────────────────────────────────────────
 
 /// All contract storage keys.
@@ -56,34 +154,217 @@ const PERSISTENT_LIFETIME_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
 pub enum DataKey {
     /// Global auto-increment counter for project IDs (Instance).
     ProjectCount,
-    /// Immutable project configuration keyed by ID (Persistent).
+    /// Legacy project configuration keyed by ID (Persistent).
+    /// Holds a `LegacyProjectConfig`; migrated to `ProjConfigV2` on first read.
     ProjConfig(u64),
-    /// Mutable project state keyed by ID (Persistent).
+    /// Legacy mutable project state keyed by ID (Persistent).
+    /// Holds a `LegacyProjectState`; migrated to `ProjStateV2` on first read.
     ProjState(u64),
+    /// Tokens currently accepted by a project (Persistent).
+    ProjTokens(u64),
     /// Token balance for a specific project and token (Persistent).
     TokenBalance(u64, Address),
     /// Protocol pause state (Instance).
     IsPaused,
     /// Tracks whether a (project_id, donator, token) combination has donated before (Persistent).
     DonatorSeen(u64, Address, Address),
+    /// Set while an entry point is performing external token calls (Instance).
+    ReentrancyLock,
+    /// Number of tokens on the protocol allowlist (Instance).
+    AllowedTokenCount,
+    /// Allowlist entry and optional metadata for a token (Persistent).
+    AllowedToken(Address),
+    /// Position of an allowlisted token in the paginated index (Persistent).
+    AllowedTokenIdx(Address),
+    /// Allowlisted token stored at a position of the paginated index (Persistent).
+    AllowedTokenAt(u32),
+    /// Current protocol parameters (Instance).
+    Params,
+    /// Version number of the current protocol parameters (Instance).
+    ParamsVersion,
+    /// Protocol parameters as of a given version (Persistent).
+    ParamsAt(u32),
+    /// Deposits by a donor in a token still inside the cooling-off window (Persistent).
+    DepositLog(u64, Address, Address),
+    /// Mutable project state keyed by ID (Persistent).
+    ProjStateV2(u64),
+    /// Anonymous donation keyed by project and commitment (Persistent).
+    Commitment(u64, BytesN<32>),
+    /// Net contribution of a donor to a project in a token (Persistent).
+    Contribution(u64, Address, Address),
+    /// Net amount raised by a project in a token; not reset on release (Persistent).
+    TotalRaised(u64, Address),
+    /// Number of distinct donors of a project (Persistent).
+    DonorCount(u64),
+    /// Position of a donor in a project's donor index (Persistent).
+    DonorIdx(u64, Address),
+    /// Donor stored at a position of a project's donor index (Persistent).
+    DonorAt(u64, u32),
+    /// Keeper a donor has authorised to pull deposits from their allowance (Persistent).
+    Keeper(Address, Address),
+    /// Global auto-increment counter for subscription IDs (Instance).
+    SubscriptionCount,
+    /// Recurring donation keyed by ID (Persistent).
+    Subscription(u64),
+    /// Global auto-increment counter for matching round IDs (Instance).
+    RoundCount,
+    /// Quadratic funding round keyed by ID (Persistent).
+    Round(u64),
+    /// Open round a project is participating in (Persistent).
+    ProjectRound(u64),
+    /// Tally of a project within a round (Persistent).
+    RoundProject(u64, u64),
+    /// Contribution of a donor to a project counted by a round (Persistent).
+    RoundContrib(u64, u64, Address),
+    /// Global auto-increment counter for matching pledge IDs (Instance).
+    MatchCount,
+    /// Sponsor matching pledge keyed by ID (Persistent).
+    Match(u64),
+    /// Unsettled matching pledges of a project (Persistent).
+    ProjectMatches(u64),
+    /// Amount a pledge has matched for one donor (Persistent).
+    MatchedFor(u64, Address),
+    /// Amount matched by pledges for a project in a token (Persistent).
+    MatchedTotal(u64, Address),
+    /// Sum of all donor contributions to a project in a token (Persistent).
+    ContributedTotal(u64, Address),
+    /// Latest deadline extension proposal of a project (Persistent).
+    Extension(u64),
+    /// Donor vote on a project's extension proposal, by sequence number (Persistent).
+    ExtensionVote(u64, u32, Address),
+    /// Donor vote on a `DonorVote` project's proof (Persistent).
+    ReleaseVote(u64),
+    /// Donor ballot in a project's release vote (Persistent).
+    ReleaseBallot(u64, Address),
+    /// Latest proof submission of a project and its review (Persistent).
+    ProofReview(u64),
+    /// Number of entries in a project's evidence log (Persistent).
+    EvidenceCount(u64),
+    /// Evidence log entry at an index (Persistent).
+    Evidence(u64, u32),
+    /// Evidence entry cited by the oracle at release (Persistent).
+    VerifiedEvidence(u64),
+    /// Set when a project's `proof` is a salted commitment; projects
+    /// registered before commitments lack it (Persistent).
+    SaltedProof(u64),
+    /// Project configuration keyed by ID (Persistent); only `deadline` is ever rewritten.
+    ProjConfigV2(u64),
+    /// Current instance TTL threshold and bump, read by every bump (Instance).
+    InstanceTtl,
+    /// Current persistent TTL threshold and bump, read by every bump (Instance).
+    PersistentTtl,
 }
 
 // ── Instance Storage Helpers ─────────────────────────────────────────
 
 /// Extend instance storage TTL if it falls below the threshold.
 fn bump_instance(env: &Env) {
+    let (threshold, bump) = ttl_params(env, DataKey::InstanceTtl, |p| {
+        (p.instance_lifetime_threshold, p.instance_bump_amount)
+    });
+    env.storage().instance().extend_ttl(threshold, bump);
+}
+
+/// Read a `(threshold, bump)` TTL pair from its own instance key.
+///
+/// Every storage access bumps a TTL, so the pairs are copied out of
+/// `Params` by [`set_params`] rather than decoded from it each time.
+/// Parameters set before the keys existed are read from `Params` until
+/// the next `set_params`.
+fn ttl_params(env: &Env, key: DataKey, pick: fn(&ProtocolParams) -> (u32, u32)) -> (u32, u32) {
     env.storage()
         .instance()
-        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        .get(&key)
+        .unwrap_or_else(|| pick(&get_params(env)))
+}
+
+// ─────────────────────────────────────────────────────────
+// Protocol parameters
+// ─────────────────────────────────────────────────────────
+
+/// Return the current protocol parameters.
+pub fn get_params(env: &Env) -> ProtocolParams {
+    match env.storage().instance().get(&DataKey::Params) {
+        Some(stored) => decode_params(env, stored),
+        None => default_params(),
+    }
+}
+
+/// Decode stored parameters, giving any field added since they were
+/// written its [`default_params`] value.
+///
+/// A `contracttype` struct only decodes with its exact field set, so the
+/// stored value is read as a field map and laid over the defaults.
+fn decode_params(env: &Env, stored: Val) -> ProtocolParams {
+    let defaults: Val = default_params().into_val(env);
+    let mut fields = Map::<Symbol, Val>::try_from_val(env, &defaults).unwrap();
+    let stored = Map::<Symbol, Val>::try_from_val(env, &stored).expect("invalid params");
+    for (field, value) in stored.iter() {
+        fields.set(field, value);
+    }
+    ProtocolParams::try_from_val(env, &fields.to_val()).expect("invalid params")
+}
+
+/// Return the version number of the current protocol parameters.
+pub fn get_params_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ParamsVersion)
+        .unwrap_or(0)
+}
+
+/// Return the protocol parameters as of `version`.
+///
+/// Version 0 is always [`default_params`]; later versions are read from
+/// the persistent history written by [`set_params`].
+pub fn get_params_at(env: &Env, version: u32) -> ProtocolParams {
+    if version == 0 {
+        return default_params();
+    }
+    let key = DataKey::ParamsAt(version);
+    let stored = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .expect("params version not found");
+    bump_persistent(env, &key);
+    decode_params(env, stored)
+}
+
+/// Install `params` as a new version and return its version number.
+pub fn set_params(env: &Env, params: &ProtocolParams) -> u32 {
+    let version = get_params_version(env) + 1;
+    let instance = env.storage().instance();
+    instance.set(&DataKey::Params, params);
+    instance.set(&DataKey::ParamsVersion, &version);
+    instance.set(
+        &DataKey::InstanceTtl,
+        &(
+            params.instance_lifetime_threshold,
+            params.instance_bump_amount,
+        ),
+    );
+    instance.set(
+        &DataKey::PersistentTtl,
+        &(
+            params.persistent_lifetime_threshold,
+            params.persistent_bump_amount,
+        ),
+    );
+    let key = DataKey::ParamsAt(version);
+    env.storage().persistent().set(&key, params);
+    bump_instance(env);
+    bump_persistent(env, &key);
+    version
 }
 
 // ─────────────────────────────────────────────────────────
 // Project counter
 // ─────────────────────────────────────────────────────────
 
-/// Atomically read and increment the project counter.
-/// Returns the ID that should be used for the next project.
-pub fn get_and_increment_project_id(env: &Env) -> u64 {
+/// Atomically reserve `count` contiguous project IDs with a single write.
+/// Returns the first reserved ID.
+pub fn get_and_increment_project_id(env: &Env, count: u64) -> u64 {
     bump_instance(env);
     let current: u64 = env
         .storage()
@@ -92,7 +373,7 @@ pub fn get_and_increment_project_id(env: &Env) -> u64 {
         .unwrap_or(0);
     env.storage()
         .instance()
-        .set(&DataKey::ProjectCount, &(current + 1));
+        .set(&DataKey::ProjectCount, &(current + count));
     current
 }
 
@@ -110,40 +391,75 @@ pub fn set_paused(env: &Env, paused: bool) {
     env.storage().instance().set(&DataKey::IsPaused, &paused);
 }
 
+/// Return true if an entry point currently holds the reentrancy lock.
+pub fn is_reentrancy_locked(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReentrancyLock)
+        .unwrap_or(false)
+}
+
+/// Acquire or release the reentrancy lock.
+///
+/// The lock never needs explicit cleanup on failure: a panic reverts every
+/// storage write in the transaction, including the write that acquired it.
+pub fn set_reentrancy_locked(env: &Env, locked: bool) {
+    if locked {
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyLock, &true);
+    } else {
+        env.storage().instance().remove(&DataKey::ReentrancyLock);
+    }
+}
+
 // ── Persistent Storage Helpers ───────────────────────────────────────
 
 /// Extend the TTL for a persistent storage key.
 fn bump_persistent(env: &Env, key: &DataKey) {
-    env.storage().persistent().extend_ttl(
-        key,
-        PERSISTENT_LIFETIME_THRESHOLD,
-        PERSISTENT_BUMP_AMOUNT,
-    );
+    let (threshold, bump) = ttl_params(env, DataKey::PersistentTtl, |p| {
+        (p.persistent_lifetime_threshold, p.persistent_bump_amount)
+    });
+    env.storage().persistent().extend_ttl(key, threshold, bump);
 }
 
 /// Save both the immutable config and initial mutable state for a new project.
 pub fn save_project(env: &Env, project: &Project) {
-    let config_key = DataKey::ProjConfig(project.id);
-    let state_key = DataKey::ProjState(project.id);
+    let config_key = DataKey::ProjConfigV2(project.id);
+    let state_key = DataKey::ProjStateV2(project.id);
 
     let config = ProjectConfig {
         id: project.id,
         creator: project.creator.clone(),
-        accepted_tokens: project.accepted_tokens.clone(),
         goal: project.goal,
-        proof_hash: project.proof_hash.clone(),
+        proof: project.proof.clone(),
         deadline: project.deadline,
+        token_goals: project.token_goals.clone(),
+        goal_rule: project.goal_rule.clone(),
+        funding_mode: project.funding_mode,
+        max_raise: project.max_raise.clone(),
+        excess_policy: project.excess_policy,
+        verification_mode: project.verification_mode,
+        params_version: get_params_version(env),
     };
 
     let state = ProjectState {
         status: project.status.clone(),
         donation_count: project.donation_count,
+        unique_donors: project.unique_donors,
+        deposit_count: project.deposit_count,
     };
 
     env.storage().persistent().set(&config_key, &config);
     env.storage().persistent().set(&state_key, &state);
     bump_persistent(env, &config_key);
     bump_persistent(env, &state_key);
+    set_accepted_tokens(env, project.id, &project.accepted_tokens);
+    if project.proof_salted {
+        let salted_key = DataKey::SaltedProof(project.id);
+        env.storage().persistent().set(&salted_key, &true);
+        bump_persistent(env, &salted_key);
+    }
 
     // Initialise balances to 0 for all accepted tokens.
     for token in project.accepted_tokens.iter() {
@@ -171,7 +487,7 @@ pub fn load_project_state(env: &Env, id: u64) -> ProjectState {
 
 /// Save only the mutable project state (optimized for deposits/verification).
 pub fn save_project_state(env: &Env, id: u64, state: &ProjectState) {
-    let key = DataKey::ProjState(id);
+    let key = DataKey::ProjStateV2(id);
     env.storage().persistent().set(&key, state);
     bump_persistent(env, &key);
 }
@@ -185,8 +501,8 @@ pub fn save_project_state(env: &Env, id: u64, state: &ProjectState) {
 /// unwrapping.
 #[allow(dead_code)]
 pub fn project_exists(env: &Env, id: u64) -> bool {
-    let config_key = DataKey::ProjConfig(id);
-    env.storage().persistent().has(&config_key)
+    let storage = env.storage().persistent();
+    storage.has(&DataKey::ProjConfigV2(id)) || storage.has(&DataKey::ProjConfig(id))
 }
 
 /// Attempt to load the immutable configuration for `id`.
@@ -196,12 +512,48 @@ pub fn project_exists(env: &Env, id: u64) -> bool {
 /// exist **no TTL bump occurs**.
 #[allow(dead_code)]
 pub fn maybe_load_project_config(env: &Env, id: u64) -> Option<ProjectConfig> {
-    let key = DataKey::ProjConfig(id);
+    let key = DataKey::ProjConfigV2(id);
     let opt: Option<ProjectConfig> = env.storage().persistent().get(&key);
     if opt.is_some() {
         bump_persistent(env, &key);
+        return opt;
+    }
+    migrate_legacy_config(env, id)
+}
+
+/// Convert a `LegacyProjectConfig` into a `ProjectConfig` and store it
+/// under `ProjConfigV2`, moving its token list to `ProjTokens` and removing
+/// the legacy entry.
+///
+/// The project keeps the rules it was registered under: its goal stays the
+/// first accepted token's goal, funding is `Flexible` and uncapped, the
+/// oracle verifies it under the version 0 parameters, and its proof is the
+/// plain SHA2-256 digest it was registered with.
+fn migrate_legacy_config(env: &Env, id: u64) -> Option<ProjectConfig> {
+    let legacy_key = DataKey::ProjConfig(id);
+    let legacy: LegacyProjectConfig = env.storage().persistent().get(&legacy_key)?;
+    let mut token_goals = Map::new(env);
+    if let Some(first) = legacy.accepted_tokens.get(0) {
+        token_goals.set(first, legacy.goal);
     }
-    opt
+    let config = ProjectConfig {
+        id,
+        creator: legacy.creator,
+        goal: legacy.goal,
+        proof: ContentId::sha2_256(legacy.proof_hash),
+        deadline: legacy.deadline,
+        token_goals,
+        goal_rule: GoalRule::AnyToken,
+        funding_mode: FundingMode::Flexible,
+        max_raise: Map::new(env),
+        excess_policy: ExcessPolicy::Reject,
+        verification_mode: VerificationMode::Oracle,
+        params_version: 0,
+    };
+    env.storage().persistent().remove(&legacy_key);
+    save_project_config(env, &config);
+    set_accepted_tokens(env, id, &legacy.accepted_tokens);
+    Some(config)
 }
 
 /// Attempt to load the mutable state for `id`.
@@ -209,12 +561,35 @@ pub fn maybe_load_project_config(env: &Env, id: u64) -> Option<ProjectConfig> {
 /// Works analogously to [`maybe_load_project_config`].
 #[allow(dead_code)]
 pub fn maybe_load_project_state(env: &Env, id: u64) -> Option<ProjectState> {
-    let key = DataKey::ProjState(id);
+    let key = DataKey::ProjStateV2(id);
     let opt: Option<ProjectState> = env.storage().persistent().get(&key);
     if opt.is_some() {
         bump_persistent(env, &key);
+        return opt;
     }
-    opt
+    migrate_legacy_state(env, id)
+}
+
+/// Convert a `LegacyProjectState` into a `ProjectState` and store it under
+/// `ProjStateV2`, removing the legacy entry.
+///
+/// Legacy state only counted (donor, token) pairs. A donor covers at most
+/// one pair per accepted token, so unique donors are estimated as the pair
+/// count divided by the token count, rounded up, and each pair is counted as
+/// one deposit. Both are therefore lower bounds.
+fn migrate_legacy_state(env: &Env, id: u64) -> Option<ProjectState> {
+    let legacy_key = DataKey::ProjState(id);
+    let legacy: LegacyProjectState = env.storage().persistent().get(&legacy_key)?;
+    let token_count = get_accepted_tokens(env, id).len().max(1);
+    let state = ProjectState {
+        status: legacy.status,
+        donation_count: legacy.donation_count,
+        unique_donors: legacy.donation_count.div_ceil(token_count),
+        deposit_count: legacy.donation_count,
+    };
+    env.storage().persistent().remove(&legacy_key);
+    save_project_state(env, id, &state);
+    Some(state)
 }
 
 /// Fetch both config and state in one call.
@@ -227,27 +602,12 @@ pub fn maybe_load_project_state(env: &Env, id: u64) -> Option<ProjectState> {
 ///
 /// Panics with `project not found` if either component is missing.
 pub fn load_project_pair(env: &Env, id: u64) -> (ProjectConfig, ProjectState) {
-    let config_key = DataKey::ProjConfig(id);
-    let state_key = DataKey::ProjState(id);
-
-    let config: ProjectConfig = env
-        .storage()
-        .persistent()
-        .get(&config_key)
-        .expect("project not found");
-    let state: ProjectState = env
-        .storage()
-        .persistent()
-        .get(&state_key)
-        .expect("project not found");
-
-    bump_persistent(env, &config_key);
-    bump_persistent(env, &state_key);
-
+    let config = maybe_load_project_config(env, id).expect("project not found");
+    let state = maybe_load_project_state(env, id).expect("project not found");
     (config, state)
 }
 
-/// Load the full `Project` by combining config and state.
+/// Load the full `Project` by combining config, state and the token list.
 ///
 /// Internally this now just delegates to [`load_project_pair`], avoiding
 /// duplicate TTL bumps and read boilerplate.
@@ -256,12 +616,35 @@ pub fn load_project(env: &Env, id: u64) -> Project {
     Project {
         id: config.id,
         creator: config.creator,
-        accepted_tokens: config.accepted_tokens,
+        accepted_tokens: get_accepted_tokens(env, id),
         goal: config.goal,
-        proof_hash: config.proof_hash,
+        proof: config.proof,
+        proof_salted: is_proof_salted(env, id),
         deadline: config.deadline,
+        token_goals: config.token_goals,
+        goal_rule: config.goal_rule,
+        funding_mode: config.funding_mode,
+        max_raise: config.max_raise,
+        excess_policy: config.excess_policy,
+        verification_mode: config.verification_mode,
+        proof_review: get_proof_review(env, id),
+        evidence_count: evidence_count(env, id),
+        verified_evidence: get_verified_evidence(env, id),
         status: state.status,
         donation_count: state.donation_count,
+        unique_donors: state.unique_donors,
+        deposit_count: state.deposit_count,
+    }
+}
+
+/// Whether `project_id` stores its proof as a salted commitment.
+pub fn is_proof_salted(env: &Env, project_id: u64) -> bool {
+    let key = DataKey::SaltedProof(project_id);
+    if env.storage().persistent().has(&key) {
+        bump_persistent(env, &key);
+        true
+    } else {
+        false
     }
 }
 
@@ -269,27 +652,42 @@ pub fn load_project(env: &Env, id: u64) -> Project {
 ///
 /// This is the most efficient way to query the contract when callers are
 /// unsure whether the project exists; it avoids any panics and still bumps the
-/// TTL of both underlying entries when present.
+/// TTL of the underlying entries when present.
 #[allow(dead_code)]
 pub fn maybe_load_project(env: &Env, id: u64) -> Option<Project> {
-    let config_key = DataKey::ProjConfig(id);
-    // We test existence on one key only; if a project is corrupt (config
+    // We test existence on the config only; if a project is corrupt (config
     // without state) the subsequent `get` will still panic, which is acceptable
     // since such a situation should never occur in normal operation.
-    if !env.storage().persistent().has(&config_key) {
+    if !project_exists(env, id) {
         return None;
     }
-    let (config, state) = load_project_pair(env, id);
-    Some(Project {
-        id: config.id,
-        creator: config.creator,
-        accepted_tokens: config.accepted_tokens,
-        goal: config.goal,
-        proof_hash: config.proof_hash,
-        deadline: config.deadline,
-        status: state.status,
-        donation_count: state.donation_count,
-    })
+    Some(load_project(env, id))
+}
+
+/// Return the tokens currently accepted by `project_id`.
+///
+/// The list lives in its own entry rather than in `ProjectConfig` (which is
+/// immutable) or `ProjectState` (which is written on every new donor), since
+/// it changes only through `add_accepted_token` / `remove_accepted_token`.
+///
+/// Projects stored before the list had its own entry keep it in their
+/// `LegacyProjectConfig`; the first read migrates that config, which writes
+/// the entry.
+pub fn get_accepted_tokens(env: &Env, project_id: u64) -> Vec<Address> {
+    let key = DataKey::ProjTokens(project_id);
+    if let Some(tokens) = env.storage().persistent().get(&key) {
+        bump_persistent(env, &key);
+        return tokens;
+    }
+    migrate_legacy_config(env, project_id).expect("project not found");
+    env.storage().persistent().get(&key).unwrap()
+}
+
+/// Overwrite the accepted token list of `project_id`.
+pub fn set_accepted_tokens(env: &Env, project_id: u64, tokens: &Vec<Address>) {
+    let key = DataKey::ProjTokens(project_id);
+    env.storage().persistent().set(&key, tokens);
+    bump_persistent(env, &key);
 }
 
 /// Retrieve the balance of `token` for `project_id`.
@@ -316,6 +714,14 @@ pub fn add_to_token_balance(env: &Env, project_id: u64, token: &Address, amount:
     new_balance
 }
 
+/// Delete the balance entry of `token` for `project_id`.
+/// Only called for tokens removed from a project with a zero balance.
+pub fn remove_token_balance(env: &Env, project_id: u64, token: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TokenBalance(project_id, token.clone()));
+}
+
 /// Zero out the balance of `token` for `project_id` and return what it was.
 /// Called during `verify_and_release` after transferring funds to the creator.
 #[allow(dead_code)]
@@ -327,20 +733,37 @@ pub fn drain_token_balance(env: &Env, project_id: u64, token: &Address) -> i128
     balance
 }
 
-/// Build a `ProjectBalances` snapshot by reading each accepted token's balance.
+/// Build a `ProjectBalances` snapshot by reading each accepted token's
+/// balance alongside its per-token goal.
 #[allow(dead_code)]
 pub fn get_all_balances(env: &Env, project: &Project) -> ProjectBalances {
     let mut balances: Vec<TokenBalance> = Vec::new(env);
     for token in project.accepted_tokens.iter() {
         let balance = get_token_balance(env, project.id, &token);
+        let goal = project.token_goals.get(token.clone()).unwrap_or(0);
         balances.push_back(TokenBalance {
             token: token.clone(),
             balance,
+            goal,
+            raised: get_total_raised(env, project.id, &token),
+            matched: get_matched_total(env, project.id, &token),
+            match_available: match_available(env, project.id, &token),
         });
     }
     ProjectBalances {
         project_id: project.id,
         balances,
+        goal_reached: goals::is_goal_reached(
+            env,
+            project.id,
+            project.goal,
+            &project.token_goals,
+            &project.goal_rule,
+        ),
+        quote_value: match &project.goal_rule {
+            GoalRule::QuoteValue(quote) => goals::quote_value(env, project.id, quote),
+            _ => 0,
+        },
     }
 }
 
@@ -363,3 +786,715 @@ pub fn mark_donator_seen(env: &Env, project_id: u64, donator: &Address, token: &
     bump_persistent(env, &key);
 }
 
+// ── Cooling-off Deposit Log ──────────────────────────────────────────
+//
+// Only deposits that can still be withdrawn are kept. Expired records are
+// pruned on every write, so the log stays bounded by how often a donor
+// gives within a single window.
+
+/// Deposits by `donator` in `token` that are still inside `window` seconds.
+pub fn get_withdrawable_deposits(
+    env: &Env,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    window: u64,
+) -> Vec<DepositRecord> {
+    let key = DataKey::DepositLog(project_id, donator.clone(), token.clone());
+    let log: Vec<DepositRecord> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    let now = env.ledger().timestamp();
+    let mut fresh = Vec::new(env);
+    for record in log.iter() {
+        if record.timestamp.saturating_add(window) > now {
+            fresh.push_back(record);
+        }
+    }
+    fresh
+}
+
+/// Replace the deposit log, deleting the entry once it is empty.
+pub fn set_deposit_log(
+    env: &Env,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    log: &Vec<DepositRecord>,
+) {
+    let key = DataKey::DepositLog(project_id, donator.clone(), token.clone());
+    if log.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, log);
+        bump_persistent(env, &key);
+    }
+}
+
+/// Append a deposit of `amount` made now, pruning records older than `window`.
+pub fn record_deposit(
+    env: &Env,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+    window: u64,
+) {
+    let mut log = get_withdrawable_deposits(env, project_id, donator, token, window);
+    log.push_back(DepositRecord {
+        amount,
+        timestamp: env.ledger().timestamp(),
+    });
+    set_deposit_log(env, project_id, donator, token, &log);
+}
+
+// ── Contribution Ledger ──────────────────────────────────────────────
+//
+// Contributions are net of cooling-off withdrawals and, unlike
+// `TokenBalance`, are never drained on release, so they remain available
+// for receipts and refunds.
+
+/// Net amount `donor` has contributed to `project_id` in `token`.
+pub fn get_contribution(env: &Env, project_id: u64, donor: &Address, token: &Address) -> i128 {
+    let key = DataKey::Contribution(project_id, donor.clone(), token.clone());
+    let amount = env.storage().persistent().get(&key).unwrap_or(0);
+    if amount != 0 {
+        bump_persistent(env, &key);
+    }
+    amount
+}
+
+/// Non-zero contributions of `donor` in each of `tokens`.
+pub fn get_contributions(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    tokens: &Vec<Address>,
+) -> Vec<Contribution> {
+    let mut out = Vec::new(env);
+    for token in tokens.iter() {
+        let amount = get_contribution(env, project_id, donor, &token);
+        if amount != 0 {
+            out.push_back(Contribution { token, amount });
+        }
+    }
+    out
+}
+
+/// Net amount `project_id` has raised in `token`.
+pub fn get_total_raised(env: &Env, project_id: u64, token: &Address) -> i128 {
+    let key = DataKey::TotalRaised(project_id, token.clone());
+    let amount = env.storage().persistent().get(&key).unwrap_or(0);
+    if amount != 0 {
+        bump_persistent(env, &key);
+    }
+    amount
+}
+
+/// Apply `delta` to both the donor's contribution and the project total.
+/// Positive for deposits, negative for withdrawals.
+pub fn record_contribution(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+    delta: i128,
+) {
+    add_contribution(env, project_id, donor, token, delta);
+    add_total_raised(env, project_id, token, delta);
+}
+
+/// Apply `delta` to the donor's contribution only.
+pub fn add_contribution(env: &Env, project_id: u64, donor: &Address, token: &Address, delta: i128) {
+    let key = DataKey::Contribution(project_id, donor.clone(), token.clone());
+    let current = get_contribution(env, project_id, donor, token);
+    let updated = current.checked_add(delta).expect("contribution overflow");
+    env.storage().persistent().set(&key, &updated);
+    bump_persistent(env, &key);
+
+    let total_key = DataKey::ContributedTotal(project_id, token.clone());
+    let total = get_contributed_total(env, project_id, token)
+        .checked_add(delta)
+        .expect("contribution overflow");
+    env.storage().persistent().set(&total_key, &total);
+    bump_persistent(env, &total_key);
+}
+
+/// Sum of every donor's contributions to `project_id` in `token`.
+///
+/// Only tracks contributions recorded after it was introduced.
+pub fn get_contributed_total(env: &Env, project_id: u64, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ContributedTotal(project_id, token.clone()))
+        .unwrap_or(0)
+}
+
+/// Apply `delta` to the project's total raised in `token` only.
+pub fn add_total_raised(env: &Env, project_id: u64, token: &Address, delta: i128) {
+    let key = DataKey::TotalRaised(project_id, token.clone());
+    let total = get_total_raised(env, project_id, token);
+    let updated = total.checked_add(delta).expect("total raised overflow");
+    env.storage().persistent().set(&key, &updated);
+    bump_persistent(env, &key);
+}
+
+/// Number of distinct donors of `project_id`.
+pub fn donor_count(env: &Env, project_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DonorCount(project_id))
+        .unwrap_or(0)
+}
+
+/// Add `donor` to the donor index of `project_id`. Returns true if new.
+pub fn add_donor(env: &Env, project_id: u64, donor: &Address) -> bool {
+    let idx_key = DataKey::DonorIdx(project_id, donor.clone());
+    if env.storage().persistent().has(&idx_key) {
+        bump_persistent(env, &idx_key);
+        return false;
+    }
+    let count = donor_count(env, project_id);
+    let at_key = DataKey::DonorAt(project_id, count);
+    let count_key = DataKey::DonorCount(project_id);
+    env.storage().persistent().set(&idx_key, &count);
+    env.storage().persistent().set(&at_key, donor);
+    env.storage().persistent().set(&count_key, &(count + 1));
+    bump_persistent(env, &idx_key);
+    bump_persistent(env, &at_key);
+    bump_persistent(env, &count_key);
+    true
+}
+
+/// Return up to `limit` (capped at [`MAX_PAGE_SIZE`]) donors of
+/// `project_id` starting at index `cursor`, in order of first deposit.
+pub fn list_donors(env: &Env, project_id: u64, cursor: u32, limit: u32) -> Vec<Address> {
+    let mut out = Vec::new(env);
+    let end = cursor
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(donor_count(env, project_id));
+    for i in cursor..end {
+        let donor: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DonorAt(project_id, i))
+            .unwrap();
+        out.push_back(donor);
+    }
+    out
+}
+
+// ── Committed Donations ──────────────────────────────────────────────
+
+/// Committed donation stored under `commitment`, if any.
+pub fn get_committed_donation(
+    env: &Env,
+    project_id: u64,
+    commitment: &BytesN<32>,
+) -> Option<CommittedDonation> {
+    let key = DataKey::Commitment(project_id, commitment.clone());
+    let donation = env.storage().persistent().get(&key);
+    if donation.is_some() {
+        bump_persistent(env, &key);
+    }
+    donation
+}
+
+pub fn set_committed_donation(
+    env: &Env,
+    project_id: u64,
+    commitment: &BytesN<32>,
+    donation: &CommittedDonation,
+) {
+    let key = DataKey::Commitment(project_id, commitment.clone());
+    env.storage().persistent().set(&key, donation);
+    bump_persistent(env, &key);
+}
+
+// ── Subscription Helpers ─────────────────────────────────────────────
+
+/// Read and increment the subscription counter; returns the new ID.
+pub fn get_and_increment_subscription_id(env: &Env) -> u64 {
+    bump_instance(env);
+    let current: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::SubscriptionCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::SubscriptionCount, &(current + 1));
+    current
+}
+
+pub fn get_subscription(env: &Env, id: u64) -> Option<Subscription> {
+    let key = DataKey::Subscription(id);
+    let sub = env.storage().persistent().get(&key);
+    if sub.is_some() {
+        bump_persistent(env, &key);
+    }
+    sub
+}
+
+pub fn save_subscription(env: &Env, sub: &Subscription) {
+    let key = DataKey::Subscription(sub.id);
+    env.storage().persistent().set(&key, sub);
+    bump_persistent(env, &key);
+}
+
+// ── Matching Round Helpers ───────────────────────────────────────────
+
+/// Read and increment the round counter; returns the new ID.
+pub fn get_and_increment_round_id(env: &Env) -> u64 {
+    bump_instance(env);
+    let current: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::RoundCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::RoundCount, &(current + 1));
+    current
+}
+
+pub fn get_round(env: &Env, id: u64) -> Option<Round> {
+    let key = DataKey::Round(id);
+    let round = env.storage().persistent().get(&key);
+    if round.is_some() {
+        bump_persistent(env, &key);
+    }
+    round
+}
+
+pub fn save_round(env: &Env, round: &Round) {
+    let key = DataKey::Round(round.id);
+    env.storage().persistent().set(&key, round);
+    bump_persistent(env, &key);
+}
+
+/// Return the open round `project_id` participates in, if any.
+pub fn get_project_round(env: &Env, project_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProjectRound(project_id))
+}
+
+pub fn set_project_round(env: &Env, project_id: u64, round_id: Option<u64>) {
+    let key = DataKey::ProjectRound(project_id);
+    match round_id {
+        Some(round_id) => {
+            env.storage().persistent().set(&key, &round_id);
+            bump_persistent(env, &key);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_round_project(env: &Env, round_id: u64, project_id: u64) -> RoundProject {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoundProject(round_id, project_id))
+        .unwrap_or(RoundProject {
+            contributions: 0,
+            sum_sqrt: 0,
+            matched: 0,
+        })
+}
+
+pub fn set_round_project(env: &Env, round_id: u64, project_id: u64, tally: &RoundProject) {
+    let key = DataKey::RoundProject(round_id, project_id);
+    env.storage().persistent().set(&key, tally);
+    bump_persistent(env, &key);
+}
+
+pub fn get_round_contribution(env: &Env, round_id: u64, project_id: u64, donor: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoundContrib(round_id, project_id, donor.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_round_contribution(
+    env: &Env,
+    round_id: u64,
+    project_id: u64,
+    donor: &Address,
+    amount: i128,
+) {
+    let key = DataKey::RoundContrib(round_id, project_id, donor.clone());
+    env.storage().persistent().set(&key, &amount);
+    bump_persistent(env, &key);
+}
+
+// ── Matching Pledge Helpers ──────────────────────────────────────────
+
+/// Read and increment the matching pledge counter; returns the new ID.
+pub fn get_and_increment_match_id(env: &Env) -> u64 {
+    bump_instance(env);
+    let current: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MatchCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::MatchCount, &(current + 1));
+    current
+}
+
+pub fn get_match(env: &Env, id: u64) -> Option<MatchPledge> {
+    let key = DataKey::Match(id);
+    let pledge = env.storage().persistent().get(&key);
+    if pledge.is_some() {
+        bump_persistent(env, &key);
+    }
+    pledge
+}
+
+pub fn save_match(env: &Env, pledge: &MatchPledge) {
+    let key = DataKey::Match(pledge.id);
+    env.storage().persistent().set(&key, pledge);
+    bump_persistent(env, &key);
+}
+
+/// IDs of the project's pledges that have not been settled yet.
+pub fn get_project_matches(env: &Env, project_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProjectMatches(project_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_project_matches(env: &Env, project_id: u64, ids: &Vec<u64>) {
+    let key = DataKey::ProjectMatches(project_id);
+    if ids.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, ids);
+        bump_persistent(env, &key);
+    }
+}
+
+pub fn get_matched_for(env: &Env, match_id: u64, donor: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MatchedFor(match_id, donor.clone()))
+        .unwrap_or(0)
+}
+
+pub fn add_matched_for(env: &Env, match_id: u64, donor: &Address, delta: i128) {
+    let key = DataKey::MatchedFor(match_id, donor.clone());
+    let updated = get_matched_for(env, match_id, donor) + delta;
+    env.storage().persistent().set(&key, &updated);
+    bump_persistent(env, &key);
+}
+
+pub fn get_matched_total(env: &Env, project_id: u64, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MatchedTotal(project_id, token.clone()))
+        .unwrap_or(0)
+}
+
+pub fn add_matched_total(env: &Env, project_id: u64, token: &Address, delta: i128) {
+    let key = DataKey::MatchedTotal(project_id, token.clone());
+    let updated = get_matched_total(env, project_id, token) + delta;
+    env.storage().persistent().set(&key, &updated);
+    bump_persistent(env, &key);
+}
+
+/// Sum of `remaining` over the project's unexpired pledges in `token`.
+pub fn match_available(env: &Env, project_id: u64, token: &Address) -> i128 {
+    let now = env.ledger().timestamp();
+    let mut total: i128 = 0;
+    for id in get_project_matches(env, project_id).iter() {
+        if let Some(pledge) = get_match(env, id) {
+            if &pledge.token == token && now < pledge.expires_at {
+                total = total.saturating_add(pledge.remaining);
+            }
+        }
+    }
+    total
+}
+
+// ── Donor Vote Helpers ───────────────────────────────────────────────
+
+pub fn get_extension(env: &Env, project_id: u64) -> Option<ExtensionProposal> {
+    let key = DataKey::Extension(project_id);
+    let proposal = env.storage().persistent().get(&key);
+    if proposal.is_some() {
+        bump_persistent(env, &key);
+    }
+    proposal
+}
+
+pub fn save_extension(env: &Env, proposal: &ExtensionProposal) {
+    let key = DataKey::Extension(proposal.project_id);
+    env.storage().persistent().set(&key, proposal);
+    bump_persistent(env, &key);
+}
+
+pub fn get_extension_vote(env: &Env, project_id: u64, seq: u32, donor: &Address) -> Option<Ballot> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ExtensionVote(project_id, seq, donor.clone()))
+}
+
+pub fn set_extension_vote(env: &Env, project_id: u64, seq: u32, donor: &Address, vote: &Ballot) {
+    let key = DataKey::ExtensionVote(project_id, seq, donor.clone());
+    env.storage().persistent().set(&key, vote);
+    bump_persistent(env, &key);
+}
+
+pub fn get_release_vote(env: &Env, project_id: u64) -> Option<ReleaseVote> {
+    let key = DataKey::ReleaseVote(project_id);
+    let vote = env.storage().persistent().get(&key);
+    if vote.is_some() {
+        bump_persistent(env, &key);
+    }
+    vote
+}
+
+pub fn save_release_vote(env: &Env, vote: &ReleaseVote) {
+    let key = DataKey::ReleaseVote(vote.project_id);
+    env.storage().persistent().set(&key, vote);
+    bump_persistent(env, &key);
+}
+
+pub fn get_release_ballot(env: &Env, project_id: u64, donor: &Address) -> Option<Ballot> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReleaseBallot(project_id, donor.clone()))
+}
+
+pub fn set_release_ballot(env: &Env, project_id: u64, donor: &Address, ballot: &Ballot) {
+    let key = DataKey::ReleaseBallot(project_id, donor.clone());
+    env.storage().persistent().set(&key, ballot);
+    bump_persistent(env, &key);
+}
+
+/// Latest proof submission of a project, or `ProofReview::None`.
+pub fn get_proof_review(env: &Env, project_id: u64) -> ProofReview {
+    let key = DataKey::ProofReview(project_id);
+    match env.storage().persistent().get(&key) {
+        Some(review) => {
+            bump_persistent(env, &key);
+            review
+        }
+        None => ProofReview::None,
+    }
+}
+
+pub fn set_proof_review(env: &Env, project_id: u64, review: &ProofReview) {
+    let key = DataKey::ProofReview(project_id);
+    env.storage().persistent().set(&key, review);
+    bump_persistent(env, &key);
+}
+
+// ── Evidence Log ─────────────────────────────────────────────────────
+
+/// Number of entries in `project_id`'s evidence log.
+pub fn evidence_count(env: &Env, project_id: u64) -> u32 {
+    let key = DataKey::EvidenceCount(project_id);
+    match env.storage().persistent().get(&key) {
+        Some(count) => {
+            bump_persistent(env, &key);
+            count
+        }
+        None => 0,
+    }
+}
+
+/// Append `entry` to its project's evidence log. The caller sets
+/// `entry.index` to the current [`evidence_count`].
+pub fn append_evidence(env: &Env, entry: &EvidenceEntry) {
+    let at_key = DataKey::Evidence(entry.project_id, entry.index);
+    let count_key = DataKey::EvidenceCount(entry.project_id);
+    env.storage().persistent().set(&at_key, entry);
+    env.storage()
+        .persistent()
+        .set(&count_key, &(entry.index + 1));
+    bump_persistent(env, &at_key);
+    bump_persistent(env, &count_key);
+}
+
+/// Evidence entry at `index`, if any.
+pub fn get_evidence(env: &Env, project_id: u64, index: u32) -> Option<EvidenceEntry> {
+    let key = DataKey::Evidence(project_id, index);
+    let entry = env.storage().persistent().get(&key);
+    if entry.is_some() {
+        bump_persistent(env, &key);
+    }
+    entry
+}
+
+/// Return up to `limit` (capped at [`MAX_PAGE_SIZE`]) evidence entries of
+/// `project_id` starting at index `cursor`, oldest first.
+pub fn list_evidence(env: &Env, project_id: u64, cursor: u32, limit: u32) -> Vec<EvidenceEntry> {
+    let mut out = Vec::new(env);
+    let end = cursor
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(evidence_count(env, project_id));
+    for i in cursor..end {
+        let entry: EvidenceEntry = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Evidence(project_id, i))
+            .unwrap();
+        out.push_back(entry);
+    }
+    out
+}
+
+pub fn get_verified_evidence(env: &Env, project_id: u64) -> Option<u32> {
+    let key = DataKey::VerifiedEvidence(project_id);
+    let index = env.storage().persistent().get(&key);
+    if index.is_some() {
+        bump_persistent(env, &key);
+    }
+    index
+}
+
+pub fn set_verified_evidence(env: &Env, project_id: u64, index: u32) {
+    let key = DataKey::VerifiedEvidence(project_id);
+    env.storage().persistent().set(&key, &index);
+    bump_persistent(env, &key);
+}
+
+/// Overwrite a project's configuration, e.g. when an extension takes effect.
+pub fn save_project_config(env: &Env, config: &ProjectConfig) {
+    let key = DataKey::ProjConfigV2(config.id);
+    env.storage().persistent().set(&key, config);
+    bump_persistent(env, &key);
+}
+
+// ── Keeper Helpers ───────────────────────────────────────────────────
+
+/// Return true if `donator` has authorised `keeper` to call `deposit_from`
+/// on their behalf.
+pub fn is_keeper(env: &Env, donator: &Address, keeper: &Address) -> bool {
+    let key = DataKey::Keeper(donator.clone(), keeper.clone());
+    let approved = env.storage().persistent().has(&key);
+    if approved {
+        bump_persistent(env, &key);
+    }
+    approved
+}
+
+pub fn set_keeper(env: &Env, donator: &Address, keeper: &Address, approved: bool) {
+    let key = DataKey::Keeper(donator.clone(), keeper.clone());
+    if approved {
+        env.storage().persistent().set(&key, &true);
+        bump_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+// ── Token Allowlist Helpers ──────────────────────────────────────────
+//
+// The allowlist is kept as a dense index (`AllowedTokenAt(0..count)`) so it
+// can be paginated, plus a reverse index so removal is O(1): the last entry
+// is moved into the removed slot.
+
+/// Return true if `token` is on the protocol allowlist.
+pub fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::AllowedToken(token.clone()))
+}
+
+/// Return the metadata of an allowlisted token, or `None` if it has none or
+/// is not listed.
+pub fn get_token_metadata(env: &Env, token: &Address) -> Option<TokenMetadata> {
+    let key = DataKey::AllowedToken(token.clone());
+    let metadata: Option<Option<TokenMetadata>> = env.storage().persistent().get(&key);
+    if metadata.is_some() {
+        bump_persistent(env, &key);
+    }
+    metadata.flatten()
+}
+
+/// Return the number of allowlisted tokens.
+pub fn allowed_token_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AllowedTokenCount)
+        .unwrap_or(0)
+}
+
+/// Add `token` to the allowlist, or replace its metadata if already listed.
+pub fn allow_token(env: &Env, token: &Address, metadata: &Option<TokenMetadata>) {
+    bump_instance(env);
+    let key = DataKey::AllowedToken(token.clone());
+    if !env.storage().persistent().has(&key) {
+        let count = allowed_token_count(env);
+        let idx_key = DataKey::AllowedTokenIdx(token.clone());
+        let at_key = DataKey::AllowedTokenAt(count);
+        env.storage().persistent().set(&idx_key, &count);
+        env.storage().persistent().set(&at_key, token);
+        bump_persistent(env, &idx_key);
+        bump_persistent(env, &at_key);
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowedTokenCount, &(count + 1));
+    }
+    env.storage().persistent().set(&key, metadata);
+    bump_persistent(env, &key);
+}
+
+/// Remove `token` from the allowlist. Returns `false` if it was not listed.
+pub fn disallow_token(env: &Env, token: &Address) -> bool {
+    let key = DataKey::AllowedToken(token.clone());
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    bump_instance(env);
+    let idx_key = DataKey::AllowedTokenIdx(token.clone());
+    let idx: u32 = env.storage().persistent().get(&idx_key).unwrap();
+    let last = allowed_token_count(env) - 1;
+    if idx != last {
+        let moved: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AllowedTokenAt(last))
+            .unwrap();
+        let moved_at = DataKey::AllowedTokenAt(idx);
+        let moved_idx = DataKey::AllowedTokenIdx(moved.clone());
+        env.storage().persistent().set(&moved_at, &moved);
+        env.storage().persistent().set(&moved_idx, &idx);
+        bump_persistent(env, &moved_at);
+        bump_persistent(env, &moved_idx);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AllowedTokenAt(last));
+    env.storage().persistent().remove(&idx_key);
+    env.storage().persistent().remove(&key);
+    env.storage()
+        .instance()
+        .set(&DataKey::AllowedTokenCount, &last);
+    true
+}
+
+/// Return up to `limit` allowlisted tokens starting at position `cursor`.
+///
+/// `limit` is clamped to [`MAX_PAGE_SIZE`].
+pub fn list_allowed_tokens(env: &Env, cursor: u32, limit: u32) -> Vec<Address> {
+    let mut out = Vec::new(env);
+    let end = cursor
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(allowed_token_count(env));
+    for i in cursor..end {
+        let token: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AllowedTokenAt(i))
+            .unwrap();
+        out.push_back(token);
+    }
+    out
+}
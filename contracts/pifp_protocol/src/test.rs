@@ -5,7 +5,8 @@ use soroban_sdk::{
     token, Address, BytesN, Env, Vec,
 };
 
-use crate::{PifpProtocol, PifpProtocolClient, Role, ProjectStatus};
+use crate::test_util::{dummy_commitment, dummy_proof, dummy_salt};
+use crate::{ContentId, PifpProtocol, PifpProtocolClient, ProjectStatus, Role};
 
 // ─── Helpers ─────────────────────────────────────────────
 
@@ -25,7 +26,7 @@ fn setup() -> (Env, PifpProtocolClient<'static>) {
         max_entry_ttl: 1000,
     });
 
-    let contract_id = env.register_contract(None, PifpProtocol);
+    let contract_id = env.register(PifpProtocol, ());
     let client = PifpProtocolClient::new(&env, &contract_id);
     (env, client)
 }
@@ -42,14 +43,16 @@ fn create_token_contract<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
     token::Client::new(env, &addr.address())
 }
 
-fn dummy_proof(env: &Env) -> BytesN<32> {
-    BytesN::from_array(env, &[0xabu8; 32])
-}
-
 fn future_deadline(env: &Env) -> u64 {
     env.ledger().timestamp() + 86_400
 }
 
+fn allow_tokens(client: &PifpProtocolClient, admin: &Address, tokens: &Vec<Address>) {
+    for token in tokens.iter() {
+        client.allow_token(admin, &token, &None);
+    }
+}
+
 // ─── 1. Initialisation ───────────────────────────────────
 
 #[test]
@@ -77,17 +80,18 @@ fn test_register_project_success() {
     let mut tokens = Vec::new(&env);
     tokens.push_back(token.clone());
     
-    let proof_hash = BytesN::from_array(&env, &[1u8; 32]);
+    let proof = ContentId::sha2_256(BytesN::from_array(&env, &[1u8; 32]));
     let goal: i128 = 1_000;
     let deadline = future_deadline(&env);
 
-    let project = client.register_project(&super_admin, &tokens, &goal, &proof_hash, &deadline);
+    allow_tokens(&client, &super_admin, &tokens);
+    let project = client.register_project(&super_admin, &tokens, &goal, &proof, &deadline);
 
     assert_eq!(project.id, 0);
     assert_eq!(project.creator, super_admin);
     assert_eq!(project.accepted_tokens.get(0).unwrap(), token);
     assert_eq!(project.goal, goal);
-    assert_eq!(project.proof_hash, proof_hash);
+    assert_eq!(project.proof, proof);
     assert_eq!(project.deadline, deadline);
     assert_eq!(project.status, ProjectStatus::Funding);
 }
@@ -101,7 +105,14 @@ fn test_register_duplicate_tokens_fails() {
     let token = Address::generate(&env);
     let tokens = Vec::from_array(&env, [token.clone(), token.clone()]);
     
-    client.register_project(&admin, &tokens, &1000i128, &dummy_proof(&env), &future_deadline(&env));
+    allow_tokens(&client, &admin, &tokens);
+    client.register_project(
+        &admin,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
 }
 
 #[test]
@@ -110,7 +121,14 @@ fn test_register_zero_goal_fails() {
     let (env, client, admin) = setup_with_init();
     let tokens = Vec::from_array(&env, [Address::generate(&env)]);
     
-    client.register_project(&admin, &tokens, &0i128, &dummy_proof(&env), &future_deadline(&env));
+    allow_tokens(&client, &admin, &tokens);
+    client.register_project(
+        &admin,
+        &tokens,
+        &0i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
 }
 
 #[test]
@@ -120,7 +138,14 @@ fn test_register_past_deadline_fails() {
     let tokens = Vec::from_array(&env, [Address::generate(&env)]);
     let past_deadline = env.ledger().timestamp() - 1;
     
-    client.register_project(&admin, &tokens, &1000i128, &dummy_proof(&env), &past_deadline);
+    allow_tokens(&client, &admin, &tokens);
+    client.register_project(
+        &admin,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &past_deadline,
+    );
 }
 
 #[test]
@@ -132,8 +157,15 @@ fn test_deposit_zero_amount_fails() {
     let tokens = Vec::from_array(&env, [token.clone()]);
     
     client.grant_role(&admin, &creator, &Role::ProjectManager);
-    let project = client.register_project(&creator, &tokens, &1000i128, &dummy_proof(&env), &future_deadline(&env));
-    
+    allow_tokens(&client, &admin, &tokens);
+    let project = client.register_project(
+        &creator,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+
     client.deposit(&project.id, &creator, &token, &0i128);
 }
 
@@ -146,8 +178,15 @@ fn test_deposit_after_deadline_fails() {
     
     let pm = Address::generate(&env);
     client.grant_role(&admin, &pm, &Role::ProjectManager);
-    let project = client.register_project(&pm, &tokens, &1000i128, &dummy_proof(&env), &future_deadline(&env));
-    
+    allow_tokens(&client, &admin, &tokens);
+    let project = client.register_project(
+        &pm,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+
     // Fast-forward time
     env.ledger().set(LedgerInfo {
         timestamp: future_deadline(&env) + 1,
@@ -167,7 +206,7 @@ fn test_deposit_after_deadline_fails() {
 
 #[test]
 fn test_admin_can_pause_and_unpause() {
-    let (env, client, admin) = setup_with_init();
+    let (_env, client, admin) = setup_with_init();
     
     assert!(!client.is_paused());
     
@@ -198,11 +237,12 @@ fn test_project_exists_and_maybe_load_helpers() {
     let pm = Address::generate(&env);
     client.grant_role(&super_admin, &pm, &Role::ProjectManager);
     let tokens = Vec::from_array(&env, [token.clone()]);
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &pm,
         &tokens,
         &1_000i128,
-        &dummy_proof(&env),
+        &dummy_commitment(&env),
         &future_deadline(&env),
     );
 
@@ -253,7 +293,14 @@ fn test_registration_fails_when_paused() {
     client.pause(&admin);
     
     let tokens = Vec::from_array(&env, [Address::generate(&env)]);
-    client.register_project(&admin, &tokens, &1000i128, &dummy_proof(&env), &future_deadline(&env));
+    allow_tokens(&client, &admin, &tokens);
+    client.register_project(
+        &admin,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
 }
 
 #[test]
@@ -265,8 +312,15 @@ fn test_deposit_fails_when_paused() {
     
     let pm = Address::generate(&env);
     client.grant_role(&admin, &pm, &Role::ProjectManager);
-    let project = client.register_project(&pm, &tokens, &1000i128, &dummy_proof(&env), &future_deadline(&env));
-    
+    allow_tokens(&client, &admin, &tokens);
+    let project = client.register_project(
+        &pm,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+
     client.pause(&admin);
     client.deposit(&project.id, &pm, &token, &100i128);
 }
@@ -278,11 +332,53 @@ fn test_queries_work_when_paused() {
     
     let pm = Address::generate(&env);
     client.grant_role(&admin, &pm, &Role::ProjectManager);
-    let project = client.register_project(&pm, &tokens, &1000i128, &dummy_proof(&env), &future_deadline(&env));
-    
+    allow_tokens(&client, &admin, &tokens);
+    let project = client.register_project(
+        &pm,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+
     client.pause(&admin);
     
     // Query should still work
     let loaded = client.get_project(&project.id);
     assert_eq!(loaded.id, project.id);
-}
\ No newline at end of file
+}
+// ─── 10. Reentrancy lock ─────────────────────────────────
+
+#[test]
+fn test_lock_released_after_deposit_and_release() {
+    let (env, client, admin) = setup_with_init();
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let tokens = Vec::from_array(&env, [token.address.clone()]);
+
+    let pm = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let donator = Address::generate(&env);
+    client.grant_role(&admin, &pm, &Role::ProjectManager);
+    client.set_oracle(&admin, &oracle);
+    allow_tokens(&client, &admin, &tokens);
+    let project = client.register_project(
+        &pm,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+
+    token::StellarAssetClient::new(&env, &token.address).mint(&donator, &500i128);
+    client.deposit(&project.id, &donator, &token.address, &500i128);
+    env.as_contract(&client.address, || {
+        assert!(!crate::storage::is_reentrancy_locked(&env));
+    });
+
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+    env.as_contract(&client.address, || {
+        assert!(!crate::storage::is_reentrancy_locked(&env));
+    });
+    assert_eq!(token.balance(&pm), 500);
+}
@@ -0,0 +1,279 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, Events},
+    token, vec, Address, BytesN, Env, IntoVal, Symbol, TryIntoVal,
+};
+
+use crate::events::AcceptedTokenChanged;
+use crate::storage::DataKey;
+use crate::test_util::{dummy_commitment, dummy_proof, dummy_salt};
+use crate::types::{LegacyProjectConfig, LegacyProjectState};
+use crate::{PifpProtocol, PifpProtocolClient, Project, ProjectStatus, Role};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+/// Register a project owned by a fresh ProjectManager accepting `token`.
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, token: &Address) -> Project {
+    let creator = Address::generate(env);
+    client.grant_role(admin, &creator, &Role::ProjectManager);
+    client.register_project(
+        &creator,
+        &vec![env, token.clone()],
+        &10_000i128,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 86_400),
+    )
+}
+
+#[test]
+fn test_add_accepted_token_enables_deposits() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+
+    client.add_accepted_token(&project.id, &eurc.address);
+
+    // Only the creator's signature was required.
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, project.creator);
+    assert_eq!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            Symbol::new(&env, "add_accepted_token"),
+            (project.id, eurc.address.clone()).into_val(&env),
+        ))
+    );
+
+    let loaded = client.get_project(&project.id);
+    assert_eq!(
+        loaded.accepted_tokens,
+        vec![&env, usdc.address.clone(), eurc.address.clone()]
+    );
+
+    let donator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &eurc.address).mint(&donator, &300);
+    client.deposit(&project.id, &donator, &eurc.address, &300);
+
+    let balances = client.get_project_balances(&project.id);
+    assert_eq!(balances.balances.len(), 2);
+    assert_eq!(balances.balances.get(1).unwrap().balance, 300);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_add_duplicate_token_fails() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    client.add_accepted_token(&project.id, &usdc.address);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #22)")]
+fn test_add_unlisted_token_fails() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    client.add_accepted_token(&project.id, &Address::generate(&env));
+}
+
+#[test]
+fn test_add_respects_limit_project_was_registered_under() {
+    let (env, client, admin) = setup_with_init();
+    let mut params = client.get_params();
+    params.max_accepted_tokens = 2;
+    client.set_params(&admin, &params);
+
+    let first = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &first.address);
+
+    // Loosening the limit later does not change this project's rules.
+    params.max_accepted_tokens = 5;
+    client.set_params(&admin, &params);
+
+    let second = create_token(&env, &client, &admin);
+    let third = create_token(&env, &client, &admin);
+    client.add_accepted_token(&project.id, &second.address);
+    let result = client.try_add_accepted_token(&project.id, &third.address);
+    assert_eq!(
+        result,
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            crate::Error::TooManyTokens as u32
+        )))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #15)")]
+fn test_add_token_after_completion_fails() {
+    let (env, client, admin) = setup_with_init();
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+    client.add_accepted_token(&project.id, &eurc.address);
+}
+
+#[test]
+fn test_remove_token_with_zero_balance() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    client.add_accepted_token(&project.id, &eurc.address);
+
+    client.remove_accepted_token(&project.id, &eurc.address);
+
+    let loaded = client.get_project(&project.id);
+    assert_eq!(loaded.accepted_tokens, vec![&env, usdc.address.clone()]);
+
+    let donator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &eurc.address).mint(&donator, &100);
+    assert!(client
+        .try_deposit(&project.id, &donator, &eurc.address, &100)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #25)")]
+fn test_remove_token_with_balance_fails() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    client.add_accepted_token(&project.id, &eurc.address);
+
+    let donator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &eurc.address).mint(&donator, &100);
+    client.deposit(&project.id, &donator, &eurc.address, &100);
+
+    client.remove_accepted_token(&project.id, &eurc.address);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #17)")]
+fn test_remove_last_token_fails() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    client.remove_accepted_token(&project.id, &usdc.address);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #26)")]
+fn test_remove_unaccepted_token_fails() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    client.remove_accepted_token(&project.id, &eurc.address);
+}
+
+#[test]
+fn test_accepted_token_events() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+
+    client.add_accepted_token(&project.id, &eurc.address);
+    let last = env.events().all().last().unwrap();
+    assert_eq!(
+        last.1,
+        vec![
+            &env,
+            symbol_short!("tok_added").into_val(&env),
+            project.id.into_val(&env)
+        ]
+    );
+    let data: AcceptedTokenChanged = last.2.try_into_val(&env).unwrap();
+    assert_eq!(
+        data,
+        AcceptedTokenChanged {
+            project_id: project.id,
+            token: eurc.address.clone(),
+        }
+    );
+
+    client.remove_accepted_token(&project.id, &eurc.address);
+    let last = env.events().all().last().unwrap();
+    assert_eq!(
+        last.1,
+        vec![
+            &env,
+            symbol_short!("tok_rmvd").into_val(&env),
+            project.id.into_val(&env)
+        ]
+    );
+}
+
+#[test]
+fn test_legacy_project_token_list_is_migrated() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let second = create_token(&env, &client, &admin);
+    let creator = Address::generate(&env);
+
+    // Project 0 as stored before token lists had their own entry.
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(
+            &DataKey::ProjConfig(0),
+            &LegacyProjectConfig {
+                id: 0,
+                creator: creator.clone(),
+                accepted_tokens: vec![&env, token.address.clone()],
+                goal: 10_000,
+                proof_hash: BytesN::from_array(&env, &[0xabu8; 32]),
+                deadline: env.ledger().timestamp() + 86_400,
+            },
+        );
+        storage.set(
+            &DataKey::ProjState(0),
+            &LegacyProjectState {
+                status: ProjectStatus::Funding,
+                donation_count: 0,
+            },
+        );
+        env.storage().instance().set(&DataKey::ProjectCount, &1u64);
+    });
+
+    // Reading the token list first migrates it from the legacy config.
+    let donor = Address::generate(&env);
+    assert!(client.get_contribution(&0, &donor).is_empty());
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&DataKey::ProjTokens(0)));
+    });
+
+    client.add_accepted_token(&0, &second.address);
+    assert_eq!(
+        client.get_project(&0).accepted_tokens,
+        vec![&env, token.address.clone(), second.address.clone()]
+    );
+}
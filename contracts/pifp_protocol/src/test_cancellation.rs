@@ -0,0 +1,375 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, TryIntoVal,
+};
+
+use crate::events::{DonationRefunded, ProjectCancelled, RoundMatchReclaimed};
+use crate::test_util::{assert_error, dummy_commitment, dummy_proof, dummy_salt};
+use crate::{
+    Contribution, FundingMode, PifpProtocol, PifpProtocolClient, Project, ProjectOptions,
+    ProjectStatus, Role,
+};
+
+const DAY: u64 = 86_400;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn reason(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0x0fu8; 32])
+}
+
+/// A project manager and a project they registered accepting `tokens`.
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    tokens: soroban_sdk::Vec<Address>,
+) -> (Address, Project) {
+    let creator = Address::generate(env);
+    client.grant_role(admin, &creator, &Role::ProjectManager);
+    let project = client.register_project(
+        &creator,
+        &tokens,
+        &10_000,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 60 * DAY),
+    );
+    (creator, project)
+}
+
+fn donate(
+    env: &Env,
+    client: &PifpProtocolClient,
+    project_id: u64,
+    token: &token::Client,
+    amount: i128,
+) -> Address {
+    let donor = Address::generate(env);
+    token::StellarAssetClient::new(env, &token.address).mint(&donor, &amount);
+    client.deposit(&project_id, &donor, &token.address, &amount);
+    donor
+}
+
+#[test]
+fn test_creator_cancels_and_donors_are_refunded() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let (creator, project) = register(&env, &client, &admin, vec![&env, usdc.address.clone()]);
+    let alice = donate(&env, &client, project.id, &usdc, 600);
+    let bob = donate(&env, &client, project.id, &usdc, 400);
+
+    client.cancel_project(&creator, &project.id, &reason(&env));
+    let cancelled: ProjectCancelled = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("cancelled").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(cancelled.project_id, project.id);
+    assert_eq!(cancelled.caller, creator);
+    assert_eq!(cancelled.reason_hash, reason(&env));
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Cancelled
+    );
+
+    let refunds = client.claim_refund(&project.id, &alice);
+    assert_eq!(
+        refunds,
+        vec![
+            &env,
+            Contribution {
+                token: usdc.address.clone(),
+                amount: 600
+            }
+        ]
+    );
+    let refunded: DonationRefunded = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("refunded").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(refunded.donator, alice);
+    assert_eq!(refunded.amount, 600);
+
+    assert_eq!(usdc.balance(&alice), 600);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 400);
+    assert!(client.get_contribution(&project.id, &alice).is_empty());
+
+    // A refund can only be claimed once.
+    assert_error(
+        client.try_claim_refund(&project.id, &alice),
+        crate::Error::InsufficientBalance,
+    );
+
+    client.claim_refund(&project.id, &bob);
+    assert_eq!(usdc.balance(&bob), 400);
+    assert_eq!(usdc.balance(&client.address), 0);
+}
+
+#[test]
+fn test_refund_covers_every_accepted_token() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let (_, project) = register(
+        &env,
+        &client,
+        &admin,
+        vec![&env, usdc.address.clone(), eurc.address.clone()],
+    );
+    let donor = donate(&env, &client, project.id, &usdc, 300);
+    token::StellarAssetClient::new(&env, &eurc.address).mint(&donor, &200);
+    client.deposit(&project.id, &donor, &eurc.address, &200);
+
+    // Admins may cancel projects they did not create.
+    client.cancel_project(&admin, &project.id, &reason(&env));
+    let refunds = client.claim_refund(&project.id, &donor);
+    assert_eq!(refunds.len(), 2);
+    assert_eq!(usdc.balance(&donor), 300);
+    assert_eq!(eurc.balance(&donor), 200);
+}
+
+#[test]
+fn test_cancelled_project_blocks_deposits_and_verification() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let (creator, project) = register(&env, &client, &admin, vec![&env, usdc.address.clone()]);
+    donate(&env, &client, project.id, &usdc, 100);
+    client.cancel_project(&creator, &project.id, &reason(&env));
+
+    let late = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&late, &100);
+    assert_error(
+        client.try_deposit(&project.id, &late, &usdc.address, &100),
+        crate::Error::ProjectNotActive,
+    );
+
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    assert_error(
+        client.try_verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env)),
+        crate::Error::ProjectNotActive,
+    );
+    assert_error(
+        client.try_cancel_project(&creator, &project.id, &reason(&env)),
+        crate::Error::ProjectNotActive,
+    );
+}
+
+#[test]
+fn test_cancel_requires_creator_or_admin() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let (_, project) = register(&env, &client, &admin, vec![&env, usdc.address.clone()]);
+
+    let stranger = Address::generate(&env);
+    assert!(client
+        .try_cancel_project(&stranger, &project.id, &reason(&env))
+        .is_err());
+
+    let donor = donate(&env, &client, project.id, &usdc, 100);
+    assert_error(
+        client.try_claim_refund(&project.id, &donor),
+        crate::Error::NotRefundable,
+    );
+}
+
+#[test]
+fn test_completed_project_cannot_be_cancelled() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let (creator, project) = register(&env, &client, &admin, vec![&env, usdc.address.clone()]);
+    donate(&env, &client, project.id, &usdc, 100);
+
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+
+    assert_error(
+        client.try_cancel_project(&creator, &project.id, &reason(&env)),
+        crate::Error::MilestoneAlreadyReleased,
+    );
+}
+
+#[test]
+fn test_cancellation_returns_matched_funds_to_sponsor() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let (creator, project) = register(&env, &client, &admin, vec![&env, usdc.address.clone()]);
+    let sponsor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&sponsor, &1_000);
+    let match_id = client.create_match(
+        &sponsor,
+        &project.id,
+        &usdc.address,
+        &10_000,
+        &1_000,
+        &(env.ledger().timestamp() + 30 * DAY),
+    );
+    let donor = donate(&env, &client, project.id, &usdc, 300);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 600);
+
+    client.cancel_project(&creator, &project.id, &reason(&env));
+    client.claim_refund(&project.id, &donor);
+    assert_eq!(client.release_match(&match_id), 1_000);
+
+    assert_eq!(usdc.balance(&donor), 300);
+    assert_eq!(usdc.balance(&sponsor), 1_000);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 0);
+    assert_eq!(usdc.balance(&client.address), 0);
+}
+
+#[test]
+fn test_cancellation_returns_round_match_to_funder() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let (creator, project) = register(&env, &client, &admin, vec![&env, usdc.address.clone()]);
+    let funder = Address::generate(&env);
+    client.grant_role(&admin, &funder, &Role::Admin);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&funder, &1_000);
+    let now = env.ledger().timestamp();
+    let round_id = client.create_round(&funder, &usdc.address, &1_000, &now, &(now + DAY), &10_000);
+    client.join_round(&round_id, &project.id);
+    let donors = [
+        donate(&env, &client, project.id, &usdc, 100),
+        donate(&env, &client, project.id, &usdc, 100),
+    ];
+
+    env.ledger().set_timestamp(now + DAY);
+    assert_error(
+        client.try_reclaim_round_match(&round_id, &project.id),
+        crate::Error::RoundNotEnded,
+    );
+    client.close_round(&round_id);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 1_200);
+    assert_error(
+        client.try_reclaim_round_match(&round_id, &project.id),
+        crate::Error::NotRefundable,
+    );
+
+    client.cancel_project(&creator, &project.id, &reason(&env));
+    for donor in donors.iter() {
+        client.claim_refund(&project.id, donor);
+    }
+    assert_eq!(client.reclaim_round_match(&round_id, &project.id), 1_000);
+    let last = env.events().all().last().unwrap();
+    let data: RoundMatchReclaimed = last.2.try_into_val(&env).unwrap();
+    assert_eq!(
+        data,
+        RoundMatchReclaimed {
+            round_id,
+            project_id: project.id,
+            returned: 1_000,
+        }
+    );
+
+    assert_eq!(usdc.balance(&funder), 1_000);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 0);
+    assert_eq!(client.get_round_project(&round_id, &project.id).matched, 0);
+    assert_eq!(usdc.balance(&client.address), 0);
+    assert_error(
+        client.try_reclaim_round_match(&round_id, &project.id),
+        crate::Error::InsufficientBalance,
+    );
+}
+
+#[test]
+fn test_missed_goal_returns_round_match_to_funder() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let creator = Address::generate(&env);
+    client.grant_role(&admin, &creator, &Role::ProjectManager);
+    let mut options = ProjectOptions::default_for(&env);
+    options.funding_mode = FundingMode::AllOrNothing;
+    let project = client.register_project_with_options(
+        &creator,
+        &vec![&env, usdc.address.clone()],
+        &10_000,
+        &dummy_commitment(&env),
+        &(env.ledger().timestamp() + 60 * DAY),
+        &options,
+    );
+    let funder = Address::generate(&env);
+    client.grant_role(&admin, &funder, &Role::Admin);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&funder, &1_000);
+    let now = env.ledger().timestamp();
+    let round_id = client.create_round(&funder, &usdc.address, &1_000, &now, &(now + DAY), &10_000);
+    client.join_round(&round_id, &project.id);
+    let donors = [
+        donate(&env, &client, project.id, &usdc, 100),
+        donate(&env, &client, project.id, &usdc, 100),
+    ];
+
+    env.ledger().set_timestamp(now + DAY);
+    client.close_round(&round_id);
+    assert_error(
+        client.try_reclaim_round_match(&round_id, &project.id),
+        crate::Error::NotRefundable,
+    );
+
+    // 1_200 of 10_000 at the deadline: the goal is missed.
+    env.ledger().set_timestamp(project.deadline);
+    for donor in donors.iter() {
+        client.claim_refund(&project.id, donor);
+    }
+    assert_eq!(client.reclaim_round_match(&round_id, &project.id), 1_000);
+    assert_eq!(usdc.balance(&funder), 1_000);
+    assert_eq!(usdc.balance(&client.address), 0);
+}
+
+#[test]
+fn test_committed_donation_is_refundable_after_reveal() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let (creator, project) = register(&env, &client, &admin, vec![&env, usdc.address.clone()]);
+    let donor = Address::generate(&env);
+    let secret = Bytes::from_array(&env, &[9u8; 32]);
+    let mut preimage = donor.clone().to_xdr(&env);
+    preimage.append(&secret);
+    let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+    let relayer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&relayer, &400);
+    client.donate_committed(&project.id, &relayer, &usdc.address, &400, &commitment);
+
+    client.cancel_project(&creator, &project.id, &reason(&env));
+    assert_error(
+        client.try_claim_refund(&project.id, &donor),
+        crate::Error::InsufficientBalance,
+    );
+    client.reveal_donation(&project.id, &donor, &secret);
+    client.claim_refund(&project.id, &donor);
+
+    assert_eq!(usdc.balance(&donor), 400);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 0);
+}
@@ -0,0 +1,227 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    token, vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, TryIntoVal,
+};
+
+use crate::events::{DonationCommitted, DonationRevealed};
+use crate::{
+    CommittedDonation, ContentId, Contribution, PifpProtocol, PifpProtocolClient, Project,
+};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, token: &Address) -> Project {
+    client.register_project(
+        admin,
+        &vec![env, token.clone()],
+        &10_000,
+        &ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+    )
+}
+
+/// Off-chain commitment computation a donor's wallet would perform.
+fn commitment_for(env: &Env, donor: &Address, secret: &Bytes) -> BytesN<32> {
+    let mut preimage = donor.clone().to_xdr(env);
+    preimage.append(secret);
+    env.crypto().sha256(&preimage).into()
+}
+
+fn funded_relayer(env: &Env, token: &Address, amount: i128) -> Address {
+    let relayer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&relayer, &amount);
+    relayer
+}
+
+#[test]
+fn test_committed_donation_hides_donor() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donor = Address::generate(&env);
+    let relayer = funded_relayer(&env, &usdc.address, 1_000);
+    let commitment = commitment_for(&env, &donor, &Bytes::from_array(&env, &[7u8; 32]));
+
+    let accepted = client.donate_committed(&project.id, &relayer, &usdc.address, &600, &commitment);
+    assert_eq!(accepted, 600);
+
+    let last = env.events().all().last().unwrap();
+    assert_eq!(
+        last.1,
+        vec![
+            &env,
+            symbol_short!("committed").into_val(&env),
+            project.id.into_val(&env)
+        ]
+    );
+    let data: DonationCommitted = last.2.try_into_val(&env).unwrap();
+    assert_eq!(
+        data,
+        DonationCommitted {
+            project_id: project.id,
+            token: usdc.address.clone(),
+            amount: 600,
+            commitment: commitment.clone(),
+        }
+    );
+
+    // Funds are escrowed and counted, but nobody is credited yet.
+    assert_eq!(usdc.balance(&relayer), 400);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 600);
+    let loaded = client.get_project(&project.id);
+    assert_eq!(loaded.deposit_count, 1);
+    assert_eq!(loaded.unique_donors, 0);
+    assert_eq!(client.list_donors(&project.id, &0, &10).len(), 0);
+    assert_eq!(client.get_contribution(&project.id, &relayer).len(), 0);
+    assert_eq!(
+        client.get_committed_donation(&project.id, &commitment),
+        Some(CommittedDonation {
+            token: usdc.address.clone(),
+            amount: 600,
+            revealed: false,
+        })
+    );
+}
+
+#[test]
+fn test_reveal_credits_donor() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donor = Address::generate(&env);
+    let relayer = funded_relayer(&env, &usdc.address, 1_000);
+    let secret = Bytes::from_array(&env, &[7u8; 32]);
+    let commitment = commitment_for(&env, &donor, &secret);
+
+    client.donate_committed(&project.id, &relayer, &usdc.address, &600, &commitment);
+    client.reveal_donation(&project.id, &donor, &secret);
+
+    let last = env.events().all().last().unwrap();
+    let data: DonationRevealed = last.2.try_into_val(&env).unwrap();
+    assert_eq!(
+        data,
+        DonationRevealed {
+            project_id: project.id,
+            commitment: commitment.clone(),
+            donor: donor.clone(),
+        }
+    );
+
+    assert_eq!(
+        client.get_contribution(&project.id, &donor),
+        vec![
+            &env,
+            Contribution {
+                token: usdc.address.clone(),
+                amount: 600
+            }
+        ]
+    );
+    assert_eq!(
+        client.list_donors(&project.id, &0, &10),
+        vec![&env, donor.clone()]
+    );
+    let loaded = client.get_project(&project.id);
+    assert_eq!(
+        (
+            loaded.unique_donors,
+            loaded.donation_count,
+            loaded.deposit_count
+        ),
+        (1, 1, 1)
+    );
+    // Totals raised are not counted twice.
+    assert_eq!(
+        client
+            .get_project_balances(&project.id)
+            .balances
+            .get(0)
+            .unwrap()
+            .raised,
+        600
+    );
+
+    let again = client.try_reveal_donation(&project.id, &donor, &secret);
+    assert_eq!(
+        again.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            crate::Error::AlreadyRevealed as u32
+        )))
+    );
+}
+
+#[test]
+fn test_secret_alone_cannot_claim_donation() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donor = Address::generate(&env);
+    let thief = Address::generate(&env);
+    let relayer = funded_relayer(&env, &usdc.address, 1_000);
+    let secret = Bytes::from_array(&env, &[7u8; 32]);
+
+    client.donate_committed(
+        &project.id,
+        &relayer,
+        &usdc.address,
+        &600,
+        &commitment_for(&env, &donor, &secret),
+    );
+
+    let result = client.try_reveal_donation(&project.id, &thief, &secret);
+    assert_eq!(
+        result.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            crate::Error::CommitmentNotFound as u32
+        )))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #31)")]
+fn test_commitment_cannot_be_reused() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let relayer = funded_relayer(&env, &usdc.address, 1_000);
+    let commitment = BytesN::from_array(&env, &[9u8; 32]);
+
+    client.donate_committed(&project.id, &relayer, &usdc.address, &100, &commitment);
+    client.donate_committed(&project.id, &relayer, &usdc.address, &100, &commitment);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")]
+fn test_committed_donation_is_validated_like_deposit() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let relayer = funded_relayer(&env, &usdc.address, 1_000);
+
+    client.donate_committed(
+        &project.id,
+        &relayer,
+        &usdc.address,
+        &0,
+        &BytesN::from_array(&env, &[9u8; 32]),
+    );
+}
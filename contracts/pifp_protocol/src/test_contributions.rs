@@ -0,0 +1,206 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token, vec, Address, Env, Vec};
+
+use crate::test_util::{dummy_commitment, dummy_proof, dummy_salt};
+use crate::{Contribution, PifpProtocol, PifpProtocolClient, Project};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    tokens: &Vec<Address>,
+) -> Project {
+    client.register_project(
+        admin,
+        tokens,
+        &1_000_000,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 86_400 * 30),
+    )
+}
+
+fn deposit(
+    env: &Env,
+    client: &PifpProtocolClient,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donor, &amount);
+    client.deposit(&project_id, donor, token, &amount);
+}
+
+#[test]
+fn test_contributions_accumulate_per_token() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(
+        &env,
+        &client,
+        &admin,
+        &vec![&env, usdc.address.clone(), eurc.address.clone()],
+    );
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    deposit(&env, &client, project.id, &alice, &usdc.address, 100);
+    deposit(&env, &client, project.id, &alice, &usdc.address, 50);
+    deposit(&env, &client, project.id, &alice, &eurc.address, 70);
+    deposit(&env, &client, project.id, &bob, &eurc.address, 30);
+
+    assert_eq!(
+        client.get_contribution(&project.id, &alice),
+        vec![
+            &env,
+            Contribution {
+                token: usdc.address.clone(),
+                amount: 150
+            },
+            Contribution {
+                token: eurc.address.clone(),
+                amount: 70
+            },
+        ]
+    );
+    assert_eq!(
+        client.get_contribution(&project.id, &bob),
+        vec![
+            &env,
+            Contribution {
+                token: eurc.address.clone(),
+                amount: 30
+            }
+        ]
+    );
+    assert_eq!(
+        client
+            .get_contribution(&project.id, &Address::generate(&env))
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_withdrawal_reduces_contribution_and_total() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &vec![&env, usdc.address.clone()]);
+    let alice = Address::generate(&env);
+
+    deposit(&env, &client, project.id, &alice, &usdc.address, 100);
+    client.withdraw_donation(&project.id, &alice, &usdc.address, &40);
+
+    assert_eq!(
+        client.get_contribution(&project.id, &alice),
+        vec![
+            &env,
+            Contribution {
+                token: usdc.address.clone(),
+                amount: 60
+            }
+        ]
+    );
+    assert_eq!(
+        client
+            .get_project_balances(&project.id)
+            .balances
+            .get(0)
+            .unwrap()
+            .raised,
+        60
+    );
+}
+
+#[test]
+fn test_totals_survive_release() {
+    let (env, client, admin) = setup_with_init();
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &vec![&env, usdc.address.clone()]);
+    let alice = Address::generate(&env);
+
+    deposit(&env, &client, project.id, &alice, &usdc.address, 500);
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+
+    let progress = client
+        .get_project_balances(&project.id)
+        .balances
+        .get(0)
+        .unwrap();
+    assert_eq!(progress.balance, 0);
+    assert_eq!(progress.raised, 500);
+    assert_eq!(
+        client.get_contribution(&project.id, &alice),
+        vec![
+            &env,
+            Contribution {
+                token: usdc.address.clone(),
+                amount: 500
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_list_donors_paginates_in_first_deposit_order() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(
+        &env,
+        &client,
+        &admin,
+        &vec![&env, usdc.address.clone(), eurc.address.clone()],
+    );
+
+    let donors: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&env)).collect();
+    for donor in &donors {
+        deposit(&env, &client, project.id, donor, &usdc.address, 10);
+    }
+    // Repeat donors, in either token, are listed once.
+    deposit(&env, &client, project.id, &donors[0], &eurc.address, 10);
+    deposit(&env, &client, project.id, &donors[3], &usdc.address, 10);
+
+    let page1 = client.list_donors(&project.id, &0, &2);
+    let page2 = client.list_donors(&project.id, &2, &2);
+    let page3 = client.list_donors(&project.id, &4, &2);
+    assert_eq!(page1, vec![&env, donors[0].clone(), donors[1].clone()]);
+    assert_eq!(page2, vec![&env, donors[2].clone(), donors[3].clone()]);
+    assert_eq!(page3, vec![&env, donors[4].clone()]);
+    assert_eq!(client.list_donors(&project.id, &5, &10).len(), 0);
+    assert_eq!(client.list_donors(&project.id, &0, &100).len(), 5);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_get_contribution_unknown_project() {
+    let (env, client, _admin) = setup_with_init();
+    client.get_contribution(&42, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_list_donors_unknown_project() {
+    let (_env, client, _admin) = setup_with_init();
+    client.list_donors(&42, &0, &10);
+}
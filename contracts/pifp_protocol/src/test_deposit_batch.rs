@@ -0,0 +1,218 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, Events},
+    token, vec, Address, BytesN, Env, IntoVal, Map, Symbol, TryIntoVal, Vec,
+};
+
+use crate::events::ProjectFunded;
+use crate::{
+    ContentId, ExcessPolicy, PifpProtocol, PifpProtocolClient, Project, ProjectOptions,
+    ProjectStatus,
+};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    tokens: &Vec<Address>,
+    goal: i128,
+) -> Project {
+    client.register_project(
+        admin,
+        tokens,
+        &goal,
+        &ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+    )
+}
+
+fn batch_error(
+    result: Result<
+        Result<Vec<i128>, soroban_sdk::ConversionError>,
+        Result<soroban_sdk::Error, soroban_sdk::InvokeError>,
+    >,
+    error: crate::Error,
+) {
+    assert_eq!(
+        result.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(error as u32)))
+    );
+}
+
+#[test]
+fn test_batch_funds_several_projects_with_one_auth() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone(), eurc.address.clone()];
+    let a = register(&env, &client, &admin, &tokens, 1_000);
+    let b = register(&env, &client, &admin, &tokens, 1_000);
+    let donator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&donator, &2_000);
+    token::StellarAssetClient::new(&env, &eurc.address).mint(&donator, &2_000);
+
+    let allocations = vec![
+        &env,
+        (a.id, usdc.address.clone(), 1_000i128),
+        (b.id, usdc.address.clone(), 300i128),
+        (b.id, eurc.address.clone(), 200i128),
+    ];
+    let accepted = client.deposit_batch(&donator, &allocations);
+    assert_eq!(accepted, vec![&env, 1_000i128, 300, 200]);
+
+    // A single authorization covers the whole batch.
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, donator);
+    assert_eq!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            Symbol::new(&env, "deposit_batch"),
+            (donator.clone(), allocations.clone()).into_val(&env),
+        ))
+    );
+
+    // One funded event per allocation, plus goal_met for project A.
+    let funded: std::vec::Vec<ProjectFunded> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("funded").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .collect();
+    assert_eq!(funded.len(), 3);
+    assert_eq!(funded[1].project_id, b.id);
+    assert_eq!(funded[1].amount, 300);
+
+    assert_eq!(client.get_project(&a.id).status, ProjectStatus::Active);
+    let b_loaded = client.get_project(&b.id);
+    assert_eq!(b_loaded.status, ProjectStatus::Funding);
+    assert_eq!((b_loaded.unique_donors, b_loaded.deposit_count), (1, 2));
+    assert_eq!(client.get_balance(&b.id, &eurc.address), 200);
+    assert_eq!(usdc.balance(&donator), 700);
+    assert_eq!(eurc.balance(&donator), 1_800);
+}
+
+#[test]
+fn test_batch_is_atomic() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+    let a = register(&env, &client, &admin, &tokens, 1_000);
+    let donator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&donator, &1_000);
+
+    // The second allocation targets a project that does not exist.
+    let result = client.try_deposit_batch(
+        &donator,
+        &vec![
+            &env,
+            (a.id, usdc.address.clone(), 500i128),
+            (99, usdc.address.clone(), 500i128),
+        ],
+    );
+    assert!(result.is_err());
+    assert_eq!(client.get_balance(&a.id, &usdc.address), 0);
+    assert_eq!(usdc.balance(&donator), 1_000);
+    assert_eq!(client.get_project(&a.id).deposit_count, 0);
+}
+
+#[test]
+fn test_batch_rejects_malformed_batches() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+    let a = register(&env, &client, &admin, &tokens, 1_000);
+    let donator = Address::generate(&env);
+
+    batch_error(
+        client.try_deposit_batch(&donator, &Vec::new(&env)),
+        crate::Error::InvalidBatch,
+    );
+
+    let duplicate = vec![
+        &env,
+        (a.id, usdc.address.clone(), 1i128),
+        (a.id, usdc.address.clone(), 1i128),
+    ];
+    batch_error(
+        client.try_deposit_batch(&donator, &duplicate),
+        crate::Error::InvalidBatch,
+    );
+
+    let mut too_long = Vec::new(&env);
+    for _ in 0..=crate::MAX_BATCH_SIZE {
+        too_long.push_back((a.id, usdc.address.clone(), 1i128));
+    }
+    batch_error(
+        client.try_deposit_batch(&donator, &too_long),
+        crate::Error::InvalidBatch,
+    );
+
+    batch_error(
+        client.try_deposit_batch(&donator, &vec![&env, (a.id, usdc.address.clone(), 0i128)]),
+        crate::Error::InvalidAmount,
+    );
+}
+
+#[test]
+fn test_batch_applies_caps() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let mut options = ProjectOptions::default_for(&env);
+    let mut caps = Map::new(&env);
+    caps.set(usdc.address.clone(), 1_500i128);
+    options.max_raise = caps;
+    options.excess_policy = ExcessPolicy::AcceptPartial;
+    let capped = client.register_project_with_options(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &1_000,
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+        &options,
+    );
+    let open = register(
+        &env,
+        &client,
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        1_000,
+    );
+    let donator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&donator, &5_000);
+
+    let accepted = client.deposit_batch(
+        &donator,
+        &vec![
+            &env,
+            (capped.id, usdc.address.clone(), 2_000i128),
+            (open.id, usdc.address.clone(), 2_000i128),
+        ],
+    );
+    assert_eq!(accepted, vec![&env, 1_500i128, 2_000]);
+    assert_eq!(usdc.balance(&donator), 1_500);
+}
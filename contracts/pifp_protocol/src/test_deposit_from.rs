@@ -0,0 +1,174 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, Ledger},
+    token, vec, Address, BytesN, Env, IntoVal, Symbol,
+};
+
+use crate::{ContentId, Contribution, PifpProtocol, PifpProtocolClient, Project};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, token: &Address) -> Project {
+    client.register_project(
+        admin,
+        &vec![env, token.clone()],
+        &1_000,
+        &ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+    )
+}
+
+/// Mint `amount` to a new donor who approves the protocol for `allowance`
+/// and authorises a new keeper.
+fn donor_with_keeper(
+    env: &Env,
+    client: &PifpProtocolClient,
+    token: &token::Client,
+    amount: i128,
+    allowance: i128,
+) -> (Address, Address) {
+    let donator = Address::generate(env);
+    let keeper = Address::generate(env);
+    token::StellarAssetClient::new(env, &token.address).mint(&donator, &amount);
+    token.approve(
+        &donator,
+        &client.address,
+        &allowance,
+        &(env.ledger().sequence() + 1_000),
+    );
+    client.set_keeper(&donator, &keeper, &true);
+    (donator, keeper)
+}
+
+fn deposit_error<E>(
+    result: Result<Result<i128, E>, Result<soroban_sdk::Error, soroban_sdk::InvokeError>>,
+    error: crate::Error,
+) {
+    assert_eq!(
+        result.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(error as u32)))
+    );
+}
+
+#[test]
+fn test_keeper_pulls_deposit_from_allowance() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let (donator, keeper) = donor_with_keeper(&env, &client, &token, 1_000, 600);
+
+    let accepted = client.deposit_from(&keeper, &project.id, &donator, &token.address, &400);
+    assert_eq!(accepted, 400);
+
+    // Only the keeper signed; the donor's allowance covered the transfer.
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, keeper);
+    assert_eq!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            Symbol::new(&env, "deposit_from"),
+            (
+                keeper.clone(),
+                project.id,
+                donator.clone(),
+                token.address.clone(),
+                400i128
+            )
+                .into_val(&env),
+        ))
+    );
+
+    assert_eq!(token.balance(&donator), 600);
+    assert_eq!(token.balance(&client.address), 400);
+    assert_eq!(token.allowance(&donator, &client.address), 200);
+    assert_eq!(client.get_balance(&project.id, &token.address), 400);
+
+    // The contribution belongs to the donor, not the keeper.
+    assert_eq!(
+        client.get_contribution(&project.id, &donator),
+        vec![
+            &env,
+            Contribution {
+                token: token.address.clone(),
+                amount: 400
+            }
+        ]
+    );
+    assert_eq!(client.get_contribution(&project.id, &keeper).len(), 0);
+    assert_eq!(
+        client.list_donors(&project.id, &0, &10),
+        vec![&env, donator]
+    );
+}
+
+#[test]
+fn test_deposit_from_requires_approved_keeper() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let (donator, keeper) = donor_with_keeper(&env, &client, &token, 1_000, 1_000);
+
+    let stranger = Address::generate(&env);
+    deposit_error(
+        client.try_deposit_from(&stranger, &project.id, &donator, &token.address, &100),
+        crate::Error::NotAuthorized,
+    );
+
+    client.set_keeper(&donator, &keeper, &false);
+    deposit_error(
+        client.try_deposit_from(&keeper, &project.id, &donator, &token.address, &100),
+        crate::Error::NotAuthorized,
+    );
+    assert_eq!(token.balance(&donator), 1_000);
+}
+
+#[test]
+fn test_deposit_from_beyond_allowance_fails() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let (donator, keeper) = donor_with_keeper(&env, &client, &token, 1_000, 300);
+
+    let result = client.try_deposit_from(&keeper, &project.id, &donator, &token.address, &301);
+    assert!(result.is_err());
+    assert_eq!(token.balance(&donator), 1_000);
+    assert_eq!(client.get_balance(&project.id, &token.address), 0);
+    assert_eq!(client.get_project(&project.id).deposit_count, 0);
+}
+
+#[test]
+fn test_deposit_from_applies_deposit_checks() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let (donator, keeper) = donor_with_keeper(&env, &client, &token, 1_000, 1_000);
+
+    deposit_error(
+        client.try_deposit_from(&keeper, &project.id, &donator, &token.address, &0),
+        crate::Error::InvalidAmount,
+    );
+
+    env.ledger().set_timestamp(project.deadline);
+    deposit_error(
+        client.try_deposit_from(&keeper, &project.id, &donator, &token.address, &100),
+        crate::Error::ProjectExpired,
+    );
+    assert_eq!(token.balance(&donator), 1_000);
+}
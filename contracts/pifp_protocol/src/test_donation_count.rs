@@ -1,11 +1,16 @@
 extern crate std;
 
+use proptest::prelude::*;
 use soroban_sdk::{
     testutils::Address as _,
     token, Address, BytesN, Env,
 };
 
-use crate::{PifpProtocol, PifpProtocolClient, Role};
+use crate::invariants::assert_donor_metrics_ordered;
+use crate::storage::DataKey;
+use crate::test_util::dummy_proof;
+use crate::types::{LegacyProjectConfig, LegacyProjectState};
+use crate::{PifpProtocol, PifpProtocolClient, ProjectStatus, Role};
 
 fn setup() -> (Env, PifpProtocolClient<'static>) {
     let env = Env::default();
@@ -27,14 +32,16 @@ fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
     token::Client::new(env, &addr.address())
 }
 
-fn dummy_proof(env: &Env) -> BytesN<32> {
-    BytesN::from_array(env, &[0xabu8; 32])
-}
-
 fn future_deadline(env: &Env) -> u64 {
     env.ledger().timestamp() + 86_400
 }
 
+fn allow_tokens(client: &PifpProtocolClient, admin: &Address, tokens: &soroban_sdk::Vec<Address>) {
+    for token in tokens.iter() {
+        client.allow_token(admin, &token, &None);
+    }
+}
+
 #[test]
 fn test_donation_count_initialized_to_zero() {
     let (env, client, super_admin) = setup_with_init();
@@ -45,6 +52,7 @@ fn test_donation_count_initialized_to_zero() {
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
 
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
@@ -67,6 +75,7 @@ fn test_donation_count_increments_for_new_donor() {
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
 
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
@@ -97,6 +106,7 @@ fn test_donation_count_stays_same_for_repeated_donor() {
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
 
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
@@ -134,6 +144,7 @@ fn test_donation_count_increments_for_different_donors() {
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
 
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
@@ -172,6 +183,7 @@ fn test_donation_count_increments_for_same_donor_different_tokens() {
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
 
     let tokens = soroban_sdk::vec![&env, token1.address.clone(), token2.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
@@ -213,6 +225,7 @@ fn test_donation_count_complex_scenario() {
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
 
     let tokens = soroban_sdk::vec![&env, token1.address.clone(), token2.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
@@ -258,3 +271,136 @@ fn test_donation_count_complex_scenario() {
     client.deposit(&project.id, &donator2, &token2.address, &100i128);
     assert_eq!(client.get_project(&project.id).donation_count, 5);
 }
+
+#[test]
+fn test_metrics_distinguish_donors_pairs_and_deposits() {
+    let (env, client, super_admin) = setup_with_init();
+    let token_admin = Address::generate(&env);
+    let token1 = create_token(&env, &token_admin);
+    let token2 = create_token(&env, &token_admin);
+    let donator = Address::generate(&env);
+
+    let tokens = soroban_sdk::vec![&env, token1.address.clone(), token2.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
+    let project = client.register_project(
+        &super_admin,
+        &tokens,
+        &10_000i128,
+        &dummy_proof(&env),
+        &future_deadline(&env),
+    );
+    assert_eq!((project.unique_donors, project.deposit_count), (0, 0));
+
+    token::StellarAssetClient::new(&env, &token1.address).mint(&donator, &500i128);
+    token::StellarAssetClient::new(&env, &token2.address).mint(&donator, &500i128);
+    client.deposit(&project.id, &donator, &token1.address, &100i128);
+    client.deposit(&project.id, &donator, &token1.address, &100i128);
+    client.deposit(&project.id, &donator, &token2.address, &100i128);
+
+    // One person giving three times in two tokens.
+    let project = client.get_project(&project.id);
+    assert_eq!(project.unique_donors, 1);
+    assert_eq!(project.donation_count, 2);
+    assert_eq!(project.deposit_count, 3);
+}
+
+#[test]
+fn test_legacy_state_is_migrated_on_read() {
+    let (env, client, super_admin) = setup_with_init();
+    let token_admin = Address::generate(&env);
+    let token = create_token(&env, &token_admin);
+    let donator = Address::generate(&env);
+
+    let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
+
+    // Store project 0 exactly as the contract did before donor metrics
+    // were split out: baseline config and pre-metrics state.
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(
+            &DataKey::ProjConfig(0),
+            &LegacyProjectConfig {
+                id: 0,
+                creator: super_admin.clone(),
+                accepted_tokens: tokens.clone(),
+                goal: 10_000,
+                proof_hash: BytesN::from_array(&env, &[0xabu8; 32]),
+                deadline: future_deadline(&env),
+            },
+        );
+        storage.set(
+            &DataKey::ProjState(0),
+            &LegacyProjectState {
+                status: ProjectStatus::Funding,
+                donation_count: 4,
+            },
+        );
+        storage.set(&DataKey::TokenBalance(0, token.address.clone()), &0i128);
+        env.storage().instance().set(&DataKey::ProjectCount, &1u64);
+    });
+
+    let migrated = client.get_project(&0);
+    assert_eq!(migrated.donation_count, 4);
+    assert_eq!(migrated.deposit_count, 4);
+    // Four (donor, token) pairs over one token: at least four donors.
+    assert_eq!(migrated.unique_donors, 4);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::ProjState(0)));
+        assert!(storage.has(&DataKey::ProjStateV2(0)));
+    });
+
+    // Deposits keep counting from the migrated values.
+    token::StellarAssetClient::new(&env, &token.address).mint(&donator, &100i128);
+    client.deposit(&0, &donator, &token.address, &100i128);
+    let project = client.get_project(&0);
+    assert_eq!(project.donation_count, 5);
+    assert_eq!(project.deposit_count, 5);
+    assert_eq!(project.unique_donors, 5);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Replays a random sequence of (donor, token) deposits and checks each
+    /// metric against a simple model.
+    #[test]
+    fn prop_donor_metrics_match_model(
+        deposits in prop::collection::vec((0usize..4, 0usize..3), 1..20)
+    ) {
+        let (env, client, super_admin) = setup_with_init();
+        let token_admin = Address::generate(&env);
+        let token_list: std::vec::Vec<_> = (0..3).map(|_| create_token(&env, &token_admin)).collect();
+        let donors: std::vec::Vec<Address> = (0..4).map(|_| Address::generate(&env)).collect();
+
+        let mut tokens = soroban_sdk::Vec::new(&env);
+        for t in &token_list {
+            tokens.push_back(t.address.clone());
+        }
+        allow_tokens(&client, &super_admin, &tokens);
+        let project = client.register_project(
+            &super_admin,
+            &tokens,
+            &1_000_000i128,
+            &dummy_proof(&env),
+            &future_deadline(&env),
+        );
+
+        let mut seen_donors = std::collections::BTreeSet::new();
+        let mut seen_pairs = std::collections::BTreeSet::new();
+        for (n, (d, t)) in deposits.iter().enumerate() {
+            let token = &token_list[*t].address;
+            token::StellarAssetClient::new(&env, token).mint(&donors[*d], &10i128);
+            client.deposit(&project.id, &donors[*d], token, &10i128);
+            seen_donors.insert(*d);
+            seen_pairs.insert((*d, *t));
+
+            let current = client.get_project(&project.id);
+            prop_assert_eq!(current.unique_donors as usize, seen_donors.len());
+            prop_assert_eq!(current.donation_count as usize, seen_pairs.len());
+            prop_assert_eq!(current.deposit_count as usize, n + 1);
+            assert_donor_metrics_ordered(&current);
+        }
+    }
+}
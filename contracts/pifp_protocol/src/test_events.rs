@@ -3,11 +3,11 @@ extern crate std;
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events},
-    token, vec, Address, BytesN, Env, IntoVal, TryIntoVal,
+    token, vec, Address, Bytes, BytesN, Env, IntoVal, TryIntoVal,
 };
 
 use crate::events::{ProjectCreated, ProjectFunded, ProjectVerified};
-use crate::{PifpProtocol, PifpProtocolClient, Role};
+use crate::{ContentId, FundingMode, PifpProtocol, PifpProtocolClient, Role};
 
 fn setup() -> (Env, PifpProtocolClient<'static>) {
     let env = Env::default();
@@ -29,6 +29,19 @@ fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
     token::Client::new(env, &addr.address())
 }
 
+fn allow_tokens(client: &PifpProtocolClient, admin: &Address, tokens: &soroban_sdk::Vec<Address>) {
+    for token in tokens.iter() {
+        client.allow_token(admin, &token, &None);
+    }
+}
+
+/// Salted commitment to `proof`, as passed to `register_project`.
+fn commit_proof(env: &Env, proof: &ContentId, salt: &BytesN<32>) -> ContentId {
+    let mut preimage = proof.digest.clone();
+    preimage.append(&Bytes::from_array(env, &salt.to_array()));
+    ContentId::sha2_256(env.crypto().sha256(&preimage).into())
+}
+
 #[test]
 fn test_project_created_event() {
     let (env, client, super_admin) = setup_with_init();
@@ -36,13 +49,14 @@ fn test_project_created_event() {
     let token_admin = Address::generate(&env);
     let token = create_token(&env, &token_admin);
     let goal = 5000i128;
-    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let proof = ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32]));
     let deadline = env.ledger().timestamp() + 86400;
 
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
 
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
-    let project = client.register_project(&creator, &tokens, &goal, &proof_hash, &deadline);
+    allow_tokens(&client, &super_admin, &tokens);
+    let project = client.register_project(&creator, &tokens, &goal, &proof, &deadline);
 
     let all_events = env.events().all();
     let last_event = all_events.last().expect("No events found");
@@ -65,6 +79,8 @@ fn test_project_created_event() {
             creator: creator.clone(),
             token: token.address.clone(),
             goal,
+            funding_mode: FundingMode::Flexible,
+            proof: proof.clone(),
         }
     );
 }
@@ -80,11 +96,12 @@ fn test_project_funded_event() {
 
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
         &10000,
-        &BytesN::from_array(&env, &[0u8; 32]),
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0u8; 32])),
         &(env.ledger().timestamp() + 86400),
     );
 
@@ -124,21 +141,23 @@ fn test_project_verified_event() {
     let oracle = Address::generate(&env);
     let token_admin = Address::generate(&env);
     let token = create_token(&env, &token_admin);
-    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let proof = ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32]));
+    let salt = BytesN::from_array(&env, &[0x5au8; 32]);
 
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
     client.set_oracle(&super_admin, &oracle);
 
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
         &1000,
-        &proof_hash,
+        &commit_proof(&env, &proof, &salt),
         &(env.ledger().timestamp() + 86400),
     );
 
-    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    client.verify_and_release(&oracle, &project.id, &proof, &salt);
 
     let all_events = env.events().all();
     let last_event = all_events.last().expect("No events found");
@@ -159,7 +178,10 @@ fn test_project_verified_event() {
         ProjectVerified {
             project_id: project.id,
             oracle: oracle.clone(),
-            proof_hash: proof_hash.clone(),
+            proof: proof.clone(),
+            salt: salt.clone(),
+            commitment: commit_proof(&env, &proof, &salt),
+            evidence_index: None,
         }
     );
 }
@@ -190,11 +212,12 @@ fn test_get_project_balances() {
     // Grant creator the ProjectManager role and register project
     client.grant_role(&super_admin, &creator, &Role::ProjectManager);
     let tokens = soroban_sdk::vec![&env, token_a.address.clone(), token_b.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
         &10_000i128,
-        &BytesN::from_array(&env, &[0u8; 32]),
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0u8; 32])),
         &(env.ledger().timestamp() + 86_400),
     );
 
@@ -233,7 +256,8 @@ fn test_funds_released_to_creator() {
     let token_admin = Address::generate(&env);
     let donator = Address::generate(&env);
     let deposit_amount = 1000i128;
-    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let proof = ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32]));
+    let salt = BytesN::from_array(&env, &[0x5au8; 32]);
 
     // Create a mock token
     let token = create_token(&env, &token_admin);
@@ -244,11 +268,12 @@ fn test_funds_released_to_creator() {
 
     // Register project
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    allow_tokens(&client, &super_admin, &tokens);
     let project = client.register_project(
         &creator,
         &tokens,
         &5000,
-        &proof_hash,
+        &commit_proof(&env, &proof, &salt),
         &(env.ledger().timestamp() + 86400),
     );
 
@@ -258,7 +283,7 @@ fn test_funds_released_to_creator() {
     client.deposit(&project.id, &donator, &token.address, &deposit_amount);
 
     // Verify and release - this should transfer funds to creator
-    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    client.verify_and_release(&oracle, &project.id, &proof, &salt);
 
     // Check creator received the funds
     let creator_token_client = token::Client::new(&env, &token.address);
@@ -0,0 +1,231 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, BytesN, Env, TryIntoVal,
+};
+
+use crate::events::{EvidenceAdded, ProjectVerified};
+use crate::test_util::{assert_error, dummy_commitment, dummy_proof, dummy_salt};
+use crate::{
+    ContentId, EvidenceKind, GovernanceError, PifpProtocol, PifpProtocolClient, Project, Role,
+};
+
+const DAY: u64 = 86_400;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn content_id(env: &Env, byte: u8) -> ContentId {
+    ContentId::sha2_256(BytesN::from_array(env, &[byte; 32]))
+}
+
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, token: &Address) -> Project {
+    client.register_project(
+        admin,
+        &vec![env, token.clone()],
+        &1_000,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 30 * DAY),
+    )
+}
+
+#[test]
+fn test_creator_and_auditor_append_entries() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let auditor = Address::generate(&env);
+    client.grant_role(&admin, &auditor, &Role::Auditor);
+
+    env.ledger().set_timestamp(1_000);
+    let first = client.add_evidence(
+        &admin,
+        &project.id,
+        &content_id(&env, 1),
+        &EvidenceKind::Progress,
+    );
+    assert_eq!(first.index, 0);
+    assert_eq!(first.timestamp, 1_000);
+
+    env.ledger().set_timestamp(2_000);
+    let second = client.add_evidence(
+        &auditor,
+        &project.id,
+        &content_id(&env, 2),
+        &EvidenceKind::Audit,
+    );
+    let added: EvidenceAdded = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("evidence").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(added.entry, second);
+    assert_eq!(second.index, 1);
+    assert_eq!(second.author, auditor);
+    assert_eq!(second.kind, EvidenceKind::Audit);
+
+    assert_eq!(client.get_evidence(&project.id, &0), first);
+    assert_eq!(client.get_project(&project.id).evidence_count, 2);
+}
+
+#[test]
+fn test_list_evidence_pages_in_order() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    for i in 0..5u8 {
+        client.add_evidence(
+            &admin,
+            &project.id,
+            &content_id(&env, i),
+            &EvidenceKind::Photo,
+        );
+    }
+
+    let page = client.list_evidence(&project.id, &0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(1).unwrap().content_hash, content_id(&env, 1));
+    let page = client.list_evidence(&project.id, &4, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().index, 4);
+    assert_eq!(client.list_evidence(&project.id, &5, &10).len(), 0);
+    assert_eq!(client.list_evidence(&project.id, &0, &500).len(), 5);
+}
+
+#[test]
+fn test_add_evidence_requires_creator_or_auditor() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let stranger = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+
+    assert_error(
+        client.try_add_evidence(
+            &stranger,
+            &project.id,
+            &content_id(&env, 1),
+            &EvidenceKind::Other,
+        ),
+        crate::Error::NotAuthorized,
+    );
+    assert_error(
+        client.try_add_evidence(
+            &oracle,
+            &project.id,
+            &content_id(&env, 1),
+            &EvidenceKind::Other,
+        ),
+        crate::Error::NotAuthorized,
+    );
+    assert_error(
+        client.try_get_evidence(&project.id, &0),
+        GovernanceError::EvidenceNotFound,
+    );
+    assert_error(
+        client.try_list_evidence(&99, &0, &10),
+        crate::Error::ProjectNotFound,
+    );
+}
+
+#[test]
+fn test_add_evidence_rejects_unknown_content_id() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+
+    let unknown = ContentId {
+        code: 0x99,
+        digest: content_id(&env, 1).digest,
+    };
+    assert_error(
+        client.try_add_evidence(&admin, &project.id, &unknown, &EvidenceKind::Audit),
+        GovernanceError::InvalidContentId,
+    );
+    assert_eq!(client.list_evidence(&project.id, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_verification_cites_evidence_entry() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    client.add_evidence(
+        &admin,
+        &project.id,
+        &content_id(&env, 1),
+        &EvidenceKind::Progress,
+    );
+    client.add_evidence(
+        &admin,
+        &project.id,
+        &content_id(&env, 2),
+        &EvidenceKind::Audit,
+    );
+
+    assert_error(
+        client.try_verify_with_evidence(
+            &oracle,
+            &project.id,
+            &dummy_proof(&env),
+            &dummy_salt(&env),
+            &2,
+        ),
+        GovernanceError::EvidenceNotFound,
+    );
+    assert_eq!(client.get_project(&project.id).verified_evidence, None);
+
+    client.verify_with_evidence(
+        &oracle,
+        &project.id,
+        &dummy_proof(&env),
+        &dummy_salt(&env),
+        &1,
+    );
+    let verified: ProjectVerified = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("verified").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(verified.evidence_index, Some(1));
+    assert_eq!(client.get_project(&project.id).verified_evidence, Some(1));
+
+    // The log stays open after release.
+    let report = client.add_evidence(
+        &admin,
+        &project.id,
+        &content_id(&env, 3),
+        &EvidenceKind::Progress,
+    );
+    assert_eq!(report.index, 2);
+}
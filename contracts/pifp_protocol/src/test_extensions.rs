@@ -0,0 +1,320 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, BytesN, Env, TryIntoVal,
+};
+
+use crate::events::ExtensionResolved;
+use crate::test_util::assert_error;
+use crate::{
+    ContentId, ExtensionStatus, FundingMode, GovernanceError, PifpProtocol, PifpProtocolClient,
+    Project, ProjectOptions,
+};
+
+const DAY: u64 = 86_400;
+const WINDOW: u64 = 7 * DAY;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, token: &Address) -> Project {
+    client.register_project(
+        admin,
+        &vec![env, token.clone()],
+        &100_000,
+        &ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 30 * DAY),
+    )
+}
+
+fn donate(
+    env: &Env,
+    client: &PifpProtocolClient,
+    project_id: u64,
+    token: &token::Client,
+    amount: i128,
+) -> Address {
+    let donor = Address::generate(env);
+    token::StellarAssetClient::new(env, &token.address).mint(&donor, &amount);
+    client.deposit(&project_id, &donor, &token.address, &amount);
+    donor
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + seconds);
+}
+
+#[test]
+fn test_unopposed_extension_takes_effect_after_window() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    donate(&env, &client, project.id, &usdc, 1_000);
+
+    let new_deadline = project.deadline + 30 * DAY;
+    let proposal = client.propose_extension(&project.id, &new_deadline);
+    assert_eq!(proposal.old_deadline, project.deadline);
+    assert_eq!(proposal.ends_at, env.ledger().timestamp() + WINDOW);
+    assert_eq!(proposal.objection_bps, 2_000);
+
+    assert_error(
+        client.try_finalize_extension(&project.id),
+        GovernanceError::VotingOpen,
+    );
+    assert_eq!(client.get_project(&project.id).deadline, project.deadline);
+
+    advance(&env, WINDOW);
+    assert_eq!(
+        client.finalize_extension(&project.id),
+        ExtensionStatus::Approved
+    );
+    let resolved: ExtensionResolved = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("ext_end").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(resolved.status, ExtensionStatus::Approved);
+    assert_eq!(resolved.deadline, new_deadline);
+    assert_eq!(client.get_project(&project.id).deadline, new_deadline);
+
+    // Deposits are accepted past the original deadline.
+    env.ledger().set_timestamp(project.deadline + DAY);
+    donate(&env, &client, project.id, &usdc, 100);
+    assert_error(
+        client.try_finalize_extension(&project.id),
+        GovernanceError::VotingClosed,
+    );
+}
+
+#[test]
+fn test_objections_above_threshold_reject_extension() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    donate(&env, &client, project.id, &usdc, 700);
+    let objector = donate(&env, &client, project.id, &usdc, 300);
+
+    client.propose_extension(&project.id, &(project.deadline + 30 * DAY));
+    client.vote_extension(&project.id, &objector, &false);
+    assert_eq!(client.get_extension(&project.id).object_weight, 300);
+
+    advance(&env, WINDOW);
+    assert_eq!(
+        client.finalize_extension(&project.id),
+        ExtensionStatus::Rejected
+    );
+    assert_eq!(client.get_project(&project.id).deadline, project.deadline);
+
+    // A new proposal can follow a decided one.
+    let next = client.propose_extension(&project.id, &(project.deadline + DAY));
+    assert_eq!(next.seq, 1);
+    assert_eq!(next.object_weight, 0);
+}
+
+#[test]
+fn test_majority_approval_outweighs_objections() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let supporter = donate(&env, &client, project.id, &usdc, 600);
+    let objector = donate(&env, &client, project.id, &usdc, 400);
+
+    client.propose_extension(&project.id, &(project.deadline + 30 * DAY));
+    client.vote_extension(&project.id, &supporter, &false);
+    client.vote_extension(&project.id, &objector, &false);
+    // Voting again replaces the earlier vote.
+    client.vote_extension(&project.id, &supporter, &true);
+    let proposal = client.get_extension(&project.id);
+    assert_eq!(proposal.approve_weight, 600);
+    assert_eq!(proposal.object_weight, 400);
+
+    advance(&env, WINDOW);
+    assert_error(
+        client.try_vote_extension(&project.id, &objector, &false),
+        GovernanceError::VotingClosed,
+    );
+    assert_eq!(
+        client.finalize_extension(&project.id),
+        ExtensionStatus::Approved
+    );
+}
+
+#[test]
+fn test_donors_can_refund_during_window() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let stayer = donate(&env, &client, project.id, &usdc, 500);
+    let leaver = donate(&env, &client, project.id, &usdc, 500);
+
+    assert_error(
+        client.try_claim_refund(&project.id, &leaver),
+        crate::Error::NotRefundable,
+    );
+
+    client.propose_extension(&project.id, &(project.deadline + 30 * DAY));
+    client.vote_extension(&project.id, &leaver, &false);
+    assert_eq!(client.get_extension(&project.id).object_weight, 500);
+
+    // Refunding withdraws the donor's objection along with their funds.
+    client.claim_refund(&project.id, &leaver);
+    assert_eq!(usdc.balance(&leaver), 500);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 500);
+    assert_eq!(client.get_extension(&project.id).object_weight, 0);
+
+    advance(&env, WINDOW);
+    assert_error(
+        client.try_claim_refund(&project.id, &stayer),
+        crate::Error::NotRefundable,
+    );
+    assert_eq!(
+        client.finalize_extension(&project.id),
+        ExtensionStatus::Approved
+    );
+}
+
+#[test]
+fn test_refund_leaves_nothing_to_withdraw() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let other = register(&env, &client, &admin, &usdc.address);
+    let sponsor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&sponsor, &1_000);
+    let match_id = client.create_match(
+        &sponsor,
+        &project.id,
+        &usdc.address,
+        &10_000,
+        &1_000,
+        &(env.ledger().timestamp() + 60 * DAY),
+    );
+    let donor = donate(&env, &client, project.id, &usdc, 500);
+    donate(&env, &client, other.id, &usdc, 700);
+    assert_eq!(client.get_match(&match_id).remaining, 500);
+
+    client.propose_extension(&project.id, &(project.deadline + 30 * DAY));
+    client.claim_refund(&project.id, &donor);
+
+    // The deposit is still inside its cooling-off window, but the refund
+    // already returned it.
+    assert_error(
+        client.try_withdraw_donation(&project.id, &donor, &usdc.address, &500),
+        crate::Error::CoolingOffExceeded,
+    );
+    assert_eq!(usdc.balance(&donor), 500);
+    assert_eq!(client.get_match(&match_id).remaining, 1_000);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 0);
+    assert_eq!(client.get_balance(&other.id, &usdc.address), 700);
+    assert_eq!(usdc.balance(&client.address), 1_700);
+}
+
+#[test]
+fn test_propose_extension_validation() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let max = client.get_params().max_deadline_duration;
+    let now = env.ledger().timestamp();
+
+    assert_error(
+        client.try_get_extension(&project.id),
+        GovernanceError::ExtensionNotFound,
+    );
+    assert_error(
+        client.try_propose_extension(&project.id, &project.deadline),
+        crate::Error::InvalidDeadline,
+    );
+    assert_error(
+        client.try_propose_extension(&project.id, &(now + max + 1)),
+        crate::Error::InvalidDeadline,
+    );
+
+    client.propose_extension(&project.id, &(now + max));
+    assert_error(
+        client.try_propose_extension(&project.id, &(project.deadline + DAY)),
+        GovernanceError::ExtensionPending,
+    );
+    let stranger = Address::generate(&env);
+    assert_error(
+        client.try_vote_extension(&project.id, &stranger, &true),
+        GovernanceError::NoVotingPower,
+    );
+
+    let late = register(&env, &client, &admin, &usdc.address);
+    env.ledger().set_timestamp(late.deadline);
+    assert_error(
+        client.try_propose_extension(&late.id, &(late.deadline + DAY)),
+        crate::Error::ProjectExpired,
+    );
+}
+
+#[test]
+fn test_cancelled_project_keeps_its_deadline() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donor = donate(&env, &client, project.id, &usdc, 100);
+
+    client.propose_extension(&project.id, &(project.deadline + 30 * DAY));
+    client.vote_extension(&project.id, &donor, &true);
+    client.cancel_project(&admin, &project.id, &BytesN::from_array(&env, &[0u8; 32]));
+
+    advance(&env, WINDOW);
+    assert_eq!(
+        client.finalize_extension(&project.id),
+        ExtensionStatus::Rejected
+    );
+    assert_eq!(client.get_project(&project.id).deadline, project.deadline);
+}
+
+#[test]
+fn test_all_or_nothing_extension_cannot_outlive_the_deadline() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let mut options = ProjectOptions::default_for(&env);
+    options.funding_mode = FundingMode::AllOrNothing;
+    let project = client.register_project_with_options(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &100_000,
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 3 * DAY),
+        &options,
+    );
+    let donor = donate(&env, &client, project.id, &usdc, 100);
+
+    // The window outlasts the deadline, at which refunds open.
+    client.propose_extension(&project.id, &(project.deadline + 30 * DAY));
+    client.vote_extension(&project.id, &donor, &true);
+    advance(&env, WINDOW);
+    assert_eq!(
+        client.finalize_extension(&project.id),
+        ExtensionStatus::Rejected
+    );
+    assert_eq!(client.get_project(&project.id).deadline, project.deadline);
+    client.claim_refund(&project.id, &donor);
+    assert_eq!(usdc.balance(&donor), 100);
+}
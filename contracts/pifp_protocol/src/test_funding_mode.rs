@@ -0,0 +1,202 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Env, TryIntoVal,
+};
+
+use crate::events::ProjectCreated;
+use crate::test_util::{dummy_commitment, dummy_proof, dummy_salt};
+use crate::{
+    FundingMode, PifpProtocol, PifpProtocolClient, Project, ProjectOptions, ProjectStatus,
+};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register_with_mode(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    token: &Address,
+    mode: FundingMode,
+) -> Project {
+    let mut options = ProjectOptions::default_for(env);
+    options.funding_mode = mode;
+    client.register_project_with_options(
+        admin,
+        &vec![env, token.clone()],
+        &1_000,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 86_400),
+        &options,
+    )
+}
+
+fn donate(env: &Env, client: &PifpProtocolClient, project_id: u64, token: &Address, amount: i128) {
+    let donator = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&donator, &amount);
+    client.deposit(&project_id, &donator, token, &amount);
+}
+
+#[test]
+fn test_register_project_defaults_to_flexible() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = client.register_project(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &1_000,
+        &dummy_commitment(&env),
+        &(env.ledger().timestamp() + 86_400),
+    );
+    assert_eq!(project.funding_mode, FundingMode::Flexible);
+}
+
+#[test]
+fn test_mode_is_stored_and_emitted() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register_with_mode(
+        &env,
+        &client,
+        &admin,
+        &usdc.address,
+        FundingMode::AllOrNothing,
+    );
+
+    let last = env.events().all().last().unwrap();
+    let data: ProjectCreated = last.2.try_into_val(&env).unwrap();
+    assert_eq!(data.funding_mode, FundingMode::AllOrNothing);
+    assert_eq!(
+        client.get_project(&project.id).funding_mode,
+        FundingMode::AllOrNothing
+    );
+}
+
+#[test]
+fn test_flexible_releases_partial_funding() {
+    let (env, client, admin) = setup_with_init();
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    let usdc = create_token(&env, &client, &admin);
+    let project = register_with_mode(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+
+    donate(&env, &client, project.id, &usdc.address, 10);
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+    assert_eq!(usdc.balance(&admin), 10);
+}
+
+#[test]
+fn test_all_or_nothing_refuses_release_below_goal() {
+    let (env, client, admin) = setup_with_init();
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    let usdc = create_token(&env, &client, &admin);
+    let project = register_with_mode(
+        &env,
+        &client,
+        &admin,
+        &usdc.address,
+        FundingMode::AllOrNothing,
+    );
+
+    donate(&env, &client, project.id, &usdc.address, 999);
+    let result =
+        client.try_verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+    assert_eq!(
+        result,
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            crate::Error::GoalNotReached as u32
+        )))
+    );
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 999);
+
+    donate(&env, &client, project.id, &usdc.address, 1);
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+    assert_eq!(usdc.balance(&admin), 1_000);
+}
+
+#[test]
+fn test_all_or_nothing_refunds_open_at_deadline() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register_with_mode(
+        &env,
+        &client,
+        &admin,
+        &usdc.address,
+        FundingMode::AllOrNothing,
+    );
+    let donator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&donator, &400);
+    client.deposit(&project.id, &donator, &usdc.address, &400);
+
+    let not_refundable = Err(Ok(soroban_sdk::Error::from_contract_error(
+        crate::Error::NotRefundable as u32,
+    )));
+    assert_eq!(
+        client.try_claim_refund(&project.id, &donator),
+        not_refundable
+    );
+
+    env.ledger().set_timestamp(project.deadline);
+    let refunds = client.claim_refund(&project.id, &donator);
+    assert_eq!(refunds.get(0).unwrap().amount, 400);
+    assert_eq!(usdc.balance(&donator), 400);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 0);
+}
+
+#[test]
+fn test_deadline_does_not_open_refunds_for_flexible_or_funded_projects() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let flexible = register_with_mode(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    let funded = register_with_mode(
+        &env,
+        &client,
+        &admin,
+        &usdc.address,
+        FundingMode::AllOrNothing,
+    );
+    let donator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&donator, &1_400);
+    client.deposit(&flexible.id, &donator, &usdc.address, &400);
+    client.deposit(&funded.id, &donator, &usdc.address, &1_000);
+    assert_eq!(client.get_project(&funded.id).status, ProjectStatus::Active);
+
+    env.ledger().set_timestamp(flexible.deadline);
+    let not_refundable = Err(Ok(soroban_sdk::Error::from_contract_error(
+        crate::Error::NotRefundable as u32,
+    )));
+    assert_eq!(
+        client.try_claim_refund(&flexible.id, &donator),
+        not_refundable
+    );
+    assert_eq!(
+        client.try_claim_refund(&funded.id, &donator),
+        not_refundable
+    );
+}
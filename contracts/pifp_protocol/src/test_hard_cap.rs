@@ -0,0 +1,191 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token, vec, Address, BytesN, Env, Map, TryIntoVal,
+};
+
+use crate::events::ProjectFunded;
+use crate::{
+    ContentId, ExcessPolicy, PifpProtocol, PifpProtocolClient, Project, ProjectOptions,
+    ProjectStatus,
+};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register_capped(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    tokens: &soroban_sdk::Vec<Address>,
+    max_raise: Map<Address, i128>,
+    policy: ExcessPolicy,
+) -> Project {
+    let mut options = ProjectOptions::default_for(env);
+    options.max_raise = max_raise;
+    options.excess_policy = policy;
+    client.register_project_with_options(
+        admin,
+        tokens,
+        &1_000,
+        &ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+        &options,
+    )
+}
+
+fn funded_donator(env: &Env, token: &Address, amount: i128) -> Address {
+    let donator = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&donator, &amount);
+    donator
+}
+
+fn cap_error() -> Option<Result<soroban_sdk::Error, soroban_sdk::InvokeError>> {
+    Some(Ok(soroban_sdk::Error::from_contract_error(
+        crate::Error::CapExceeded as u32,
+    )))
+}
+
+#[test]
+fn test_reject_policy_refuses_excess() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let mut caps = Map::new(&env);
+    caps.set(usdc.address.clone(), 1_500i128);
+    let project = register_capped(
+        &env,
+        &client,
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        caps,
+        ExcessPolicy::Reject,
+    );
+
+    let donator = funded_donator(&env, &usdc.address, 5_000);
+    assert_eq!(
+        client.deposit(&project.id, &donator, &usdc.address, &1_000),
+        1_000
+    );
+    let result = client.try_deposit(&project.id, &donator, &usdc.address, &501);
+    assert_eq!(result.err(), cap_error());
+
+    // Exactly filling the cap is fine; afterwards the token is closed.
+    assert_eq!(
+        client.deposit(&project.id, &donator, &usdc.address, &500),
+        500
+    );
+    let result = client.try_deposit(&project.id, &donator, &usdc.address, &1);
+    assert_eq!(result.err(), cap_error());
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 1_500);
+    assert_eq!(usdc.balance(&donator), 3_500);
+}
+
+#[test]
+fn test_partial_policy_accepts_headroom_only() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let mut caps = Map::new(&env);
+    caps.set(usdc.address.clone(), 1_200i128);
+    let project = register_capped(
+        &env,
+        &client,
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        caps,
+        ExcessPolicy::AcceptPartial,
+    );
+
+    let donator = funded_donator(&env, &usdc.address, 5_000);
+    client.deposit(&project.id, &donator, &usdc.address, &1_000);
+    let accepted = client.deposit(&project.id, &donator, &usdc.address, &1_000);
+    assert_eq!(accepted, 200);
+
+    // The funded event reports the accepted amount.
+    let last = env.events().all().last().unwrap();
+    let data: ProjectFunded = last.2.try_into_val(&env).unwrap();
+    assert_eq!(data.amount, 200);
+
+    assert_eq!(usdc.balance(&donator), 3_800);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 1_200);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+
+    let result = client.try_deposit(&project.id, &donator, &usdc.address, &10);
+    assert_eq!(result.err(), cap_error());
+}
+
+#[test]
+fn test_caps_are_per_token() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let mut caps = Map::new(&env);
+    caps.set(usdc.address.clone(), 1_000i128);
+    let project = register_capped(
+        &env,
+        &client,
+        &admin,
+        &vec![&env, usdc.address.clone(), eurc.address.clone()],
+        caps,
+        ExcessPolicy::Reject,
+    );
+
+    let donator = funded_donator(&env, &usdc.address, 1_000);
+    client.deposit(&project.id, &donator, &usdc.address, &1_000);
+
+    // The uncapped token keeps accepting funds after USDC is full.
+    let donator = funded_donator(&env, &eurc.address, 50_000);
+    assert_eq!(
+        client.deposit(&project.id, &donator, &eurc.address, &50_000),
+        50_000
+    );
+}
+
+#[test]
+fn test_invalid_caps_rejected() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let other = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+
+    // Zero cap, cap below the token's goal, cap on an unaccepted token.
+    let cases = [
+        (usdc.address.clone(), 0i128),
+        (usdc.address.clone(), 999),
+        (other.address.clone(), 5_000),
+    ];
+    for (token, cap) in cases {
+        let mut options = ProjectOptions::default_for(&env);
+        options.max_raise.set(token, cap);
+        let result = client.try_register_project_with_options(
+            &admin,
+            &tokens,
+            &1_000,
+            &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+            &(env.ledger().timestamp() + 86_400),
+            &options,
+        );
+        assert_eq!(
+            result.err(),
+            Some(Ok(soroban_sdk::Error::from_contract_error(
+                crate::Error::InvalidGoal as u32
+            )))
+        );
+    }
+}
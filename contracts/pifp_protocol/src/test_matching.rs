@@ -0,0 +1,289 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Env, Map, TryIntoVal,
+};
+
+use crate::events::DonationMatched;
+use crate::test_util::{assert_error, dummy_commitment, dummy_proof, dummy_salt};
+use crate::{ExcessPolicy, PifpProtocol, PifpProtocolClient, Project, ProjectOptions};
+
+const DAY: u64 = 86_400;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, token: &Address) -> Project {
+    client.register_project(
+        admin,
+        &vec![env, token.clone()],
+        &100_000,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 60 * DAY),
+    )
+}
+
+fn funded(env: &Env, token: &token::Client, amount: i128) -> Address {
+    let who = Address::generate(env);
+    token::StellarAssetClient::new(env, &token.address).mint(&who, &amount);
+    who
+}
+
+fn matched_events(env: &Env) -> std::vec::Vec<DonationMatched> {
+    env.events()
+        .all()
+        .iter()
+        .filter(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("matched").to_val())
+        })
+        .map(|e| e.2.try_into_val(env).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_deposits_are_matched_one_to_one_up_to_cap() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let sponsor = funded(&env, &usdc, 1_000);
+
+    let expires_at = env.ledger().timestamp() + 30 * DAY;
+    let match_id = client.create_match(
+        &sponsor,
+        &project.id,
+        &usdc.address,
+        &10_000,
+        &1_000,
+        &expires_at,
+    );
+    assert_eq!(usdc.balance(&sponsor), 0);
+
+    let donor = funded(&env, &usdc, 1_200);
+    client.deposit(&project.id, &donor, &usdc.address, &300);
+    let events = matched_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].match_id, match_id);
+    assert_eq!(events[0].amount, 300);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 600);
+
+    // Only the remaining 700 of the cap can be matched.
+    client.deposit(&project.id, &donor, &usdc.address, &900);
+    assert_eq!(matched_events(&env)[0].amount, 700);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 2_200);
+
+    let pledge = client.get_match(&match_id);
+    assert_eq!(pledge.remaining, 0);
+    assert_eq!(pledge.matched, 1_000);
+
+    let balances = client.get_project_balances(&project.id);
+    let usdc_balance = balances.balances.get(0).unwrap();
+    assert_eq!(usdc_balance.raised, 2_200);
+    assert_eq!(usdc_balance.matched, 1_000);
+    assert_eq!(usdc_balance.match_available, 0);
+
+    // Matched funds are not part of the donor's contribution.
+    assert_eq!(
+        client
+            .get_contribution(&project.id, &donor)
+            .get(0)
+            .unwrap()
+            .amount,
+        1_200
+    );
+}
+
+#[test]
+fn test_unused_match_is_returned_after_expiry() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let sponsor = funded(&env, &usdc, 1_000);
+    let expires_at = env.ledger().timestamp() + 10 * DAY;
+    let match_id = client.create_match(
+        &sponsor,
+        &project.id,
+        &usdc.address,
+        &5_000,
+        &1_000,
+        &expires_at,
+    );
+
+    let donor = funded(&env, &usdc, 1_000);
+    client.deposit(&project.id, &donor, &usdc.address, &400);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 600);
+    assert_eq!(
+        client
+            .get_project_balances(&project.id)
+            .balances
+            .get(0)
+            .unwrap()
+            .match_available,
+        800
+    );
+
+    assert_error(
+        client.try_release_match(&match_id),
+        crate::Error::MatchActive,
+    );
+
+    env.ledger().set_timestamp(expires_at);
+    client.deposit(&project.id, &donor, &usdc.address, &400);
+    assert!(matched_events(&env).is_empty());
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 1_000);
+
+    assert_eq!(client.release_match(&match_id), 800);
+    assert_eq!(usdc.balance(&sponsor), 800);
+    assert!(client.get_match(&match_id).settled);
+    assert_error(
+        client.try_release_match(&match_id),
+        crate::Error::MatchSettled,
+    );
+}
+
+#[test]
+fn test_match_is_released_once_project_completes() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let sponsor = funded(&env, &usdc, 1_000);
+    let match_id = client.create_match(
+        &sponsor,
+        &project.id,
+        &usdc.address,
+        &10_000,
+        &1_000,
+        &(env.ledger().timestamp() + 30 * DAY),
+    );
+    let donor = funded(&env, &usdc, 100);
+    client.deposit(&project.id, &donor, &usdc.address, &100);
+
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+    assert_eq!(usdc.balance(&admin), 200);
+
+    assert_eq!(client.release_match(&match_id), 900);
+    assert_eq!(usdc.balance(&sponsor), 900);
+}
+
+#[test]
+fn test_withdrawal_hands_match_back_to_pledge() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let sponsor = funded(&env, &usdc, 1_000);
+    let match_id = client.create_match(
+        &sponsor,
+        &project.id,
+        &usdc.address,
+        &10_000,
+        &1_000,
+        &(env.ledger().timestamp() + 30 * DAY),
+    );
+    let donor = funded(&env, &usdc, 500);
+    client.deposit(&project.id, &donor, &usdc.address, &500);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 1_000);
+
+    client.withdraw_donation(&project.id, &donor, &usdc.address, &200);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 600);
+    let pledge = client.get_match(&match_id);
+    assert_eq!(pledge.remaining, 700);
+    assert_eq!(pledge.matched, 300);
+    let balances = client.get_project_balances(&project.id);
+    let usdc_balance = balances.balances.get(0).unwrap();
+    assert_eq!(usdc_balance.raised, 600);
+    assert_eq!(usdc_balance.matched, 300);
+}
+
+#[test]
+fn test_match_respects_max_raise() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let mut options = ProjectOptions::default_for(&env);
+    let mut max_raise = Map::new(&env);
+    max_raise.set(usdc.address.clone(), 1_500i128);
+    options.max_raise = max_raise;
+    options.excess_policy = ExcessPolicy::AcceptPartial;
+    let project = client.register_project_with_options(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &1_000,
+        &dummy_commitment(&env),
+        &(env.ledger().timestamp() + 60 * DAY),
+        &options,
+    );
+    let sponsor = funded(&env, &usdc, 1_000);
+    let match_id = client.create_match(
+        &sponsor,
+        &project.id,
+        &usdc.address,
+        &10_000,
+        &1_000,
+        &(env.ledger().timestamp() + 30 * DAY),
+    );
+
+    let donor = funded(&env, &usdc, 1_000);
+    client.deposit(&project.id, &donor, &usdc.address, &1_000);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 1_500);
+    assert_eq!(client.get_match(&match_id).matched, 500);
+}
+
+#[test]
+fn test_create_match_validation() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let sponsor = funded(&env, &usdc, 100_000);
+    let later = env.ledger().timestamp() + DAY;
+
+    assert_error(
+        client.try_create_match(&sponsor, &project.id, &usdc.address, &0, &100, &later),
+        crate::Error::InvalidMatch,
+    );
+    assert_error(
+        client.try_create_match(&sponsor, &project.id, &usdc.address, &10_000, &0, &later),
+        crate::Error::InvalidMatch,
+    );
+    assert_error(
+        client.try_create_match(
+            &sponsor,
+            &project.id,
+            &usdc.address,
+            &10_000,
+            &100,
+            &env.ledger().timestamp(),
+        ),
+        crate::Error::InvalidMatch,
+    );
+    assert_error(
+        client.try_create_match(&sponsor, &project.id, &eurc.address, &10_000, &100, &later),
+        crate::Error::TokenNotAccepted,
+    );
+    assert_error(client.try_get_match(&0), crate::Error::MatchNotFound);
+
+    for _ in 0..crate::MAX_PROJECT_MATCHES {
+        client.create_match(&sponsor, &project.id, &usdc.address, &10_000, &100, &later);
+    }
+    assert_error(
+        client.try_create_match(&sponsor, &project.id, &usdc.address, &10_000, &100, &later),
+        crate::Error::TooManyMatches,
+    );
+}
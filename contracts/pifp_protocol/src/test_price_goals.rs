@@ -0,0 +1,355 @@
+extern crate std;
+
+use mock_price_feed::{Asset, MockPriceFeed, MockPriceFeedClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, Vec,
+};
+
+use crate::{
+    ContentId, FundingMode, GoalRule, PifpProtocol, PifpProtocolClient, Project, ProjectOptions,
+    ProjectStatus, QuoteGoal, TokenMetadata,
+};
+
+/// Prices are quoted with 14 decimals, as Reflector does.
+const ONE_DOLLAR: i128 = 100_000_000_000_000;
+const MAX_PRICE_AGE: u64 = 300;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    decimals: u32,
+) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    let metadata = TokenMetadata {
+        symbol: symbol_short!("TKN"),
+        decimals,
+        min_deposit: 0,
+    };
+    client.allow_token(admin, &addr.address(), &Some(metadata));
+    token::Client::new(env, &addr.address())
+}
+
+fn create_feed<'a>(env: &Env) -> MockPriceFeedClient<'a> {
+    let feed = MockPriceFeedClient::new(env, &env.register(MockPriceFeed, ()));
+    feed.set_decimals(&14);
+    feed
+}
+
+fn set_price(env: &Env, feed: &MockPriceFeedClient, token: &Address, price: i128) {
+    feed.set_price(
+        &Asset::Stellar(token.clone()),
+        &price,
+        &env.ledger().timestamp(),
+    );
+}
+
+fn register_quoted(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    tokens: &Vec<Address>,
+    goal: i128,
+    feed: &Address,
+) -> Project {
+    let mut options = ProjectOptions::default_for(env);
+    options.goal_rule = GoalRule::QuoteValue(QuoteGoal {
+        feed: feed.clone(),
+        max_price_age: MAX_PRICE_AGE,
+        decimals: 14,
+    });
+    client.register_project_with_options(
+        admin,
+        tokens,
+        &goal,
+        &ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+        &options,
+    )
+}
+
+fn donate(env: &Env, client: &PifpProtocolClient, project_id: u64, token: &Address, amount: i128) {
+    let donator = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&donator, &amount);
+    client.deposit(&project_id, &donator, token, &amount);
+}
+
+#[test]
+fn test_quote_goal_sums_tokens_at_current_prices() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin, 7);
+    let xlm = create_token(&env, &client, &admin, 7);
+    let feed = create_feed(&env);
+    set_price(&env, &feed, &usdc.address, ONE_DOLLAR);
+    set_price(&env, &feed, &xlm.address, ONE_DOLLAR / 10);
+
+    // Goal: $1,000.
+    let tokens = vec![&env, usdc.address.clone(), xlm.address.clone()];
+    let project = register_quoted(
+        &env,
+        &client,
+        &admin,
+        &tokens,
+        1_000 * ONE_DOLLAR,
+        &feed.address,
+    );
+    assert!(project.token_goals.is_empty());
+
+    // $600 in USDC + $390 in XLM.
+    donate(&env, &client, project.id, &usdc.address, 600 * 10_000_000);
+    donate(&env, &client, project.id, &xlm.address, 3_900 * 10_000_000);
+    let progress = client.get_project_balances(&project.id);
+    assert_eq!(progress.quote_value, 990 * ONE_DOLLAR);
+    assert!(!progress.goal_reached);
+    assert!(progress.balances.iter().all(|b| b.goal == 0));
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+
+    // XLM rallies: the same escrow is now worth $1,380.
+    set_price(&env, &feed, &xlm.address, ONE_DOLLAR / 5);
+    assert!(client.get_project_balances(&project.id).goal_reached);
+    donate(&env, &client, project.id, &usdc.address, 1);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+}
+
+#[test]
+fn test_quote_goal_does_not_bind_first_token() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin, 7);
+    let xlm = create_token(&env, &client, &admin, 7);
+    let feed = create_feed(&env);
+
+    // A USDC cap far below the quote-unit goal is still a valid cap.
+    let mut options = ProjectOptions::default_for(&env);
+    options.goal_rule = GoalRule::QuoteValue(QuoteGoal {
+        feed: feed.address.clone(),
+        max_price_age: MAX_PRICE_AGE,
+        decimals: 14,
+    });
+    options
+        .max_raise
+        .set(usdc.address.clone(), 500 * 10_000_000);
+    let project = client.register_project_with_options(
+        &admin,
+        &vec![&env, usdc.address.clone(), xlm.address.clone()],
+        &(1_000 * ONE_DOLLAR),
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+        &options,
+    );
+
+    // Nor does the goal pin USDC to the project.
+    client.remove_accepted_token(&project.id, &usdc.address);
+    assert_eq!(
+        client.get_project(&project.id).accepted_tokens,
+        vec![&env, xlm.address.clone()]
+    );
+}
+
+#[test]
+fn test_quote_goal_scales_by_token_decimals() {
+    let (env, client, admin) = setup_with_init();
+    let six = create_token(&env, &client, &admin, 6);
+    let feed = create_feed(&env);
+    set_price(&env, &feed, &six.address, 2 * ONE_DOLLAR);
+
+    let tokens = vec![&env, six.address.clone()];
+    let project = register_quoted(
+        &env,
+        &client,
+        &admin,
+        &tokens,
+        100 * ONE_DOLLAR,
+        &feed.address,
+    );
+
+    // 50 whole tokens at $2 each.
+    donate(&env, &client, project.id, &six.address, 50 * 1_000_000);
+    assert_eq!(
+        client.get_project_balances(&project.id).quote_value,
+        100 * ONE_DOLLAR
+    );
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+}
+
+#[test]
+fn test_stale_or_missing_prices_count_as_zero() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin, 7);
+    let xlm = create_token(&env, &client, &admin, 7);
+    let feed = create_feed(&env);
+    set_price(&env, &feed, &usdc.address, ONE_DOLLAR);
+
+    let tokens = vec![&env, usdc.address.clone(), xlm.address.clone()];
+    let project = register_quoted(
+        &env,
+        &client,
+        &admin,
+        &tokens,
+        100 * ONE_DOLLAR,
+        &feed.address,
+    );
+
+    // No XLM price: the deposit is accepted but adds no value.
+    donate(&env, &client, project.id, &xlm.address, 10_000 * 10_000_000);
+    assert_eq!(client.get_project_balances(&project.id).quote_value, 0);
+
+    // Let the USDC price go stale before the deposit that would meet the goal.
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_PRICE_AGE + 1);
+    donate(&env, &client, project.id, &usdc.address, 100 * 10_000_000);
+    let progress = client.get_project_balances(&project.id);
+    assert_eq!(progress.quote_value, 0);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+
+    // A fresh price brings it back into the goal check.
+    set_price(&env, &feed, &usdc.address, ONE_DOLLAR);
+    donate(&env, &client, project.id, &usdc.address, 1);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_zero_max_price_age_rejected() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin, 7);
+    let feed = create_feed(&env);
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.goal_rule = GoalRule::QuoteValue(QuoteGoal {
+        feed: feed.address.clone(),
+        max_price_age: 0,
+        decimals: 14,
+    });
+    client.register_project_with_options(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &ONE_DOLLAR,
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+        &options,
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_goal_decimals_must_match_feed() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin, 7);
+    let feed = create_feed(&env);
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.goal_rule = GoalRule::QuoteValue(QuoteGoal {
+        feed: feed.address.clone(),
+        max_price_age: MAX_PRICE_AGE,
+        decimals: 7,
+    });
+    client.register_project_with_options(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &(100 * 10_000_000),
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+        &options,
+    );
+}
+
+#[test]
+fn test_future_prices_are_ignored() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin, 7);
+    let feed = create_feed(&env);
+    let tokens = vec![&env, usdc.address.clone()];
+    let project = register_quoted(
+        &env,
+        &client,
+        &admin,
+        &tokens,
+        100 * ONE_DOLLAR,
+        &feed.address,
+    );
+
+    // A price stamped ahead of the ledger would otherwise never go stale.
+    feed.set_price(
+        &Asset::Stellar(usdc.address.clone()),
+        &ONE_DOLLAR,
+        &(env.ledger().timestamp() + 3_600),
+    );
+    donate(&env, &client, project.id, &usdc.address, 100 * 10_000_000);
+    assert_eq!(client.get_project_balances(&project.id).quote_value, 0);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+}
+
+#[test]
+fn test_price_rise_reaches_goal_without_a_deposit() {
+    let (env, client, admin) = setup_with_init();
+    let xlm = create_token(&env, &client, &admin, 7);
+    let feed = create_feed(&env);
+    set_price(&env, &feed, &xlm.address, ONE_DOLLAR / 10);
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.funding_mode = FundingMode::AllOrNothing;
+    options.goal_rule = GoalRule::QuoteValue(QuoteGoal {
+        feed: feed.address.clone(),
+        max_price_age: MAX_PRICE_AGE,
+        decimals: 14,
+    });
+    let project = client.register_project_with_options(
+        &admin,
+        &vec![&env, xlm.address.clone()],
+        &(100 * ONE_DOLLAR),
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+        &options,
+    );
+    let donor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm.address).mint(&donor, &(500 * 10_000_000));
+    client.deposit(&project.id, &donor, &xlm.address, &(500 * 10_000_000));
+    assert!(!client.refresh_goal(&project.id));
+
+    // $50 of XLM becomes $100 by the deadline with no further deposit.
+    env.ledger().set_timestamp(project.deadline);
+    set_price(&env, &feed, &xlm.address, ONE_DOLLAR / 5);
+    assert_eq!(
+        client.try_claim_refund(&project.id, &donor),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            crate::Error::NotRefundable as u32
+        )))
+    );
+    assert!(client.refresh_goal(&project.id));
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+    assert!(!client.refresh_goal(&project.id));
+}
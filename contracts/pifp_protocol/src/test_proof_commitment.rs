@@ -0,0 +1,194 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, vec, Address, Bytes, BytesN, Env};
+
+use crate::storage::DataKey;
+use crate::types::{LegacyProjectConfig, LegacyProjectState};
+use crate::{
+    proofs, ContentId, GovernanceError, PifpProtocol, PifpProtocolClient, Project, ProjectStatus,
+};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    let oracle = Address::generate(&env);
+    client.set_oracle(&super_admin, &oracle);
+    (env, client, super_admin, oracle)
+}
+
+fn hash(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+fn sha2_256(env: &Env, byte: u8) -> ContentId {
+    ContentId::sha2_256(hash(env, byte))
+}
+
+fn commit_proof(env: &Env, proof: &ContentId, salt: &BytesN<32>) -> ContentId {
+    let mut preimage = proof.digest.clone();
+    preimage.append(&Bytes::from_array(env, &salt.to_array()));
+    let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+    ContentId {
+        code: proof.code,
+        digest: commitment.into(),
+    }
+}
+
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, proof: &ContentId) -> Project {
+    let token = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &token.address(), &None);
+    client.register_project(
+        admin,
+        &vec![env, token.address()],
+        &1_000,
+        proof,
+        &(env.ledger().timestamp() + 86_400),
+    )
+}
+
+#[test]
+fn test_verification_requires_preimage_and_salt() {
+    let (env, client, admin, oracle) = setup_with_init();
+    let proof = sha2_256(&env, 0xab);
+    let salt = hash(&env, 0x5a);
+    let commitment = commit_proof(&env, &proof, &salt);
+    let project = register(&env, &client, &admin, &commitment);
+
+    // Only the commitment is readable on-chain.
+    let stored = client.get_project(&project.id);
+    assert_eq!(stored.proof, commitment);
+    assert!(stored.proof_salted);
+
+    assert_eq!(
+        client.try_verify_and_release(&oracle, &project.id, &commitment, &salt),
+        Err(Ok(crate::Error::VerificationFailed.into()))
+    );
+    assert_eq!(
+        client.try_verify_and_release(&oracle, &project.id, &proof, &hash(&env, 0)),
+        Err(Ok(crate::Error::VerificationFailed.into()))
+    );
+
+    client.verify_and_release(&oracle, &project.id, &proof, &salt);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+fn test_project_without_commitment_verifies_plain_hash() {
+    let (env, client, admin, oracle) = setup_with_init();
+    let token = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(&admin, &token.address(), &None);
+
+    // Project 0 exactly as the contract stored it before commitments: the
+    // baseline config holds the artifact digest itself.
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(
+            &DataKey::ProjConfig(0),
+            &LegacyProjectConfig {
+                id: 0,
+                creator: admin.clone(),
+                accepted_tokens: vec![&env, token.address()],
+                goal: 1_000,
+                proof_hash: hash(&env, 0xab),
+                deadline: env.ledger().timestamp() + 86_400,
+            },
+        );
+        storage.set(
+            &DataKey::ProjState(0),
+            &LegacyProjectState {
+                status: ProjectStatus::Funding,
+                donation_count: 0,
+            },
+        );
+        storage.set(&DataKey::TokenBalance(0, token.address()), &0i128);
+        env.storage().instance().set(&DataKey::ProjectCount, &1u64);
+    });
+    let project = client.get_project(&0);
+    assert!(!project.proof_salted);
+    assert_eq!(project.proof, sha2_256(&env, 0xab));
+
+    assert_eq!(
+        client.try_verify_and_release(&oracle, &0, &sha2_256(&env, 1), &hash(&env, 0)),
+        Err(Ok(crate::Error::VerificationFailed.into()))
+    );
+    client.verify_and_release(&oracle, &0, &sha2_256(&env, 0xab), &hash(&env, 0));
+    assert_eq!(client.get_project(&0).status, ProjectStatus::Completed);
+}
+
+#[test]
+fn test_register_rejects_unknown_content_ids() {
+    let (env, client, admin, _) = setup_with_init();
+    let token = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(&admin, &token.address(), &None);
+    let deadline = env.ledger().timestamp() + 86_400;
+
+    let unknown = ContentId {
+        code: 0x99,
+        digest: Bytes::from_array(&env, &[1u8; 32]),
+    };
+    let short = ContentId {
+        code: proofs::SHA2_256,
+        digest: Bytes::from_array(&env, &[1u8; 20]),
+    };
+    for id in [unknown, short] {
+        assert_eq!(
+            client.try_register_project(
+                &admin,
+                &vec![&env, token.address()],
+                &1_000,
+                &id,
+                &deadline
+            ),
+            Err(Ok(soroban_sdk::Error::from_contract_error(
+                GovernanceError::InvalidContentId as u32
+            )))
+        );
+    }
+}
+
+#[test]
+fn test_blake3_proof_verifies_under_its_own_code() {
+    let (env, client, admin, oracle) = setup_with_init();
+    let salt = hash(&env, 0x5a);
+    let proof = ContentId {
+        code: proofs::BLAKE3,
+        digest: Bytes::from_array(&env, &[0xcd; 48]),
+    };
+    let project = register(&env, &client, &admin, &commit_proof(&env, &proof, &salt));
+    assert_eq!(client.get_project(&project.id).proof.code, proofs::BLAKE3);
+
+    // Same digest bytes under another algorithm are a different artifact.
+    let other = ContentId {
+        code: proofs::SHA2_512,
+        digest: Bytes::from_array(&env, &[0xcd; 64]),
+    };
+    assert_eq!(
+        client.try_verify_and_release(&oracle, &project.id, &other, &salt),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            GovernanceError::ProofAlgorithmMismatch as u32
+        )))
+    );
+    let oversized = ContentId {
+        code: proofs::BLAKE3,
+        digest: Bytes::from_array(&env, &[0xcd; 65]),
+    };
+    assert_eq!(
+        client.try_verify_and_release(&oracle, &project.id, &oversized, &salt),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            GovernanceError::InvalidContentId as u32
+        )))
+    );
+
+    client.verify_and_release(&oracle, &project.id, &proof, &salt);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
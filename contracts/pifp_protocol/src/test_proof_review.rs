@@ -0,0 +1,258 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, BytesN, Env, TryIntoVal,
+};
+
+use crate::events::{ProofAccepted, ProofRejected};
+use crate::test_util::{assert_error, dummy_commitment, dummy_proof, dummy_salt};
+use crate::{
+    ContentId, FundingMode, GovernanceError, PifpProtocol, PifpProtocolClient, Project,
+    ProjectOptions, ProjectStatus, ProofReview, VerificationMode,
+};
+
+const DAY: u64 = 86_400;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    let oracle = Address::generate(&env);
+    client.set_oracle(&super_admin, &oracle);
+    (env, client, super_admin, oracle)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn hash(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+fn content_id(env: &Env, byte: u8) -> ContentId {
+    ContentId::sha2_256(hash(env, byte))
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    token: &Address,
+    funding_mode: FundingMode,
+) -> Project {
+    let mut options = ProjectOptions::default_for(env);
+    options.funding_mode = funding_mode;
+    client.register_project_with_options(
+        admin,
+        &vec![env, token.clone()],
+        &1_000,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 30 * DAY),
+        &options,
+    )
+}
+
+fn donate(
+    env: &Env,
+    client: &PifpProtocolClient,
+    project_id: u64,
+    token: &token::Client,
+    amount: i128,
+) -> Address {
+    let donor = Address::generate(env);
+    token::StellarAssetClient::new(env, &token.address).mint(&donor, &amount);
+    client.deposit(&project_id, &donor, &token.address, &amount);
+    donor
+}
+
+#[test]
+fn test_submitted_proof_is_accepted_on_verification() {
+    let (env, client, admin, oracle) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    donate(&env, &client, project.id, &usdc, 400);
+
+    env.ledger().set_timestamp(1_000);
+    let submission = client.submit_proof(&project.id, &content_id(&env, 1), &hash(&env, 2));
+    assert_eq!(submission.submitted_at, 1_000);
+    assert_eq!(submission.prior_status, ProjectStatus::Funding);
+
+    let stored = client.get_project(&project.id);
+    assert_eq!(stored.status, ProjectStatus::UnderReview);
+    assert_eq!(
+        stored.proof_review,
+        ProofReview::Pending(submission.clone())
+    );
+
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+    let accepted: ProofAccepted = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("proof_ok").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(accepted.oracle, oracle);
+    assert_eq!(accepted.submission, submission);
+
+    let stored = client.get_project(&project.id);
+    assert_eq!(stored.status, ProjectStatus::Completed);
+    assert_eq!(stored.proof_review, ProofReview::Accepted(submission));
+    assert_eq!(usdc.balance(&admin), 400);
+}
+
+#[test]
+fn test_rejected_proof_returns_project_and_can_be_resubmitted() {
+    let (env, client, admin, oracle) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(
+        &env,
+        &client,
+        &admin,
+        &usdc.address,
+        FundingMode::AllOrNothing,
+    );
+    donate(&env, &client, project.id, &usdc, 1_000);
+
+    let first = client.submit_proof(&project.id, &content_id(&env, 1), &hash(&env, 2));
+    assert_eq!(first.prior_status, ProjectStatus::Active);
+
+    client.reject_proof(&oracle, &project.id, &hash(&env, 9));
+    let rejected: ProofRejected = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("proof_rej").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(rejected.reason_hash, hash(&env, 9));
+
+    let stored = client.get_project(&project.id);
+    assert_eq!(stored.status, ProjectStatus::Active);
+    assert_eq!(
+        stored.proof_review,
+        ProofReview::Rejected(first, hash(&env, 9))
+    );
+    assert_error(
+        client.try_reject_proof(&oracle, &project.id, &hash(&env, 9)),
+        GovernanceError::NotUnderReview,
+    );
+
+    let second = client.submit_proof(&project.id, &content_id(&env, 3), &hash(&env, 4));
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+    assert_eq!(
+        client.get_project(&project.id).proof_review,
+        ProofReview::Accepted(second)
+    );
+}
+
+#[test]
+fn test_deposits_are_closed_during_review() {
+    let (env, client, admin, _oracle) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    donate(&env, &client, project.id, &usdc, 100);
+
+    client.submit_proof(&project.id, &content_id(&env, 1), &hash(&env, 2));
+    let late = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&late, &100);
+    assert_error(
+        client.try_deposit(&project.id, &late, &usdc.address, &100),
+        crate::Error::ProjectNotActive,
+    );
+    assert_error(
+        client.try_submit_proof(&project.id, &content_id(&env, 1), &hash(&env, 2)),
+        crate::Error::ProjectNotActive,
+    );
+}
+
+#[test]
+fn test_submit_proof_validation() {
+    let (env, client, admin, _oracle) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+
+    let all_or_nothing = register(
+        &env,
+        &client,
+        &admin,
+        &usdc.address,
+        FundingMode::AllOrNothing,
+    );
+    assert_error(
+        client.try_submit_proof(&all_or_nothing.id, &content_id(&env, 1), &hash(&env, 2)),
+        crate::Error::GoalNotReached,
+    );
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.verification_mode = VerificationMode::DonorVote;
+    let donor_vote = client.register_project_with_options(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &1_000,
+        &dummy_commitment(&env),
+        &(env.ledger().timestamp() + 30 * DAY),
+        &options,
+    );
+    assert_error(
+        client.try_submit_proof(&donor_vote.id, &content_id(&env, 1), &hash(&env, 2)),
+        GovernanceError::WrongVerificationMode,
+    );
+
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    assert_eq!(
+        client.get_project(&project.id).proof_review,
+        ProofReview::None
+    );
+    // A SHA2-256 code must carry a 32-byte digest.
+    let truncated = ContentId {
+        code: crate::proofs::SHA2_256,
+        digest: soroban_sdk::Bytes::from_array(&env, &[1u8; 20]),
+    };
+    assert_error(
+        client.try_submit_proof(&project.id, &truncated, &hash(&env, 2)),
+        GovernanceError::InvalidContentId,
+    );
+    client.submit_proof(&project.id, &content_id(&env, 1), &hash(&env, 2));
+    let stranger = Address::generate(&env);
+    assert_error(
+        client.try_reject_proof(&stranger, &project.id, &hash(&env, 9)),
+        crate::Error::NotAuthorized,
+    );
+}
+
+#[test]
+fn test_cancelling_during_review_closes_submission() {
+    let (env, client, admin, oracle) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    let donor = donate(&env, &client, project.id, &usdc, 100);
+
+    let submission = client.submit_proof(&project.id, &content_id(&env, 1), &hash(&env, 2));
+    client.cancel_project(&admin, &project.id, &hash(&env, 7));
+    assert_eq!(
+        client.get_project(&project.id).proof_review,
+        ProofReview::Rejected(submission, hash(&env, 7))
+    );
+    assert_error(
+        client.try_verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env)),
+        crate::Error::ProjectNotActive,
+    );
+    client.claim_refund(&project.id, &donor);
+    assert_eq!(usdc.balance(&donor), 100);
+}
@@ -0,0 +1,327 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{storage::Persistent as _, Address as _, Events},
+    token, vec, Address, BytesN, Env, IntoVal, Map, Symbol, TryIntoVal, Val, Vec,
+};
+
+use crate::storage::DataKey;
+use crate::test_util::dummy_proof;
+use crate::types::{LegacyProjectConfig, LegacyProjectState};
+use crate::{
+    ContentId, FundingMode, GoalRule, PifpProtocol, PifpProtocolClient, ProjectStatus,
+    ProtocolParams, Role,
+};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn allowed_tokens(env: &Env, client: &PifpProtocolClient, admin: &Address, n: u32) -> Vec<Address> {
+    let mut tokens = Vec::new(env);
+    for _ in 0..n {
+        let token = Address::generate(env);
+        client.allow_token(admin, &token, &None);
+        tokens.push_back(token);
+    }
+    tokens
+}
+
+#[test]
+fn test_default_params_match_legacy_limits() {
+    let (_env, client, _admin) = setup_with_init();
+    let params = client.get_params();
+    assert_eq!(params.max_accepted_tokens, 10);
+    assert_eq!(
+        params.max_goal,
+        1_000_000_000_000_000_000_000_000_000_000i128
+    );
+    assert_eq!(params.min_deadline_duration, 0);
+    assert_eq!(params.max_deadline_duration, 157_680_000);
+    assert_eq!(params.instance_bump_amount, 7 * 17_280);
+    assert_eq!(params.persistent_bump_amount, 30 * 17_280);
+    assert_eq!(params.cooling_off_period, 86_400);
+    assert_eq!(params.extension_window, 7 * 86_400);
+    assert_eq!(params.extension_objection_bps, 2_000);
+    assert_eq!(params.release_vote_window, 7 * 86_400);
+    assert_eq!(params.release_quorum_bps, 2_000);
+    assert_eq!(params.release_majority_bps, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_non_admin_cannot_set_params() {
+    let (env, client, admin) = setup_with_init();
+    let pm = Address::generate(&env);
+    client.grant_role(&admin, &pm, &Role::ProjectManager);
+    client.set_params(&pm, &client.get_params());
+}
+
+#[test]
+fn test_invalid_params_rejected() {
+    let (_env, client, admin) = setup_with_init();
+    let base = client.get_params();
+    let cases: [fn(&mut ProtocolParams); 13] = [
+        |p| p.max_accepted_tokens = 0,
+        |p| p.max_accepted_tokens = 21,
+        |p| p.max_goal = 0,
+        |p| p.min_deadline_duration = p.max_deadline_duration,
+        |p| p.instance_lifetime_threshold = p.instance_bump_amount + 1,
+        |p| p.persistent_bump_amount = 0,
+        |p| p.cooling_off_period = p.max_deadline_duration + 1,
+        |p| p.extension_window = 0,
+        |p| p.extension_objection_bps = 10_001,
+        |p| p.release_vote_window = 0,
+        |p| p.release_quorum_bps = 10_001,
+        |p| p.release_majority_bps = 4_999,
+        |p| p.release_majority_bps = 10_000,
+    ];
+    for mutate in cases {
+        let mut params = base.clone();
+        mutate(&mut params);
+        assert!(client.try_set_params(&admin, &params).is_err());
+    }
+    assert_eq!(client.get_params(), base);
+}
+
+#[test]
+fn test_set_params_applies_to_registration() {
+    let (env, client, admin) = setup_with_init();
+    let mut params = client.get_params();
+    params.max_accepted_tokens = 2;
+    params.max_goal = 5_000;
+    params.min_deadline_duration = 3_600;
+    params.max_deadline_duration = 86_400;
+    client.set_params(&admin, &params);
+    assert_eq!(client.get_params(), params);
+
+    let now = env.ledger().timestamp();
+    let two = allowed_tokens(&env, &client, &admin, 2);
+    let three = allowed_tokens(&env, &client, &admin, 3);
+
+    assert!(client
+        .try_register_project(&admin, &three, &1_000, &dummy_proof(&env), &(now + 7_200))
+        .is_err());
+    assert!(client
+        .try_register_project(&admin, &two, &5_001, &dummy_proof(&env), &(now + 7_200))
+        .is_err());
+    assert!(client
+        .try_register_project(&admin, &two, &1_000, &dummy_proof(&env), &(now + 3_600))
+        .is_err());
+    assert!(client
+        .try_register_project(&admin, &two, &1_000, &dummy_proof(&env), &(now + 86_401))
+        .is_err());
+
+    let project =
+        client.register_project(&admin, &two, &5_000, &dummy_proof(&env), &(now + 86_400));
+    assert_eq!(project.goal, 5_000);
+}
+
+#[test]
+fn test_existing_projects_keep_their_params_version() {
+    let (env, client, admin) = setup_with_init();
+    let tokens = allowed_tokens(&env, &client, &admin, 1);
+    let deadline = env.ledger().timestamp() + 86_400;
+
+    let legacy = client.register_project(&admin, &tokens, &1_000, &dummy_proof(&env), &deadline);
+
+    let mut params = client.get_params();
+    params.max_accepted_tokens = 1;
+    client.set_params(&admin, &params);
+    let current = client.register_project(&admin, &tokens, &1_000, &dummy_proof(&env), &deadline);
+
+    env.as_contract(&client.address, || {
+        let legacy_cfg = crate::storage::load_project_config(&env, legacy.id);
+        let current_cfg = crate::storage::load_project_config(&env, current.id);
+        assert_eq!(legacy_cfg.params_version, 0);
+        assert_eq!(current_cfg.params_version, 1);
+        assert_eq!(
+            crate::storage::get_params_at(&env, legacy_cfg.params_version).max_accepted_tokens,
+            10
+        );
+        assert_eq!(
+            crate::storage::get_params_at(&env, current_cfg.params_version),
+            params
+        );
+    });
+}
+
+#[test]
+fn test_set_params_emits_event() {
+    let (env, client, admin) = setup_with_init();
+    let mut params = client.get_params();
+    params.max_goal = 42;
+    client.set_params(&admin, &params);
+
+    let last = env.events().all().last().unwrap();
+    assert_eq!(last.0, client.address);
+    assert_eq!(
+        last.1,
+        vec![
+            &env,
+            symbol_short!("params").into_val(&env),
+            1u32.into_val(&env),
+            admin.into_val(&env),
+        ]
+    );
+    let data: ProtocolParams = last.2.try_into_val(&env).unwrap();
+    assert_eq!(data, params);
+}
+
+#[test]
+fn test_ttl_bumps_follow_set_params() {
+    let (env, client, admin) = setup_with_init();
+    let forty_days = 40 * 17_280;
+    let mut params = client.get_params();
+    params.persistent_lifetime_threshold = forty_days;
+    params.persistent_bump_amount = forty_days;
+    client.set_params(&admin, &params);
+
+    let token = allowed_tokens(&env, &client, &admin, 1);
+    let register = || {
+        client.register_project(
+            &admin,
+            &token,
+            &1_000,
+            &dummy_proof(&env),
+            &(env.ledger().timestamp() + 86_400),
+        )
+    };
+    let first = register();
+    env.as_contract(&client.address, || {
+        let storage = env.storage();
+        let key = DataKey::ProjConfigV2(first.id);
+        assert_eq!(storage.persistent().get_ttl(&key), forty_days);
+
+        // Parameters set before the TTL keys existed are still honoured.
+        let instance = storage.instance();
+        assert_eq!(
+            instance.get::<_, (u32, u32)>(&DataKey::PersistentTtl),
+            Some((forty_days, forty_days))
+        );
+        instance.remove(&DataKey::PersistentTtl);
+    });
+    let second = register();
+    env.as_contract(&client.address, || {
+        let key = DataKey::ProjConfigV2(second.id);
+        assert_eq!(env.storage().persistent().get_ttl(&key), forty_days);
+    });
+}
+
+#[test]
+fn test_params_stored_before_new_fields_read_defaults() {
+    let (env, client, admin) = setup_with_init();
+    let mut params = client.get_params();
+    params.max_goal = 5_000;
+    client.set_params(&admin, &params);
+
+    // Rewrite version 1 as stored before cooling-off and donor votes existed.
+    env.as_contract(&client.address, || {
+        let full: Val = params.into_val(&env);
+        let mut fields: Map<Symbol, Val> = full.try_into_val(&env).unwrap();
+        for field in [
+            "cooling_off_period",
+            "extension_window",
+            "extension_objection_bps",
+            "release_vote_window",
+            "release_quorum_bps",
+            "release_majority_bps",
+        ] {
+            fields.remove(Symbol::new(&env, field));
+        }
+        env.storage().instance().set(&DataKey::Params, &fields);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ParamsAt(1), &fields);
+    });
+
+    let read = client.get_params();
+    assert_eq!(read.max_goal, 5_000);
+    assert_eq!(read.cooling_off_period, 86_400);
+    assert_eq!(read.release_majority_bps, 5_000);
+
+    // Projects registered under version 1 keep working through its history entry.
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.allow_token(&admin, &token, &None);
+    let project = client.register_project(
+        &admin,
+        &vec![&env, token.clone()],
+        &1_000,
+        &dummy_proof(&env),
+        &(env.ledger().timestamp() + 86_400),
+    );
+    let donor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&donor, &100);
+    client.deposit(&project.id, &donor, &token, &100);
+    client.withdraw_donation(&project.id, &donor, &token, &100);
+    assert_eq!(client.get_balance(&project.id, &token), 0);
+}
+
+#[test]
+fn test_legacy_project_config_is_migrated_on_read() {
+    let (env, client, admin) = setup_with_init();
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.allow_token(&admin, &token, &None);
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xcdu8; 32]);
+    let deadline = env.ledger().timestamp() + 86_400;
+
+    // Store project 0 exactly as the contract did before per-project options.
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(
+            &DataKey::ProjConfig(0),
+            &LegacyProjectConfig {
+                id: 0,
+                creator: creator.clone(),
+                accepted_tokens: vec![&env, token.clone()],
+                goal: 1_000,
+                proof_hash: proof_hash.clone(),
+                deadline,
+            },
+        );
+        storage.set(
+            &DataKey::ProjState(0),
+            &LegacyProjectState {
+                status: ProjectStatus::Funding,
+                donation_count: 0,
+            },
+        );
+        storage.set(&DataKey::TokenBalance(0, token.clone()), &0i128);
+        env.storage().instance().set(&DataKey::ProjectCount, &1u64);
+    });
+
+    let project = client.get_project(&0);
+    assert_eq!(project.creator, creator);
+    assert_eq!(project.accepted_tokens, vec![&env, token.clone()]);
+    assert_eq!(project.token_goals.get(token.clone()), Some(1_000));
+    assert_eq!(project.goal_rule, GoalRule::AnyToken);
+    assert_eq!(project.funding_mode, FundingMode::Flexible);
+    assert!(project.max_raise.is_empty());
+    assert_eq!(project.proof, ContentId::sha2_256(proof_hash));
+    assert!(!project.proof_salted);
+    assert_eq!(project.deadline, deadline);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::ProjConfig(0)));
+        assert!(storage.has(&DataKey::ProjConfigV2(0)));
+        assert!(storage.has(&DataKey::ProjTokens(0)));
+    });
+
+    // The migrated project keeps taking deposits towards its original goal.
+    let donor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&donor, &1_000);
+    client.deposit(&0, &donor, &token, &1_000);
+    assert_eq!(client.get_project(&0).status, ProjectStatus::Active);
+}
@@ -0,0 +1,451 @@
+extern crate std;
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, testutils::Address as _, Address, Bytes, BytesN, Env,
+};
+
+use crate::{storage, ContentId, PifpProtocol, PifpProtocolClient, ProjectStatus, Role};
+
+// ─── Adversarial token ───────────────────────────────────
+//
+// A minimal token that keeps its own balances and, on every `transfer`,
+// either tries to call back into the protocol or records the protocol's
+// storage as it stands mid-transfer.
+//
+// The Soroban host already refuses any contract re-entry, so the callback
+// attacks only show that a nested call cannot succeed; they pass with or
+// without `ReentrancyLock`. The `Inspect` snapshots check what the protocol
+// itself guarantees: every effect is in persistent storage before the token
+// runs. The lock lives in instance storage, which a nested frame cannot
+// observe mid-call, so it is covered by `test_entry_point_rejects_while_lock_held`.
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Attack {
+    /// Behave like an honest token.
+    None,
+    /// Re-enter `deposit(project_id, attacker, self, amount)`.
+    Deposit(u64, Address, i128),
+    /// Re-enter `verify_and_release(oracle, project_id, proof, salt)`.
+    Verify(u64, Address, ContentId, BytesN<32>),
+    /// Re-enter `withdraw_donation(project_id, donator, self, amount)`.
+    Withdraw(u64, Address, i128),
+    /// Re-enter `claim_refund(project_id, donator)`.
+    Refund(u64, Address),
+    /// Record a [`Snapshot`] of `project_id` without calling the protocol.
+    Inspect(u64),
+}
+
+/// Protocol storage as seen from inside the token's `transfer`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+    pub status: ProjectStatus,
+    pub balance: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum TokenKey {
+    Protocol,
+    Attack,
+    Balance(Address),
+    Attempts,
+    Successes,
+    Snapshot,
+}
+
+#[contract]
+pub struct MaliciousToken;
+
+#[contractimpl]
+impl MaliciousToken {
+    pub fn setup(env: Env, protocol: Address) {
+        env.storage().instance().set(&TokenKey::Protocol, &protocol);
+        env.storage()
+            .instance()
+            .set(&TokenKey::Attack, &Attack::None);
+    }
+
+    pub fn set_attack(env: Env, attack: Attack) {
+        env.storage().instance().set(&TokenKey::Attack, &attack);
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .instance()
+            .set(&TokenKey::Balance(to), &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&TokenKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            panic!("insufficient balance");
+        }
+        env.storage()
+            .instance()
+            .set(&TokenKey::Balance(from), &(from_balance - amount));
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .instance()
+            .set(&TokenKey::Balance(to), &(to_balance + amount));
+
+        let attack: Attack = env.storage().instance().get(&TokenKey::Attack).unwrap();
+        let protocol: Address = env.storage().instance().get(&TokenKey::Protocol).unwrap();
+        let client = PifpProtocolClient::new(&env, &protocol);
+        let succeeded = match attack {
+            Attack::None => return,
+            Attack::Inspect(project_id) => {
+                let token = env.current_contract_address();
+                let snapshot = env.as_contract(&protocol, || Snapshot {
+                    status: storage::load_project_state(&env, project_id).status,
+                    balance: storage::get_token_balance(&env, project_id, &token),
+                });
+                env.storage().instance().set(&TokenKey::Snapshot, &snapshot);
+                return;
+            }
+            Attack::Deposit(project_id, attacker, amount) => client
+                .try_deposit(
+                    &project_id,
+                    &attacker,
+                    &env.current_contract_address(),
+                    &amount,
+                )
+                .is_ok(),
+            Attack::Verify(project_id, oracle, proof, salt) => client
+                .try_verify_and_release(&oracle, &project_id, &proof, &salt)
+                .is_ok(),
+            Attack::Withdraw(project_id, donator, amount) => client
+                .try_withdraw_donation(
+                    &project_id,
+                    &donator,
+                    &env.current_contract_address(),
+                    &amount,
+                )
+                .is_ok(),
+            Attack::Refund(project_id, donator) => {
+                client.try_claim_refund(&project_id, &donator).is_ok()
+            }
+        };
+        Self::bump(&env, TokenKey::Attempts);
+        if succeeded {
+            Self::bump(&env, TokenKey::Successes);
+        }
+    }
+
+    /// Return `(callback attempts, callback successes)`.
+    pub fn stats(env: Env) -> (u32, u32) {
+        let attempts = env
+            .storage()
+            .instance()
+            .get(&TokenKey::Attempts)
+            .unwrap_or(0);
+        let successes = env
+            .storage()
+            .instance()
+            .get(&TokenKey::Successes)
+            .unwrap_or(0);
+        (attempts, successes)
+    }
+
+    /// Return the snapshot taken by the last `Inspect` transfer.
+    pub fn snapshot(env: Env) -> Option<Snapshot> {
+        env.storage().instance().get(&TokenKey::Snapshot)
+    }
+
+    fn bump(env: &Env, key: TokenKey) {
+        let n: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(n + 1));
+    }
+}
+
+// ─── Helpers ─────────────────────────────────────────────
+
+struct Fixture {
+    env: Env,
+    client: PifpProtocolClient<'static>,
+    token: MaliciousTokenClient<'static>,
+    creator: Address,
+    oracle: Address,
+    proof: ContentId,
+    salt: BytesN<32>,
+    project_id: u64,
+}
+
+fn setup() -> Fixture {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+
+    let token_id = env.register(MaliciousToken, ());
+    let token = MaliciousTokenClient::new(&env, &token_id);
+    token.setup(&contract_id);
+
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager);
+    client.set_oracle(&super_admin, &oracle);
+    client.allow_token(&super_admin, &token_id, &None);
+
+    let proof = ContentId::sha2_256(BytesN::from_array(&env, &[0x42u8; 32]));
+    let salt = BytesN::from_array(&env, &[0x5au8; 32]);
+    let mut preimage = proof.digest.clone();
+    preimage.append(&Bytes::from_array(&env, &salt.to_array()));
+    let commitment = ContentId::sha2_256(env.crypto().sha256(&preimage).into());
+    let tokens = soroban_sdk::vec![&env, token_id.clone()];
+    let project = client.register_project(
+        &creator,
+        &tokens,
+        &10_000i128,
+        &commitment,
+        &(env.ledger().timestamp() + 86_400),
+    );
+
+    Fixture {
+        env,
+        client,
+        token,
+        creator,
+        oracle,
+        proof,
+        salt,
+        project_id: project.id,
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────
+
+#[test]
+fn test_reentrant_deposit_is_rejected() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    let attacker = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+    f.token.mint(&attacker, &1_000);
+
+    f.token
+        .set_attack(&Attack::Deposit(f.project_id, attacker.clone(), 500));
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+
+    // The nested deposit was attempted and refused.
+    assert_eq!(f.token.stats(), (1, 0));
+    // Only the outer deposit is accounted for, and escrow matches custody.
+    assert_eq!(f.client.get_balance(&f.project_id, &f.token.address), 1_000);
+    assert_eq!(f.token.balance(&f.client.address), 1_000);
+    assert_eq!(f.token.balance(&attacker), 1_000);
+    assert_eq!(f.client.get_project(&f.project_id).donation_count, 1);
+}
+
+#[test]
+fn test_reentrant_verify_during_deposit_is_rejected() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+
+    f.token.set_attack(&Attack::Verify(
+        f.project_id,
+        f.oracle.clone(),
+        f.proof.clone(),
+        f.salt.clone(),
+    ));
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+
+    assert_eq!(f.token.stats(), (1, 0));
+    let project = f.client.get_project(&f.project_id);
+    assert_eq!(project.status, ProjectStatus::Funding);
+    assert_eq!(f.client.get_balance(&f.project_id, &f.token.address), 1_000);
+    assert_eq!(f.token.balance(&f.creator), 0);
+}
+
+#[test]
+fn test_reentrant_verify_during_release_cannot_double_pay() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+
+    f.token.set_attack(&Attack::Verify(
+        f.project_id,
+        f.oracle.clone(),
+        f.proof.clone(),
+        f.salt.clone(),
+    ));
+    f.client
+        .verify_and_release(&f.oracle, &f.project_id, &f.proof, &f.salt);
+
+    assert_eq!(f.token.stats(), (1, 0));
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Completed
+    );
+    // Paid out exactly once; nothing left in escrow or custody.
+    assert_eq!(f.token.balance(&f.creator), 1_000);
+    assert_eq!(f.token.balance(&f.client.address), 0);
+    assert_eq!(f.client.get_balance(&f.project_id, &f.token.address), 0);
+}
+
+#[test]
+fn test_reentrant_deposit_during_release_is_rejected() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    let attacker = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+    f.token.mint(&attacker, &1_000);
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+
+    f.token
+        .set_attack(&Attack::Deposit(f.project_id, attacker.clone(), 500));
+    f.client
+        .verify_and_release(&f.oracle, &f.project_id, &f.proof, &f.salt);
+
+    assert_eq!(f.token.stats(), (1, 0));
+    assert_eq!(f.token.balance(&attacker), 1_000);
+    assert_eq!(f.token.balance(&f.creator), 1_000);
+    assert_eq!(f.client.get_balance(&f.project_id, &f.token.address), 0);
+}
+
+#[test]
+fn test_reentrant_withdraw_during_withdraw_is_rejected() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+
+    // The token pays out the first withdrawal, then tries to withdraw again.
+    f.token
+        .set_attack(&Attack::Withdraw(f.project_id, donator.clone(), 500));
+    f.client
+        .withdraw_donation(&f.project_id, &donator, &f.token.address, &500);
+
+    assert_eq!(f.token.stats(), (1, 0));
+    assert_eq!(f.token.balance(&donator), 500);
+    assert_eq!(f.token.balance(&f.client.address), 500);
+    assert_eq!(f.client.get_balance(&f.project_id, &f.token.address), 500);
+}
+
+#[test]
+fn test_reentrant_refund_during_refund_is_rejected() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+    f.client.cancel_project(
+        &f.creator,
+        &f.project_id,
+        &BytesN::from_array(&f.env, &[0x01u8; 32]),
+    );
+
+    // The token pays out the refund, then tries to claim it again.
+    f.token
+        .set_attack(&Attack::Refund(f.project_id, donator.clone()));
+    f.client.claim_refund(&f.project_id, &donator);
+
+    assert_eq!(f.token.stats(), (1, 0));
+    assert_eq!(f.token.balance(&donator), 1_000);
+    assert_eq!(f.token.balance(&f.client.address), 0);
+    assert_eq!(f.client.get_balance(&f.project_id, &f.token.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #21)")]
+fn test_entry_point_rejects_while_lock_held() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+
+    // Simulate a lock left held by an in-flight call.
+    f.env.as_contract(&f.client.address, || {
+        crate::storage::set_reentrancy_locked(&f.env, true);
+    });
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+}
+
+// ─── Mid-transfer state ──────────────────────────────────
+
+#[test]
+fn test_deposit_is_credited_before_transfer() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+
+    f.token.set_attack(&Attack::Inspect(f.project_id));
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+
+    let snapshot = f.token.snapshot().unwrap();
+    assert_eq!(snapshot.balance, 1_000);
+    assert_eq!(snapshot.status, ProjectStatus::Funding);
+}
+
+#[test]
+fn test_release_settles_before_transfer() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+
+    f.token.set_attack(&Attack::Inspect(f.project_id));
+    f.client
+        .verify_and_release(&f.oracle, &f.project_id, &f.proof, &f.salt);
+
+    let snapshot = f.token.snapshot().unwrap();
+    assert_eq!(snapshot.balance, 0);
+    assert_eq!(snapshot.status, ProjectStatus::Completed);
+}
+
+#[test]
+fn test_withdraw_is_debited_before_transfer() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+
+    f.token.set_attack(&Attack::Inspect(f.project_id));
+    f.client
+        .withdraw_donation(&f.project_id, &donator, &f.token.address, &400);
+
+    let snapshot = f.token.snapshot().unwrap();
+    assert_eq!(snapshot.balance, 600);
+}
+
+#[test]
+fn test_refund_is_debited_before_transfer() {
+    let f = setup();
+    let donator = Address::generate(&f.env);
+    f.token.mint(&donator, &1_000);
+    f.client
+        .deposit(&f.project_id, &donator, &f.token.address, &1_000);
+    f.client.cancel_project(
+        &f.creator,
+        &f.project_id,
+        &BytesN::from_array(&f.env, &[0x01u8; 32]),
+    );
+
+    f.token.set_attack(&Attack::Inspect(f.project_id));
+    f.client.claim_refund(&f.project_id, &donator);
+
+    let snapshot = f.token.snapshot().unwrap();
+    assert_eq!(snapshot.balance, 0);
+    assert_eq!(snapshot.status, ProjectStatus::Cancelled);
+}
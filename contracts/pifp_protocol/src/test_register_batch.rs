@@ -0,0 +1,229 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    vec, Address, BytesN, Env, Map, Vec,
+};
+
+use crate::{
+    ContentId, FundingMode, GoalRule, PifpProtocol, PifpProtocolClient, ProjectInput,
+    ProjectOptions,
+};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token(env: &Env, client: &PifpProtocolClient, admin: &Address) -> Address {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    addr.address()
+}
+
+fn input(env: &Env, token: &Address, goal: i128) -> ProjectInput {
+    ProjectInput {
+        accepted_tokens: vec![env, token.clone()],
+        goal,
+        proof: ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        deadline: env.ledger().timestamp() + 86_400,
+        options: ProjectOptions::default_for(env),
+    }
+}
+
+fn batch_error<T>(
+    result: Result<
+        Result<T, soroban_sdk::ConversionError>,
+        Result<soroban_sdk::Error, soroban_sdk::InvokeError>,
+    >,
+    error: crate::Error,
+) {
+    assert_eq!(
+        result.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(error as u32)))
+    );
+}
+
+#[test]
+fn test_batch_registers_projects_with_contiguous_ids() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+
+    // IDs continue from the existing counter.
+    let first = client.register_project(
+        &admin,
+        &vec![&env, token.clone()],
+        &500,
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+    );
+    assert_eq!(first.id, 0);
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.funding_mode = FundingMode::AllOrNothing;
+    let mut all_or_nothing = input(&env, &token, 3_000);
+    all_or_nothing.options = options;
+    let inputs = vec![
+        &env,
+        input(&env, &token, 1_000),
+        input(&env, &token, 2_000),
+        all_or_nothing,
+    ];
+    let projects = client.register_projects(&admin, &inputs);
+
+    // One `created` event per project.
+    let count = env
+        .events()
+        .all()
+        .iter()
+        .filter(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("created").to_val())
+        })
+        .count();
+    assert_eq!(count, 3);
+
+    assert_eq!(projects.len(), 3);
+    for (i, project) in projects.iter().enumerate() {
+        assert_eq!(project.id, 1 + i as u64);
+        assert_eq!(project.creator, admin);
+        assert_eq!(client.get_project(&project.id), project);
+    }
+    assert_eq!(projects.get(1).unwrap().goal, 2_000);
+    assert_eq!(
+        projects.get(2).unwrap().funding_mode,
+        FundingMode::AllOrNothing
+    );
+
+    let next = client.register_project(
+        &admin,
+        &vec![&env, token.clone()],
+        &500,
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+    );
+    assert_eq!(next.id, 4);
+}
+
+#[test]
+fn test_batch_with_one_invalid_entry_reverts_entirely() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+
+    let mut bad_goal = input(&env, &token, 1_000);
+    let mut token_goals = Map::new(&env);
+    token_goals.set(Address::generate(&env), 1_000i128);
+    bad_goal.options.token_goals = token_goals;
+    let inputs = vec![&env, input(&env, &token, 1_000), bad_goal];
+    batch_error(
+        client.try_register_projects(&admin, &inputs),
+        crate::Error::InvalidGoal,
+    );
+
+    let mut bad_deadline = input(&env, &token, 1_000);
+    bad_deadline.deadline = env.ledger().timestamp();
+    let inputs = vec![&env, input(&env, &token, 1_000), bad_deadline];
+    batch_error(
+        client.try_register_projects(&admin, &inputs),
+        crate::Error::InvalidDeadline,
+    );
+
+    // No IDs were consumed.
+    let projects = client.register_projects(&admin, &vec![&env, input(&env, &token, 1_000)]);
+    assert_eq!(projects.get(0).unwrap().id, 0);
+}
+
+#[test]
+fn test_batch_rejects_empty_and_oversized() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+
+    batch_error(
+        client.try_register_projects(&admin, &Vec::new(&env)),
+        crate::Error::InvalidBatch,
+    );
+
+    let mut inputs = Vec::new(&env);
+    for _ in 0..=crate::MAX_BATCH_SIZE {
+        inputs.push_back(input(&env, &token, 1_000));
+    }
+    batch_error(
+        client.try_register_projects(&admin, &inputs),
+        crate::Error::InvalidBatch,
+    );
+}
+
+#[test]
+fn test_batch_requires_registration_role() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let outsider = Address::generate(&env);
+
+    batch_error(
+        client.try_register_projects(&outsider, &vec![&env, input(&env, &token, 1_000)]),
+        crate::Error::NotAuthorized,
+    );
+}
+
+#[test]
+fn test_batch_validates_goal_rules_per_entry() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+
+    let mut weighted = input(&env, &token, 1_000);
+    weighted.options.goal_rule = GoalRule::WeightedSum(Map::new(&env));
+    batch_error(
+        client.try_register_projects(&admin, &vec![&env, weighted]),
+        crate::Error::InvalidGoal,
+    );
+}
+
+#[test]
+fn test_batch_costs_less_than_one_at_a_time() {
+    const N: u32 = 10;
+
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let mut singles_cpu = 0u64;
+    let mut singles_mem = 0u64;
+    for _ in 0..N {
+        let single = input(&env, &token, 1_000);
+        env.cost_estimate().budget().reset_default();
+        client.register_project(
+            &admin,
+            &single.accepted_tokens,
+            &single.goal,
+            &single.proof,
+            &single.deadline,
+        );
+        singles_cpu += env.cost_estimate().budget().cpu_instruction_cost();
+        singles_mem += env.cost_estimate().budget().memory_bytes_cost();
+    }
+
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let mut inputs = Vec::new(&env);
+    for _ in 0..N {
+        inputs.push_back(input(&env, &token, 1_000));
+    }
+    env.cost_estimate().budget().reset_default();
+    client.register_projects(&admin, &inputs);
+    let batch_cpu = env.cost_estimate().budget().cpu_instruction_cost();
+    let batch_mem = env.cost_estimate().budget().memory_bytes_cost();
+
+    assert!(
+        batch_cpu < singles_cpu,
+        "{N} projects: batched cpu {batch_cpu} vs singles {singles_cpu}"
+    );
+    assert!(
+        batch_mem < singles_mem,
+        "{N} projects: batched mem {batch_mem} vs singles {singles_mem}"
+    );
+}
@@ -0,0 +1,336 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env,
+};
+
+use crate::test_util::{assert_error, dummy_commitment, dummy_proof, dummy_salt};
+use crate::{
+    FundingMode, GovernanceError, PifpProtocol, PifpProtocolClient, Project, ProjectOptions,
+    ProjectStatus, ReleaseVoteStatus, TokenMetadata, VerificationMode,
+};
+
+const DAY: u64 = 86_400;
+const WINDOW: u64 = 7 * DAY;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    token: &Address,
+    funding_mode: FundingMode,
+) -> Project {
+    let mut options = ProjectOptions::default_for(env);
+    options.funding_mode = funding_mode;
+    options.verification_mode = VerificationMode::DonorVote;
+    client.register_project_with_options(
+        admin,
+        &vec![env, token.clone()],
+        &10_000,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 60 * DAY),
+        &options,
+    )
+}
+
+fn donate(
+    env: &Env,
+    client: &PifpProtocolClient,
+    project_id: u64,
+    token: &token::Client,
+    amount: i128,
+) -> Address {
+    let donor = Address::generate(env);
+    token::StellarAssetClient::new(env, &token.address).mint(&donor, &amount);
+    client.deposit(&project_id, &donor, &token.address, &amount);
+    donor
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + seconds);
+}
+
+#[test]
+fn test_approved_vote_releases_funds() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    let alice = donate(&env, &client, project.id, &usdc, 500);
+    let bob = donate(&env, &client, project.id, &usdc, 300);
+    donate(&env, &client, project.id, &usdc, 200);
+
+    let vote = client.start_release_vote(&project.id, &dummy_proof(&env), &dummy_salt(&env));
+    assert_eq!(vote.ends_at, env.ledger().timestamp() + WINDOW);
+    assert_eq!(vote.quorum_bps, 2_000);
+    assert_eq!(vote.majority_bps, 5_000);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Voting
+    );
+
+    // The electorate is frozen while the vote runs.
+    let late = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&late, &1_000);
+    assert_error(
+        client.try_deposit(&project.id, &late, &usdc.address, &1_000),
+        crate::Error::ProjectNotActive,
+    );
+
+    client.vote_release(&project.id, &alice, &true);
+    client.vote_release(&project.id, &bob, &false);
+    let vote = client.get_release_vote(&project.id);
+    assert_eq!(vote.approve_weight, 500);
+    assert_eq!(vote.reject_weight, 300);
+
+    assert_error(
+        client.try_finalize_release_vote(&project.id),
+        GovernanceError::VotingOpen,
+    );
+    advance(&env, WINDOW);
+    assert_error(
+        client.try_vote_release(&project.id, &bob, &false),
+        GovernanceError::VotingClosed,
+    );
+
+    assert_eq!(
+        client.finalize_release_vote(&project.id),
+        ReleaseVoteStatus::Passed
+    );
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+    assert_eq!(usdc.balance(&admin), 1_000);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 0);
+    assert_error(
+        client.try_finalize_release_vote(&project.id),
+        GovernanceError::VotingClosed,
+    );
+}
+
+#[test]
+fn test_rejected_vote_opens_refunds() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    let alice = donate(&env, &client, project.id, &usdc, 400);
+    let bob = donate(&env, &client, project.id, &usdc, 600);
+
+    client.start_release_vote(&project.id, &dummy_proof(&env), &dummy_salt(&env));
+    client.vote_release(&project.id, &alice, &true);
+    client.vote_release(&project.id, &bob, &true);
+    // Voting again replaces the earlier ballot.
+    client.vote_release(&project.id, &bob, &false);
+
+    advance(&env, WINDOW);
+    assert_eq!(
+        client.finalize_release_vote(&project.id),
+        ReleaseVoteStatus::Failed
+    );
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Cancelled
+    );
+
+    client.claim_refund(&project.id, &alice);
+    client.claim_refund(&project.id, &bob);
+    assert_eq!(usdc.balance(&alice), 400);
+    assert_eq!(usdc.balance(&bob), 600);
+    assert_eq!(usdc.balance(&admin), 0);
+}
+
+#[test]
+fn test_vote_without_quorum_fails() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    let small = donate(&env, &client, project.id, &usdc, 100);
+    donate(&env, &client, project.id, &usdc, 900);
+
+    client.start_release_vote(&project.id, &dummy_proof(&env), &dummy_salt(&env));
+    // Unanimous, but only 10% of the voting power took part.
+    client.vote_release(&project.id, &small, &true);
+
+    advance(&env, WINDOW);
+    assert_eq!(
+        client.finalize_release_vote(&project.id),
+        ReleaseVoteStatus::Failed
+    );
+}
+
+#[test]
+fn test_release_vote_validation() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    donate(&env, &client, project.id, &usdc, 100);
+
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    assert_error(
+        client.try_verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env)),
+        GovernanceError::WrongVerificationMode,
+    );
+    assert_error(
+        client.try_get_release_vote(&project.id),
+        GovernanceError::VoteNotFound,
+    );
+    assert_error(
+        client.try_start_release_vote(
+            &project.id,
+            &dummy_proof(&env),
+            &BytesN::from_array(&env, &[1u8; 32]),
+        ),
+        crate::Error::VerificationFailed,
+    );
+
+    let oracle_project = client.register_project(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &10_000,
+        &dummy_commitment(&env),
+        &(env.ledger().timestamp() + 60 * DAY),
+    );
+    assert_error(
+        client.try_start_release_vote(&oracle_project.id, &dummy_proof(&env), &dummy_salt(&env)),
+        GovernanceError::WrongVerificationMode,
+    );
+
+    let all_or_nothing = register(
+        &env,
+        &client,
+        &admin,
+        &usdc.address,
+        FundingMode::AllOrNothing,
+    );
+    assert_error(
+        client.try_start_release_vote(&all_or_nothing.id, &dummy_proof(&env), &dummy_salt(&env)),
+        crate::Error::GoalNotReached,
+    );
+
+    client.start_release_vote(&project.id, &dummy_proof(&env), &dummy_salt(&env));
+    let stranger = Address::generate(&env);
+    assert_error(
+        client.try_vote_release(&project.id, &stranger, &true),
+        GovernanceError::NoVotingPower,
+    );
+    assert_error(
+        client.try_start_release_vote(&project.id, &dummy_proof(&env), &dummy_salt(&env)),
+        crate::Error::ProjectNotActive,
+    );
+}
+
+#[test]
+fn test_cancelling_during_vote_fails_it() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    let donor = donate(&env, &client, project.id, &usdc, 100);
+
+    client.start_release_vote(&project.id, &dummy_proof(&env), &dummy_salt(&env));
+    client.cancel_project(&admin, &project.id, &BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(
+        client.get_release_vote(&project.id).status,
+        ReleaseVoteStatus::Failed
+    );
+    assert_error(
+        client.try_vote_release(&project.id, &donor, &true),
+        GovernanceError::VotingClosed,
+    );
+    client.claim_refund(&project.id, &donor);
+    assert_eq!(usdc.balance(&donor), 100);
+}
+
+#[test]
+fn test_voting_power_is_normalised_across_decimals() {
+    let (env, client, admin) = setup_with_init();
+    let xlm = create_token(&env, &client, &admin);
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    let metadata = TokenMetadata {
+        symbol: symbol_short!("WETH"),
+        decimals: 18,
+        min_deposit: 0,
+    };
+    client.allow_token(&admin, &addr.address(), &Some(metadata));
+    let weth = token::Client::new(&env, &addr.address());
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.verification_mode = VerificationMode::DonorVote;
+    let project = client.register_project_with_options(
+        &admin,
+        &vec![&env, xlm.address.clone(), weth.address.clone()],
+        &10_000,
+        &dummy_commitment(&env),
+        &(env.ledger().timestamp() + 60 * DAY),
+        &options,
+    );
+    // Two whole 7-decimal tokens against one whole 18-decimal token.
+    let alice = donate(&env, &client, project.id, &xlm, 20_000_000);
+    let bob = donate(&env, &client, project.id, &weth, 10i128.pow(18));
+
+    client.start_release_vote(&project.id, &dummy_proof(&env), &dummy_salt(&env));
+    client.vote_release(&project.id, &alice, &true);
+    client.vote_release(&project.id, &bob, &false);
+    let vote = client.get_release_vote(&project.id);
+    assert_eq!(vote.approve_weight, 20_000_000);
+    assert_eq!(vote.reject_weight, 10_000_000);
+
+    advance(&env, WINDOW);
+    assert_eq!(
+        client.finalize_release_vote(&project.id),
+        ReleaseVoteStatus::Passed
+    );
+}
+
+#[test]
+fn test_reveal_refused_while_voting() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address, FundingMode::Flexible);
+    donate(&env, &client, project.id, &usdc, 500);
+
+    let donor = Address::generate(&env);
+    let secret = Bytes::from_array(&env, &[3u8; 32]);
+    let mut preimage = donor.clone().to_xdr(&env);
+    preimage.append(&secret);
+    let relayer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&relayer, &500);
+    client.donate_committed(
+        &project.id,
+        &relayer,
+        &usdc.address,
+        &500,
+        &env.crypto().sha256(&preimage).into(),
+    );
+
+    client.start_release_vote(&project.id, &dummy_proof(&env), &dummy_salt(&env));
+    assert_error(
+        client.try_reveal_donation(&project.id, &donor, &secret),
+        GovernanceError::VotingOpen,
+    );
+    assert_error(
+        client.try_vote_release(&project.id, &donor, &true),
+        GovernanceError::NoVotingPower,
+    );
+}
@@ -0,0 +1,429 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, BytesN, Env, Map, TryIntoVal, Vec,
+};
+
+use crate::events::{RoundClosed, RoundMatched};
+use crate::rounds::isqrt;
+use crate::test_util::{assert_error, dummy_commitment, dummy_proof, dummy_salt};
+use crate::{
+    PifpProtocol, PifpProtocolClient, Project, ProjectOptions, ProjectStatus, RoundStatus,
+};
+
+const DAY: u64 = 86_400;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    tokens: &Vec<Address>,
+    goal: i128,
+) -> Project {
+    client.register_project(
+        admin,
+        tokens,
+        &goal,
+        &dummy_commitment(env),
+        &(env.ledger().timestamp() + 60 * DAY),
+    )
+}
+
+fn mint(env: &Env, token: &token::Client, to: &Address, amount: i128) {
+    token::StellarAssetClient::new(env, &token.address).mint(to, &amount);
+}
+
+/// `count` new donors each deposit `amount` into `project_id`.
+fn donate(
+    env: &Env,
+    client: &PifpProtocolClient,
+    token: &token::Client,
+    project_id: u64,
+    count: u32,
+    amount: i128,
+) {
+    for _ in 0..count {
+        let donor = Address::generate(env);
+        mint(env, token, &donor, amount);
+        client.deposit(&project_id, &donor, &token.address, &amount);
+    }
+}
+
+/// Create a round over the next 30 days funded by `admin`.
+fn create_round(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    token: &token::Client,
+    pool: i128,
+    max_share_bps: u32,
+) -> u64 {
+    mint(env, token, admin, pool);
+    let now = env.ledger().timestamp();
+    client.create_round(
+        admin,
+        &token.address,
+        &pool,
+        &now,
+        &(now + 30 * DAY),
+        &max_share_bps,
+    )
+}
+
+fn end_round(env: &Env) {
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 30 * DAY);
+}
+
+#[test]
+fn test_isqrt() {
+    assert_eq!(isqrt(-5), 0);
+    assert_eq!(isqrt(0), 0);
+    assert_eq!(isqrt(1), 1);
+    assert_eq!(isqrt(3), 1);
+    assert_eq!(isqrt(4), 2);
+    assert_eq!(isqrt(200), 14);
+    assert_eq!(isqrt(1_000_000_000_000), 1_000_000);
+    let r = isqrt(i128::MAX);
+    assert!(r.checked_mul(r).is_some() && (r + 1).checked_mul(r + 1).is_none());
+}
+
+#[test]
+fn test_many_small_donors_attract_more_matching() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+    let broad = register(&env, &client, &admin, &tokens, 100_000);
+    let narrow = register(&env, &client, &admin, &tokens, 100_000);
+    let whale = register(&env, &client, &admin, &tokens, 100_000);
+    let round_id = create_round(&env, &client, &admin, &usdc, 1_000, 10_000);
+    for project in [&broad, &narrow, &whale] {
+        client.join_round(&round_id, &project.id);
+    }
+
+    // Same 400 raised each: 4 × 100, 2 × 200 and 1 × 400.
+    donate(&env, &client, &usdc, broad.id, 4, 100);
+    donate(&env, &client, &usdc, narrow.id, 2, 200);
+    donate(&env, &client, &usdc, whale.id, 1, 400);
+
+    let tally = client.get_round_project(&round_id, &broad.id);
+    assert_eq!(tally.contributions, 400);
+    assert_eq!(tally.sum_sqrt, 40);
+    assert_eq!(client.get_round_project(&round_id, &narrow.id).sum_sqrt, 28);
+
+    assert_error(
+        client.try_close_round(&round_id),
+        crate::Error::RoundNotEnded,
+    );
+    end_round(&env);
+    let matches = client.close_round(&round_id);
+
+    // Ideals: 40² − 400 = 1200, 28² − 400 = 384, 20² − 400 = 0.
+    assert_eq!(matches, vec![&env, 757i128, 242, 0]);
+    let events = env.events().all();
+    let matched: std::vec::Vec<RoundMatched> = events
+        .iter()
+        .filter(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("qf_match").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .collect();
+    assert_eq!(matched.len(), 2);
+    assert_eq!(matched[0].project_id, broad.id);
+    assert_eq!(matched[0].amount, 757);
+    let closed: RoundClosed = events
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("rnd_close").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(closed.distributed, 999);
+    assert_eq!(closed.returned, 1);
+
+    assert_eq!(client.get_balance(&broad.id, &usdc.address), 1_157);
+    assert_eq!(client.get_balance(&narrow.id, &usdc.address), 642);
+    assert_eq!(client.get_balance(&whale.id, &usdc.address), 400);
+    assert_eq!(client.get_round_project(&round_id, &broad.id).matched, 757);
+    assert_eq!(usdc.balance(&admin), 1);
+    assert_eq!(client.get_round(&round_id).status, RoundStatus::Closed);
+    assert_error(client.try_close_round(&round_id), crate::Error::RoundClosed);
+}
+
+#[test]
+fn test_share_cap_limits_a_single_project() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+    let broad = register(&env, &client, &admin, &tokens, 100_000);
+    let narrow = register(&env, &client, &admin, &tokens, 100_000);
+    let round_id = create_round(&env, &client, &admin, &usdc, 1_000, 5_000);
+    client.join_round(&round_id, &broad.id);
+    client.join_round(&round_id, &narrow.id);
+    donate(&env, &client, &usdc, broad.id, 4, 100);
+    donate(&env, &client, &usdc, narrow.id, 2, 200);
+
+    end_round(&env);
+    let matches = client.close_round(&round_id);
+
+    // The broad project is capped at 50% of the pool; the excess goes back.
+    assert_eq!(matches, vec![&env, 500i128, 242]);
+    assert_eq!(usdc.balance(&admin), 258);
+}
+
+#[test]
+fn test_only_window_and_matching_token_donations_count() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone(), eurc.address.clone()];
+    let project = register(&env, &client, &admin, &tokens, 100_000);
+
+    // Given before the project joined: not counted.
+    donate(&env, &client, &usdc, project.id, 1, 100);
+
+    mint(&env, &usdc, &admin, 1_000);
+    let now = env.ledger().timestamp();
+    let round_id = client.create_round(
+        &admin,
+        &usdc.address,
+        &1_000,
+        &(now + DAY),
+        &(now + 10 * DAY),
+        &10_000,
+    );
+    client.join_round(&round_id, &project.id);
+
+    // Before the window opens: not counted.
+    donate(&env, &client, &usdc, project.id, 1, 100);
+    env.ledger().set_timestamp(now + DAY);
+    // Not the matching token: not counted.
+    donate(&env, &client, &eurc, project.id, 1, 100);
+    donate(&env, &client, &usdc, project.id, 2, 100);
+
+    let tally = client.get_round_project(&round_id, &project.id);
+    assert_eq!(tally.contributions, 200);
+    assert_eq!(tally.sum_sqrt, 20);
+
+    // After the window closes: not counted.
+    env.ledger().set_timestamp(now + 10 * DAY);
+    donate(&env, &client, &usdc, project.id, 1, 100);
+    assert_eq!(
+        client
+            .get_round_project(&round_id, &project.id)
+            .contributions,
+        200
+    );
+}
+
+#[test]
+fn test_withdrawal_removes_counted_contribution() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+    let project = register(&env, &client, &admin, &tokens, 100_000);
+    let round_id = create_round(&env, &client, &admin, &usdc, 1_000, 10_000);
+    client.join_round(&round_id, &project.id);
+
+    donate(&env, &client, &usdc, project.id, 3, 100);
+    let donor = Address::generate(&env);
+    mint(&env, &usdc, &donor, 100);
+    client.deposit(&project.id, &donor, &usdc.address, &100);
+    assert_eq!(
+        client.get_round_project(&round_id, &project.id).sum_sqrt,
+        40
+    );
+
+    client.withdraw_donation(&project.id, &donor, &usdc.address, &100);
+    let tally = client.get_round_project(&round_id, &project.id);
+    assert_eq!(tally.contributions, 300);
+    assert_eq!(tally.sum_sqrt, 30);
+}
+
+#[test]
+fn test_match_can_complete_goal_and_skips_closed_projects() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+    let open = register(&env, &client, &admin, &tokens, 1_000);
+    let done = register(&env, &client, &admin, &tokens, 100_000);
+    let round_id = create_round(&env, &client, &admin, &usdc, 1_000, 10_000);
+    client.join_round(&round_id, &open.id);
+    client.join_round(&round_id, &done.id);
+    donate(&env, &client, &usdc, open.id, 4, 100);
+    donate(&env, &client, &usdc, done.id, 4, 100);
+
+    // A completed project is left out of the split.
+    let oracle = Address::generate(&env);
+    client.set_oracle(&admin, &oracle);
+    client.verify_and_release(&oracle, &done.id, &dummy_proof(&env), &dummy_salt(&env));
+
+    end_round(&env);
+    let matches = client.close_round(&round_id);
+    assert_eq!(matches, vec![&env, 1_000i128, 0]);
+    let open = client.get_project(&open.id);
+    assert_eq!(open.status, ProjectStatus::Active);
+    assert_eq!(client.get_balance(&open.id, &usdc.address), 1_400);
+    assert_eq!(client.get_balance(&done.id, &usdc.address), 0);
+}
+
+#[test]
+fn test_large_pool_does_not_overflow() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+    let project = register(&env, &client, &admin, &tokens, 100_000);
+    let pool: i128 = 10i128.pow(22);
+    let round_id = create_round(&env, &client, &admin, &usdc, pool, 10_000);
+    client.join_round(&round_id, &project.id);
+
+    // pool × ideal is around 2 × 10⁴³, beyond i128.
+    donate(&env, &client, &usdc, project.id, 2, 10i128.pow(21));
+    end_round(&env);
+    let matches = client.close_round(&round_id);
+
+    assert_eq!(matches, vec![&env, pool]);
+    assert_eq!(usdc.balance(&admin), 0);
+}
+
+#[test]
+fn test_match_is_trimmed_to_max_raise() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let mut options = ProjectOptions::default_for(&env);
+    options.max_raise = Map::from_array(&env, [(usdc.address.clone(), 500)]);
+    let project = client.register_project_with_options(
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        &500,
+        &dummy_commitment(&env),
+        &(env.ledger().timestamp() + 60 * DAY),
+        &options,
+    );
+    let round_id = create_round(&env, &client, &admin, &usdc, 1_000, 10_000);
+    client.join_round(&round_id, &project.id);
+    donate(&env, &client, &usdc, project.id, 4, 100);
+
+    end_round(&env);
+    let matches = client.close_round(&round_id);
+
+    // Only 100 of headroom is left under the cap; the rest goes back.
+    assert_eq!(matches, vec![&env, 100i128]);
+    assert_eq!(
+        client.get_round_project(&round_id, &project.id).matched,
+        100
+    );
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 500);
+    assert_eq!(usdc.balance(&admin), 900);
+}
+
+#[test]
+fn test_cancelled_project_gets_no_match() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+    let open = register(&env, &client, &admin, &tokens, 100_000);
+    let cancelled = register(&env, &client, &admin, &tokens, 100_000);
+    let round_id = create_round(&env, &client, &admin, &usdc, 1_000, 10_000);
+    client.join_round(&round_id, &open.id);
+    client.join_round(&round_id, &cancelled.id);
+    donate(&env, &client, &usdc, open.id, 2, 200);
+    donate(&env, &client, &usdc, cancelled.id, 4, 100);
+
+    client.cancel_project(&admin, &cancelled.id, &BytesN::from_array(&env, &[1u8; 32]));
+    end_round(&env);
+    let matches = client.close_round(&round_id);
+
+    assert_eq!(matches, vec![&env, 1_000i128, 0]);
+    assert_eq!(client.get_balance(&cancelled.id, &usdc.address), 400);
+    assert_eq!(client.get_balance(&open.id, &usdc.address), 1_400);
+}
+
+#[test]
+fn test_round_validation() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let project = register(
+        &env,
+        &client,
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        1_000,
+    );
+    let now = env.ledger().timestamp();
+
+    let outsider = Address::generate(&env);
+    assert_error(
+        client.try_create_round(
+            &outsider,
+            &usdc.address,
+            &1_000,
+            &now,
+            &(now + DAY),
+            &10_000,
+        ),
+        crate::Error::NotAuthorized,
+    );
+    assert_error(
+        client.try_create_round(&admin, &usdc.address, &0, &now, &(now + DAY), &10_000),
+        crate::Error::InvalidRound,
+    );
+    assert_error(
+        client.try_create_round(&admin, &usdc.address, &1_000, &now, &now, &10_000),
+        crate::Error::InvalidRound,
+    );
+    assert_error(
+        client.try_create_round(&admin, &usdc.address, &1_000, &now, &(now + DAY), &10_001),
+        crate::Error::InvalidRound,
+    );
+    assert_error(client.try_get_round(&3), crate::Error::RoundNotFound);
+
+    let eurc_round = create_round(&env, &client, &admin, &eurc, 1_000, 10_000);
+    assert_error(
+        client.try_join_round(&eurc_round, &project.id),
+        crate::Error::TokenNotAccepted,
+    );
+
+    let round_id = create_round(&env, &client, &admin, &usdc, 1_000, 10_000);
+    client.join_round(&round_id, &project.id);
+    assert_error(
+        client.try_join_round(&round_id, &project.id),
+        crate::Error::AlreadyInRound,
+    );
+
+    let late = register(
+        &env,
+        &client,
+        &admin,
+        &vec![&env, usdc.address.clone()],
+        1_000,
+    );
+    end_round(&env);
+    assert_error(
+        client.try_join_round(&round_id, &late.id),
+        crate::Error::RoundClosed,
+    );
+}
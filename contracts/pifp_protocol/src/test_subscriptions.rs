@@ -0,0 +1,321 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, BytesN, Env, TryIntoVal,
+};
+
+use crate::events::{SubscriptionCharged, SubscriptionEnded};
+use crate::test_util::assert_error;
+use crate::{
+    ContentId, Contribution, PifpProtocol, PifpProtocolClient, Project, SubscriptionStatus,
+};
+
+const MONTH: u64 = 30 * 86_400;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, token: &Address) -> Project {
+    client.register_project(
+        admin,
+        &vec![env, token.clone()],
+        &10_000,
+        &ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 12 * MONTH),
+    )
+}
+
+/// A subscriber holding `balance` who has approved the protocol for `allowance`.
+fn subscriber(
+    env: &Env,
+    client: &PifpProtocolClient,
+    token: &token::Client,
+    balance: i128,
+    allowance: i128,
+) -> Address {
+    let subscriber = Address::generate(env);
+    token::StellarAssetClient::new(env, &token.address).mint(&subscriber, &balance);
+    token.approve(
+        &subscriber,
+        &client.address,
+        &allowance,
+        &(env.ledger().sequence() + 10_000),
+    );
+    subscriber
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + seconds);
+}
+
+fn ended_event(env: &Env) -> Option<SubscriptionEnded> {
+    env.events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("sub_end").to_val())
+        })
+        .map(|e| e.2.try_into_val(env).unwrap())
+}
+
+#[test]
+fn test_subscription_charges_each_period_until_complete() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let donor = subscriber(&env, &client, &token, 1_000, 1_000);
+
+    let sub_id = client.subscribe(&project.id, &donor, &token.address, &100, &MONTH, &3);
+
+    // The first payment is due immediately; anyone can trigger it.
+    assert_eq!(client.execute_subscription(&sub_id), 100);
+    let charged: SubscriptionCharged = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            e.1.get(0)
+                .unwrap()
+                .shallow_eq(&symbol_short!("sub_paid").to_val())
+        })
+        .map(|e| e.2.try_into_val(&env).unwrap())
+        .unwrap();
+    assert_eq!(charged.sub_id, sub_id);
+    assert_eq!(charged.subscriber, donor);
+    assert_eq!(charged.amount, 100);
+    assert_eq!(charged.period, 1);
+
+    assert_error(
+        client.try_execute_subscription(&sub_id),
+        crate::Error::PaymentNotDue,
+    );
+
+    advance(&env, MONTH);
+    assert_eq!(client.execute_subscription(&sub_id), 100);
+    advance(&env, MONTH);
+    assert_eq!(client.execute_subscription(&sub_id), 100);
+    assert_eq!(
+        ended_event(&env).unwrap().status,
+        SubscriptionStatus::Completed
+    );
+
+    let sub = client.get_subscription(&sub_id);
+    assert_eq!(sub.periods_charged, 3);
+    assert_eq!(sub.status, SubscriptionStatus::Completed);
+
+    advance(&env, MONTH);
+    assert_error(
+        client.try_execute_subscription(&sub_id),
+        crate::Error::SubscriptionInactive,
+    );
+
+    // Every payment is a regular deposit credited to the subscriber.
+    assert_eq!(token.balance(&donor), 700);
+    assert_eq!(client.get_balance(&project.id, &token.address), 300);
+    assert_eq!(
+        client.get_contribution(&project.id, &donor),
+        vec![
+            &env,
+            Contribution {
+                token: token.address.clone(),
+                amount: 300
+            }
+        ]
+    );
+    let project = client.get_project(&project.id);
+    assert_eq!(project.deposit_count, 3);
+    assert_eq!(project.unique_donors, 1);
+}
+
+#[test]
+fn test_late_payment_keeps_the_schedule() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let donor = subscriber(&env, &client, &token, 1_000, 1_000);
+    let start = env.ledger().timestamp();
+
+    let sub_id = client.subscribe(&project.id, &donor, &token.address, &100, &MONTH, &5);
+    client.execute_subscription(&sub_id);
+
+    // Charged ten days late, the next payment is still due two months in.
+    advance(&env, MONTH + 10 * 86_400);
+    client.execute_subscription(&sub_id);
+    assert_eq!(client.get_subscription(&sub_id).next_due, start + 2 * MONTH);
+
+    // A missed period can be caught up once it falls due.
+    advance(&env, 2 * MONTH);
+    client.execute_subscription(&sub_id);
+    client.execute_subscription(&sub_id);
+    assert_error(
+        client.try_execute_subscription(&sub_id),
+        crate::Error::PaymentNotDue,
+    );
+    assert_eq!(client.get_subscription(&sub_id).periods_charged, 4);
+}
+
+#[test]
+fn test_subscription_stops_when_allowance_runs_out() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let donor = subscriber(&env, &client, &token, 1_000, 150);
+
+    let sub_id = client.subscribe(&project.id, &donor, &token.address, &100, &MONTH, &12);
+    assert_eq!(client.execute_subscription(&sub_id), 100);
+
+    advance(&env, MONTH);
+    assert_eq!(client.execute_subscription(&sub_id), 0);
+    assert_eq!(
+        ended_event(&env).unwrap().status,
+        SubscriptionStatus::InsufficientFunds
+    );
+    assert_eq!(
+        client.get_subscription(&sub_id).status,
+        SubscriptionStatus::InsufficientFunds
+    );
+    assert_eq!(token.balance(&donor), 900);
+    assert_eq!(client.get_balance(&project.id, &token.address), 100);
+}
+
+#[test]
+fn test_subscription_stops_when_balance_runs_out() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let donor = subscriber(&env, &client, &token, 50, 1_000);
+
+    let sub_id = client.subscribe(&project.id, &donor, &token.address, &100, &MONTH, &12);
+    assert_eq!(client.execute_subscription(&sub_id), 0);
+    assert_eq!(
+        client.get_subscription(&sub_id).status,
+        SubscriptionStatus::InsufficientFunds
+    );
+    assert_eq!(token.balance(&donor), 50);
+}
+
+#[test]
+fn test_subscription_stops_when_project_closes() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let donor = subscriber(&env, &client, &token, 10_000, 10_000);
+
+    let sub_id = client.subscribe(&project.id, &donor, &token.address, &100, &MONTH, &24);
+    client.execute_subscription(&sub_id);
+
+    env.ledger().set_timestamp(project.deadline);
+    assert_eq!(client.execute_subscription(&sub_id), 0);
+    assert_eq!(
+        ended_event(&env).unwrap().status,
+        SubscriptionStatus::ProjectClosed
+    );
+    assert_eq!(token.balance(&donor), 9_900);
+    assert_error(
+        client.try_execute_subscription(&sub_id),
+        crate::Error::SubscriptionInactive,
+    );
+}
+
+#[test]
+fn test_subscriber_can_cancel() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let donor = subscriber(&env, &client, &token, 1_000, 1_000);
+
+    let sub_id = client.subscribe(&project.id, &donor, &token.address, &100, &MONTH, &12);
+    client.cancel_subscription(&sub_id);
+    assert_eq!(
+        ended_event(&env).unwrap().status,
+        SubscriptionStatus::Cancelled
+    );
+
+    assert_error(
+        client.try_execute_subscription(&sub_id),
+        crate::Error::SubscriptionInactive,
+    );
+    assert_error(
+        client.try_cancel_subscription(&sub_id),
+        crate::Error::SubscriptionInactive,
+    );
+    assert_eq!(token.balance(&donor), 1_000);
+}
+
+#[test]
+fn test_subscribe_validates_input() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let other = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let donor = Address::generate(&env);
+
+    assert_error(
+        client.try_subscribe(&project.id, &donor, &token.address, &0, &MONTH, &12),
+        crate::Error::InvalidSubscription,
+    );
+    assert_error(
+        client.try_subscribe(&project.id, &donor, &token.address, &100, &0, &12),
+        crate::Error::InvalidSubscription,
+    );
+    assert_error(
+        client.try_subscribe(&project.id, &donor, &token.address, &100, &MONTH, &0),
+        crate::Error::InvalidSubscription,
+    );
+    assert_error(
+        client.try_subscribe(&project.id, &donor, &other.address, &100, &MONTH, &12),
+        crate::Error::TokenNotAccepted,
+    );
+    assert_error(
+        client.try_get_subscription(&7),
+        crate::Error::SubscriptionNotFound,
+    );
+}
+
+#[test]
+fn test_subscription_stops_when_project_refuses_payment() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &token.address);
+    let donor = subscriber(&env, &client, &token, 1_000, 1_000);
+
+    let sub_id = client.subscribe(&project.id, &donor, &token.address, &100, &MONTH, &12);
+    assert_eq!(client.execute_subscription(&sub_id), 100);
+
+    // A delisted token no longer accepts deposits; the keeper's call must
+    // end the subscription rather than fail every period.
+    client.disallow_token(&admin, &token.address);
+    advance(&env, MONTH);
+    assert_eq!(client.execute_subscription(&sub_id), 0);
+    assert_eq!(
+        ended_event(&env).unwrap().status,
+        SubscriptionStatus::PaymentRefused
+    );
+    assert_eq!(
+        client.get_subscription(&sub_id).status,
+        SubscriptionStatus::PaymentRefused
+    );
+    assert_eq!(token.balance(&donor), 900);
+    assert_error(
+        client.try_execute_subscription(&sub_id),
+        crate::Error::SubscriptionInactive,
+    );
+}
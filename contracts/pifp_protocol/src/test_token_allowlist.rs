@@ -0,0 +1,241 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    token, vec, Address, Env, IntoVal, TryIntoVal,
+};
+
+use crate::test_util::{dummy_commitment, dummy_proof, dummy_salt};
+use crate::{PifpProtocol, PifpProtocolClient, Role, TokenMetadata};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn future_deadline(env: &Env) -> u64 {
+    env.ledger().timestamp() + 86_400
+}
+
+fn usdc_metadata(min_deposit: i128) -> TokenMetadata {
+    TokenMetadata {
+        symbol: symbol_short!("USDC"),
+        decimals: 7,
+        min_deposit,
+    }
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #22)")]
+fn test_register_with_unlisted_token_fails() {
+    let (env, client, admin) = setup_with_init();
+    let tokens = vec![&env, Address::generate(&env)];
+    client.register_project(
+        &admin,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #22)")]
+fn test_register_with_one_unlisted_token_fails() {
+    let (env, client, admin) = setup_with_init();
+    let listed = Address::generate(&env);
+    client.allow_token(&admin, &listed, &None);
+    let tokens = vec![&env, listed, Address::generate(&env)];
+    client.register_project(
+        &admin,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_non_admin_cannot_allow_token() {
+    let (env, client, admin) = setup_with_init();
+    let pm = Address::generate(&env);
+    client.grant_role(&admin, &pm, &Role::ProjectManager);
+    client.allow_token(&pm, &Address::generate(&env), &None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #22)")]
+fn test_disallow_unlisted_token_fails() {
+    let (env, client, admin) = setup_with_init();
+    client.disallow_token(&admin, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")]
+fn test_allow_token_rejects_negative_min_deposit() {
+    let (env, client, admin) = setup_with_init();
+    client.allow_token(&admin, &Address::generate(&env), &Some(usdc_metadata(-1)));
+}
+
+#[test]
+fn test_allow_updates_metadata_without_duplicating() {
+    let (env, client, admin) = setup_with_init();
+    let token = Address::generate(&env);
+
+    client.allow_token(&admin, &token, &None);
+    client.allow_token(&admin, &token, &Some(usdc_metadata(100)));
+
+    let listed = client.list_tokens(&0, &10);
+    assert_eq!(listed, vec![&env, token.clone()]);
+    assert_eq!(client.get_token_metadata(&token), Some(usdc_metadata(100)));
+    assert!(client.is_token_allowed(&token));
+}
+
+#[test]
+fn test_list_tokens_paginates_and_survives_removal() {
+    let (env, client, admin) = setup_with_init();
+    let tokens: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&env)).collect();
+    for t in &tokens {
+        client.allow_token(&admin, t, &None);
+    }
+
+    let page1 = client.list_tokens(&0, &2);
+    let page2 = client.list_tokens(&2, &2);
+    let page3 = client.list_tokens(&4, &2);
+    assert_eq!(page1.len(), 2);
+    assert_eq!(page2.len(), 2);
+    assert_eq!(page3.len(), 1);
+    assert_eq!(page1.get(0).unwrap(), tokens[0]);
+    assert_eq!(page3.get(0).unwrap(), tokens[4]);
+    assert_eq!(client.list_tokens(&5, &2).len(), 0);
+
+    // Removing from the middle moves the last entry into the freed slot.
+    client.disallow_token(&admin, &tokens[1]);
+    assert!(!client.is_token_allowed(&tokens[1]));
+    let all = client.list_tokens(&0, &50);
+    assert_eq!(all.len(), 4);
+    assert_eq!(all.get(1).unwrap(), tokens[4]);
+
+    // Removing the last entry and re-adding a token keeps the index dense.
+    client.disallow_token(&admin, &tokens[3]);
+    client.allow_token(&admin, &tokens[1], &None);
+    let all = client.list_tokens(&0, &50);
+    assert_eq!(all.len(), 4);
+    assert_eq!(all.get(3).unwrap(), tokens[1]);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #23)")]
+fn test_deposit_below_min_deposit_fails() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &admin);
+    let donator = Address::generate(&env);
+    client.allow_token(&admin, &token.address, &Some(usdc_metadata(100)));
+
+    let tokens = vec![&env, token.address.clone()];
+    let project = client.register_project(
+        &admin,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+
+    token::StellarAssetClient::new(&env, &token.address).mint(&donator, &1_000);
+    client.deposit(&project.id, &donator, &token.address, &100);
+    client.deposit(&project.id, &donator, &token.address, &99);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #22)")]
+fn test_deposit_in_delisted_token_fails() {
+    let (env, client, admin) = setup_with_init();
+    let token = create_token(&env, &admin);
+    let donator = Address::generate(&env);
+    client.allow_token(&admin, &token.address, &None);
+
+    let tokens = vec![&env, token.address.clone()];
+    let project = client.register_project(
+        &admin,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+    client.disallow_token(&admin, &token.address);
+
+    token::StellarAssetClient::new(&env, &token.address).mint(&donator, &1_000);
+    client.deposit(&project.id, &donator, &token.address, &500);
+}
+
+#[test]
+fn test_delisting_does_not_strand_escrow() {
+    let (env, client, admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let donator = Address::generate(&env);
+    let token = create_token(&env, &admin);
+    client.grant_role(&admin, &creator, &Role::ProjectManager);
+    client.set_oracle(&admin, &oracle);
+    client.allow_token(&admin, &token.address, &None);
+
+    let tokens = vec![&env, token.address.clone()];
+    let project = client.register_project(
+        &creator,
+        &tokens,
+        &1000i128,
+        &dummy_commitment(&env),
+        &future_deadline(&env),
+    );
+    token::StellarAssetClient::new(&env, &token.address).mint(&donator, &700);
+    client.deposit(&project.id, &donator, &token.address, &700);
+
+    client.disallow_token(&admin, &token.address);
+    client.verify_and_release(&oracle, &project.id, &dummy_proof(&env), &dummy_salt(&env));
+
+    assert_eq!(token.balance(&creator), 700);
+    assert_eq!(client.get_balance(&project.id, &token.address), 0);
+}
+
+#[test]
+fn test_allowlist_events() {
+    let (env, client, admin) = setup_with_init();
+    let token = Address::generate(&env);
+
+    client.allow_token(&admin, &token, &Some(usdc_metadata(0)));
+    let last = env.events().all().last().unwrap();
+    assert_eq!(last.0, client.address);
+    assert_eq!(
+        last.1,
+        vec![
+            &env,
+            symbol_short!("tok_set").into_val(&env),
+            token.into_val(&env)
+        ]
+    );
+    let data: Option<TokenMetadata> = last.2.try_into_val(&env).unwrap();
+    assert_eq!(data, Some(usdc_metadata(0)));
+
+    client.disallow_token(&admin, &token);
+    let last = env.events().all().last().unwrap();
+    assert_eq!(
+        last.1,
+        vec![
+            &env,
+            symbol_short!("tok_del").into_val(&env),
+            token.into_val(&env)
+        ]
+    );
+}
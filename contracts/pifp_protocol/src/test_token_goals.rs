@@ -0,0 +1,236 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    token, vec, Address, BytesN, Env, IntoVal, Map, Vec,
+};
+
+use crate::{
+    ContentId, GoalRule, PifpProtocol, PifpProtocolClient, Project, ProjectOptions, ProjectStatus,
+};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register_with(
+    env: &Env,
+    client: &PifpProtocolClient,
+    admin: &Address,
+    tokens: &Vec<Address>,
+    goal: i128,
+    options: &ProjectOptions,
+) -> Project {
+    client.register_project_with_options(
+        admin,
+        tokens,
+        &goal,
+        &ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+        options,
+    )
+}
+
+fn donate(env: &Env, client: &PifpProtocolClient, project_id: u64, token: &Address, amount: i128) {
+    let donator = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&donator, &amount);
+    client.deposit(&project_id, &donator, token, &amount);
+}
+
+#[test]
+fn test_default_goal_applies_to_first_token() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let xlm = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone(), xlm.address.clone()];
+    let project = client.register_project(
+        &admin,
+        &tokens,
+        &1_000,
+        &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+        &(env.ledger().timestamp() + 86_400),
+    );
+
+    let mut expected = Map::new(&env);
+    expected.set(usdc.address.clone(), 1_000i128);
+    assert_eq!(project.token_goals, expected);
+    assert_eq!(project.goal_rule, GoalRule::AnyToken);
+
+    // Deposits in the second token do not count towards the legacy goal.
+    donate(&env, &client, project.id, &xlm.address, 5_000);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+
+    donate(&env, &client, project.id, &usdc.address, 1_000);
+    let last = env.events().all().last().unwrap();
+    assert_eq!(
+        last.1,
+        vec![
+            &env,
+            symbol_short!("goal_met").into_val(&env),
+            project.id.into_val(&env)
+        ]
+    );
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+}
+
+#[test]
+fn test_any_token_goal() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone(), eurc.address.clone()];
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.token_goals.set(usdc.address.clone(), 50_000);
+    options.token_goals.set(eurc.address.clone(), 45_000);
+    let project = register_with(&env, &client, &admin, &tokens, 50_000, &options);
+
+    donate(&env, &client, project.id, &usdc.address, 49_999);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+    donate(&env, &client, project.id, &eurc.address, 45_000);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+}
+
+#[test]
+fn test_all_tokens_goal_and_progress() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone(), eurc.address.clone()];
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.token_goals.set(usdc.address.clone(), 500);
+    options.token_goals.set(eurc.address.clone(), 400);
+    options.goal_rule = GoalRule::AllTokens;
+    let project = register_with(&env, &client, &admin, &tokens, 500, &options);
+
+    donate(&env, &client, project.id, &usdc.address, 600);
+    let progress = client.get_project_balances(&project.id);
+    assert!(!progress.goal_reached);
+    let usdc_progress = progress.balances.get(0).unwrap();
+    assert_eq!((usdc_progress.balance, usdc_progress.goal), (600, 500));
+    let eurc_progress = progress.balances.get(1).unwrap();
+    assert_eq!((eurc_progress.balance, eurc_progress.goal), (0, 400));
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+
+    donate(&env, &client, project.id, &eurc.address, 400);
+    assert!(client.get_project_balances(&project.id).goal_reached);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+}
+
+#[test]
+fn test_weighted_sum_goal() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let xlm = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone(), xlm.address.clone()];
+
+    // 1 USDC counts as 1 unit, 1 XLM as 0.1 unit.
+    let mut weights = Map::new(&env);
+    weights.set(usdc.address.clone(), 10_000u32);
+    weights.set(xlm.address.clone(), 1_000u32);
+    let mut options = ProjectOptions::default_for(&env);
+    options.goal_rule = GoalRule::WeightedSum(weights);
+    let project = register_with(&env, &client, &admin, &tokens, 1_000, &options);
+    // The weighted goal is not any one token's goal.
+    assert!(project.token_goals.is_empty());
+    client.remove_accepted_token(&project.id, &usdc.address);
+    client.add_accepted_token(&project.id, &usdc.address);
+
+    donate(&env, &client, project.id, &usdc.address, 500);
+    donate(&env, &client, project.id, &xlm.address, 4_990);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+    donate(&env, &client, project.id, &xlm.address, 10);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+}
+
+#[test]
+fn test_invalid_goal_settings_rejected() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let other = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone()];
+
+    let mut unaccepted = ProjectOptions::default_for(&env);
+    unaccepted.token_goals.set(other.address.clone(), 100);
+
+    let mut zero = ProjectOptions::default_for(&env);
+    zero.token_goals.set(usdc.address.clone(), 0);
+
+    let mut empty_weights = ProjectOptions::default_for(&env);
+    empty_weights.goal_rule = GoalRule::WeightedSum(Map::new(&env));
+
+    let mut zero_weight = ProjectOptions::default_for(&env);
+    let mut weights = Map::new(&env);
+    weights.set(usdc.address.clone(), 0u32);
+    zero_weight.goal_rule = GoalRule::WeightedSum(weights);
+
+    for options in [unaccepted, zero, empty_weights, zero_weight] {
+        let result = client.try_register_project_with_options(
+            &admin,
+            &tokens,
+            &1_000,
+            &ContentId::sha2_256(BytesN::from_array(&env, &[0xabu8; 32])),
+            &(env.ledger().timestamp() + 86_400),
+            &options,
+        );
+        assert_eq!(
+            result.err(),
+            Some(Ok(soroban_sdk::Error::from_contract_error(
+                crate::Error::InvalidGoal as u32
+            )))
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #27)")]
+fn test_cannot_remove_token_with_goal() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let eurc = create_token(&env, &client, &admin);
+    let tokens = vec![&env, usdc.address.clone(), eurc.address.clone()];
+
+    let mut options = ProjectOptions::default_for(&env);
+    options.token_goals.set(eurc.address.clone(), 100);
+    let project = register_with(&env, &client, &admin, &tokens, 100, &options);
+
+    client.remove_accepted_token(&project.id, &eurc.address);
+}
@@ -0,0 +1,33 @@
+//! Fixtures shared by the test modules.
+//!
+//! Each test module keeps its own `setup_with_init` and registration
+//! helpers; only the proof fixtures and error assertion live here.
+
+use soroban_sdk::{Bytes, BytesN, Env};
+
+use crate::ContentId;
+
+/// Proof artifact digest used by test projects.
+pub fn dummy_proof(env: &Env) -> ContentId {
+    ContentId::sha2_256(BytesN::from_array(env, &[0xabu8; 32]))
+}
+
+pub fn dummy_salt(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0x5au8; 32])
+}
+
+/// Commitment to [`dummy_proof`] under [`dummy_salt`], as registered.
+pub fn dummy_commitment(env: &Env) -> ContentId {
+    let mut preimage = dummy_proof(env).digest;
+    preimage.append(&Bytes::from_array(env, &dummy_salt(env).to_array()));
+    ContentId::sha2_256(env.crypto().sha256(&preimage).into())
+}
+
+/// Assert that a `try_` client call failed with the contract error `error`,
+/// either an [`crate::Error`] or a [`crate::GovernanceError`].
+pub fn assert_error<T: core::fmt::Debug>(
+    result: Result<T, Result<soroban_sdk::Error, soroban_sdk::InvokeError>>,
+    error: impl Into<soroban_sdk::Error>,
+) {
+    assert_eq!(result.err(), Some(Ok(error.into())));
+}
@@ -0,0 +1,196 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Env, IntoVal, Symbol, TryIntoVal,
+};
+
+use crate::events::DonationWithdrawn;
+use crate::test_util::{assert_error, dummy_proof};
+use crate::{PifpProtocol, PifpProtocolClient, Project, ProjectStatus};
+
+const DAY: u64 = 86_400;
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, client: &PifpProtocolClient, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    client.allow_token(admin, &addr.address(), &None);
+    token::Client::new(env, &addr.address())
+}
+
+fn register(env: &Env, client: &PifpProtocolClient, admin: &Address, token: &Address) -> Project {
+    client.register_project(
+        admin,
+        &vec![env, token.clone()],
+        &10_000,
+        &dummy_proof(env),
+        &(env.ledger().timestamp() + 30 * DAY),
+    )
+}
+
+fn funded_donator(env: &Env, token: &Address, amount: i128) -> Address {
+    let donator = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&donator, &amount);
+    donator
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + seconds);
+}
+
+#[test]
+fn test_withdraw_within_window_returns_funds() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donator = funded_donator(&env, &usdc.address, 1_000);
+
+    client.deposit(&project.id, &donator, &usdc.address, &1_000);
+    advance(&env, DAY - 1);
+    client.withdraw_donation(&project.id, &donator, &usdc.address, &400);
+
+    let last = env.events().all().last().unwrap();
+    assert_eq!(
+        last.1,
+        vec![
+            &env,
+            Symbol::new(&env, "donation_withdrawn").into_val(&env),
+            project.id.into_val(&env)
+        ]
+    );
+    let data: DonationWithdrawn = last.2.try_into_val(&env).unwrap();
+    assert_eq!(
+        data,
+        DonationWithdrawn {
+            project_id: project.id,
+            donator: donator.clone(),
+            token: usdc.address.clone(),
+            amount: 400,
+        }
+    );
+
+    assert_eq!(usdc.balance(&donator), 400);
+    assert_eq!(usdc.balance(&client.address), 600);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 600);
+
+    // The rest of the same deposit is still withdrawable; nothing more is.
+    client.withdraw_donation(&project.id, &donator, &usdc.address, &600);
+    assert_error(
+        client.try_withdraw_donation(&project.id, &donator, &usdc.address, &1),
+        crate::Error::CoolingOffExceeded,
+    );
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 0);
+}
+
+#[test]
+fn test_window_is_tracked_per_deposit() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donator = funded_donator(&env, &usdc.address, 1_000);
+
+    client.deposit(&project.id, &donator, &usdc.address, &700);
+    advance(&env, DAY / 2);
+    client.deposit(&project.id, &donator, &usdc.address, &300);
+    advance(&env, DAY / 2);
+
+    // The first deposit's window has closed; only the second remains.
+    assert_error(
+        client.try_withdraw_donation(&project.id, &donator, &usdc.address, &301),
+        crate::Error::CoolingOffExceeded,
+    );
+    client.withdraw_donation(&project.id, &donator, &usdc.address, &300);
+    assert_eq!(usdc.balance(&donator), 300);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 700);
+}
+
+#[test]
+fn test_withdraw_only_own_deposits() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donator = funded_donator(&env, &usdc.address, 1_000);
+    let other = Address::generate(&env);
+
+    client.deposit(&project.id, &donator, &usdc.address, &1_000);
+    assert_error(
+        client.try_withdraw_donation(&project.id, &other, &usdc.address, &1),
+        crate::Error::CoolingOffExceeded,
+    );
+}
+
+#[test]
+fn test_withdraw_refused_once_goal_reached() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donator = funded_donator(&env, &usdc.address, 10_000);
+
+    client.deposit(&project.id, &donator, &usdc.address, &10_000);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+    assert_error(
+        client.try_withdraw_donation(&project.id, &donator, &usdc.address, &1_000),
+        crate::Error::ProjectNotActive,
+    );
+}
+
+#[test]
+fn test_zero_window_disables_withdrawals() {
+    let (env, client, admin) = setup_with_init();
+    let mut params = client.get_params();
+    params.cooling_off_period = 0;
+    client.set_params(&admin, &params);
+
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donator = funded_donator(&env, &usdc.address, 1_000);
+
+    client.deposit(&project.id, &donator, &usdc.address, &1_000);
+    assert_error(
+        client.try_withdraw_donation(&project.id, &donator, &usdc.address, &1),
+        crate::Error::CoolingOffExceeded,
+    );
+}
+
+#[test]
+fn test_withdraw_never_exceeds_contribution_or_balance() {
+    let (env, client, admin) = setup_with_init();
+    let usdc = create_token(&env, &client, &admin);
+    let project = register(&env, &client, &admin, &usdc.address);
+    let donator = funded_donator(&env, &usdc.address, 500);
+    client.deposit(&project.id, &donator, &usdc.address, &500);
+
+    // The deposit is still in the log, but the contribution behind it is gone.
+    env.as_contract(&client.address, || {
+        crate::storage::record_contribution(&env, project.id, &donator, &usdc.address, -300);
+    });
+    assert_error(
+        client.try_withdraw_donation(&project.id, &donator, &usdc.address, &500),
+        crate::Error::InsufficientBalance,
+    );
+
+    // Likewise when the project holds less than the deposit.
+    env.as_contract(&client.address, || {
+        crate::storage::record_contribution(&env, project.id, &donator, &usdc.address, 300);
+        crate::storage::set_token_balance(&env, project.id, &usdc.address, 100);
+    });
+    assert_error(
+        client.try_withdraw_donation(&project.id, &donator, &usdc.address, &500),
+        crate::Error::InsufficientBalance,
+    );
+    client.withdraw_donation(&project.id, &donator, &usdc.address, &100);
+    assert_eq!(client.get_balance(&project.id, &usdc.address), 0);
+}
@@ -8,9 +8,13 @@
 //!
 //! A `Project` is internally stored as two separate ledger entries:
 //!
-//! - [`ProjectConfig`] — written once at registration; never mutated.
+//! - [`ProjectConfig`] — written at registration; only `deadline` changes,
+//!   when a donor-approved extension takes effect.
 //! - [`ProjectState`] — written on every deposit and on verification.
 //!
+//! The accepted token list is a third entry of its own, since it can be
+//! edited by the creator but is not touched by deposits.
+//!
 //! The public API exposes the reconstructed [`Project`] struct for convenience.
 //!
 //! ### Status as a Finite-State Machine
@@ -22,12 +26,23 @@
 //!     └──────────────────►┘
 //!     └──► Expired
 //! Active ──► Expired
+//! Funding | Active | Expired ──► Cancelled
+//! Funding | Active ──► Voting ──► Completed | Cancelled
+//! Funding | Active ──► UnderReview ──► Completed | Cancelled
+//!                      UnderReview ──► Funding | Active   (proof rejected)
 //! ```
 //!
+//! `Voting` is only entered by projects registered with
+//! [`VerificationMode::DonorVote`]; a failed vote cancels the project.
+//! `UnderReview` holds an oracle-verified project while the oracle checks
+//! the creator's submitted proof; a rejection returns it to the status it
+//! was submitted from.
+//!
 //! Backward transitions and transitions out of terminal states (`Completed`,
-//! `Expired`) are rejected by `verify_and_release`.
+//! `Expired`, `Cancelled`) are rejected by `verify_and_release`. `Expired` can
+//! still be cancelled so that its donors can claim refunds.
 
-use soroban_sdk::{contracttype, Address, BytesN, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, Map, Symbol, Vec};
 
 /// Current lifecycle state of a funding project.
 #[contracttype]
@@ -41,15 +56,183 @@ pub enum ProjectStatus {
     Completed,
     /// Deadline passed without reaching goal or verification.
     Expired,
+    /// Cancelled by the creator or an admin, or by a failed donor vote;
+    /// donors may claim refunds.
+    Cancelled,
+    /// Donors are voting on the creator's proof; deposits are closed.
+    Voting,
+    /// The creator submitted proof for the oracle to review; deposits are closed.
+    UnderReview,
+}
+
+/// What happens to funds when a project falls short of its goal.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FundingMode {
+    /// Verification is refused until the goal is reached; if the deadline
+    /// passes first, donors reclaim their funds with `claim_refund`.
+    AllOrNothing,
+    /// Whatever was raised can be released on verification.
+    Flexible,
+}
+
+/// What `deposit` does with the part of a donation above a token's `max_raise`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExcessPolicy {
+    /// Refuse the whole deposit.
+    Reject,
+    /// Accept only up to the remaining headroom; the rest stays with the donor.
+    AcceptPartial,
+}
+
+/// Who approves the release of a project's funds.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerificationMode {
+    /// An address holding the Oracle role calls `verify_and_release`.
+    Oracle,
+    /// Donors vote on the creator's proof, weighted by their contributions.
+    DonorVote,
 }
 
-/// Immutable project configuration, written once at registration.
+/// How a project decides that its funding goal has been reached.
+///
+/// Evaluated after every deposit; reaching the goal moves the project from
+/// `Funding` to `Active`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GoalRule {
+    /// Reached when any token with a per-token goal has raised at least that goal.
+    AnyToken,
+    /// Reached when every token with a per-token goal has raised at least that goal.
+    AllTokens,
+    /// Reached when `Σ raised(token) × weight(token) / 10_000 ≥ goal`.
+    ///
+    /// Weights are basis-point conversion rates from each token's units into
+    /// the units of the project's `goal`; unweighted tokens do not count.
+    WeightedSum(Map<Address, u32>),
+    /// Reached when the escrow, valued in a quote currency by a SEP-40 price
+    /// feed, is worth at least `goal` (quote units at the feed's precision).
+    QuoteValue(QuoteGoal),
+}
+
+/// Price feed settings for [`GoalRule::QuoteValue`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuoteGoal {
+    /// SEP-40 price feed quoting every accepted token in the goal currency.
+    pub feed: Address,
+    /// Maximum age in seconds of a price before it is ignored.
+    /// Balances with a stale or missing price count as zero.
+    pub max_price_age: u64,
+    /// Decimals of the project `goal`; must equal the feed's `decimals()`.
+    pub decimals: u32,
+}
+
+/// Optional settings accepted by `register_project_with_options`.
+///
+/// `register_project` uses [`ProjectOptions::default_for`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectOptions {
+    /// Per-token funding goals. When empty, the project `goal` becomes the
+    /// goal of the first accepted token, except under
+    /// [`GoalRule::WeightedSum`] and [`GoalRule::QuoteValue`].
+    pub token_goals: Map<Address, i128>,
+    /// Rule combining per-token progress into goal completion.
+    pub goal_rule: GoalRule,
+    /// Whether funds can be released below the goal.
+    pub funding_mode: FundingMode,
+    /// Per-token hard caps on the escrowed balance. Uncapped when absent.
+    pub max_raise: Map<Address, i128>,
+    /// Handling of deposits that would exceed a cap.
+    pub excess_policy: ExcessPolicy,
+    /// Who approves the release of funds.
+    pub verification_mode: VerificationMode,
+}
+
+impl ProjectOptions {
+    /// Options matching the behaviour of plain `register_project`.
+    pub fn default_for(env: &Env) -> Self {
+        ProjectOptions {
+            token_goals: Map::new(env),
+            goal_rule: GoalRule::AnyToken,
+            funding_mode: FundingMode::Flexible,
+            max_raise: Map::new(env),
+            excess_policy: ExcessPolicy::Reject,
+            verification_mode: VerificationMode::Oracle,
+        }
+    }
+}
+
+/// Self-describing content identifier: a multihash code and digest bytes.
+///
+/// See [`crate::proofs`] for the accepted codes and digest lengths.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContentId {
+    /// Multihash code of the hash algorithm, e.g. `0x12` for SHA2-256.
+    pub code: u32,
+    /// Digest bytes; at most [`crate::proofs::MAX_DIGEST_LEN`] long.
+    pub digest: Bytes,
+}
+
+impl ContentId {
+    /// Identifier of a SHA2-256 digest.
+    pub fn sha2_256(digest: BytesN<32>) -> Self {
+        ContentId {
+            code: crate::proofs::SHA2_256,
+            digest: digest.into(),
+        }
+    }
+}
+
+/// One project in a `register_projects` batch.
+///
+/// Fields mirror the arguments of `register_project_with_options`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectInput {
+    pub accepted_tokens: Vec<Address>,
+    pub goal: i128,
+    pub proof: ContentId,
+    pub deadline: u64,
+    pub options: ProjectOptions,
+}
+
+/// Project configuration, written at registration.
+///
+/// Only `deadline` is ever rewritten, by an approved deadline extension.
 ///
 /// Stored separately from mutable state to reduce write costs on deposits
 /// and verification (only ~20 bytes for state vs ~150 bytes for the full struct).
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProjectConfig {
+    pub id: u64,
+    pub creator: Address,
+    pub goal: i128,
+    pub proof: ContentId,
+    pub deadline: u64,
+    pub token_goals: Map<Address, i128>,
+    pub goal_rule: GoalRule,
+    pub funding_mode: FundingMode,
+    pub max_raise: Map<Address, i128>,
+    pub excess_policy: ExcessPolicy,
+    pub verification_mode: VerificationMode,
+    /// Version of [`ProtocolParams`] in force when the project was registered.
+    /// Later checks on this project read that version, not the current one.
+    pub params_version: u32,
+}
+
+/// `ProjectConfig` as stored before per-project options, governed
+/// parameters and editable token lists.
+///
+/// Still read from `DataKey::ProjConfig` and migrated on first access.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyProjectConfig {
     pub id: u64,
     pub creator: Address,
     pub accepted_tokens: Vec<Address>,
@@ -67,6 +250,20 @@ pub struct ProjectState {
     pub status: ProjectStatus,
     /// Count of unique (donator, token) pairs that have deposited.
     pub donation_count: u32,
+    /// Count of unique donors, regardless of token.
+    pub unique_donors: u32,
+    /// Count of successful `deposit` calls.
+    pub deposit_count: u32,
+}
+
+/// `ProjectState` as stored before donor metrics were split out.
+///
+/// Still read from `DataKey::ProjState` and migrated on first access.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyProjectState {
+    pub status: ProjectStatus,
+    pub donation_count: u32,
 }
 
 /// Full on-chain representation of a funding project.
@@ -81,14 +278,43 @@ pub struct Project {
     /// Address that registered and will receive released funds.
     pub creator: Address,
     /// Ordered list of SAC token addresses this project accepts.
-    /// Set once at registration; cannot be changed after creation.
-    /// Length: 1–10 tokens.
+    /// The creator may add tokens, or remove tokens with a zero balance,
+    /// while the project is `Funding`. Length: 1 to the `max_accepted_tokens`
+    /// limit the project was registered under.
     pub accepted_tokens: soroban_sdk::Vec<Address>,
-    /// Funding goal expressed in the *first* accepted token's units.
-    /// Used as a reference denominator; cross-token goals require off-chain logic.
+    /// Headline funding goal. Under `GoalRule::WeightedSum` and
+    /// `GoalRule::QuoteValue` it is the target of the weighted or quoted
+    /// total and no token gets it as its goal. Otherwise, unless
+    /// `token_goals` was given at registration, it is the goal of the first
+    /// accepted token.
     pub goal: i128,
-    /// Content hash (e.g. IPFS CID digest) of proof artifacts.
-    pub proof_hash: soroban_sdk::BytesN<32>,
+    /// Per-token funding goals, keyed by token.
+    pub token_goals: Map<Address, i128>,
+    /// Rule combining per-token progress into goal completion.
+    pub goal_rule: GoalRule,
+    /// Whether funds can be released below the goal.
+    pub funding_mode: FundingMode,
+    /// Per-token hard caps; a token at its cap stops accepting deposits.
+    pub max_raise: Map<Address, i128>,
+    /// Handling of deposits that would exceed a cap.
+    pub excess_policy: ExcessPolicy,
+    /// Who approves the release of funds.
+    pub verification_mode: VerificationMode,
+    /// Latest proof submitted by the creator and the oracle's decision.
+    pub proof_review: ProofReview,
+    /// Number of entries in the project's evidence log.
+    pub evidence_count: u32,
+    /// Evidence entry the oracle cited when it released the funds, if any.
+    pub verified_evidence: Option<u32>,
+    /// Expected proof: the multihash code of the proof artifact's content
+    /// identifier (e.g. an IPFS CID), with the commitment
+    /// `sha256(artifact_digest || salt)` as digest. The artifact digest and
+    /// salt stay off-chain until verification reveals them. For projects
+    /// registered before commitments the digest is the artifact digest.
+    pub proof: ContentId,
+    /// Whether `proof` carries a salted commitment. Always true for new
+    /// projects.
+    pub proof_salted: bool,
     /// Ledger timestamp by which the project must be completed.
     pub deadline: u64,
     /// Current lifecycle state.
@@ -96,6 +322,10 @@ pub struct Project {
     /// Count of unique (token, donator) pairs that have donated.
     /// Informational; incremented on each new deposit.
     pub donation_count: u32,
+    /// Count of unique donors; one person giving in two tokens counts once.
+    pub unique_donors: u32,
+    /// Count of deposit transactions, including repeat deposits.
+    pub deposit_count: u32,
 }
 
 impl Project {
@@ -116,6 +346,14 @@ impl Project {
 pub struct TokenBalance {
     pub token: Address,
     pub balance: i128,
+    /// Per-token goal, or `0` if this token has none.
+    pub goal: i128,
+    /// Net amount raised in this token, kept after funds are released.
+    pub raised: i128,
+    /// Part of `raised` credited by sponsor matching pledges.
+    pub matched: i128,
+    /// Sponsor funds still available to match deposits in this token.
+    pub match_available: i128,
 }
 
 /// Full balance view returned by `get_project_balances`.
@@ -124,4 +362,332 @@ pub struct TokenBalance {
 pub struct ProjectBalances {
     pub project_id: u64,
     pub balances: Vec<TokenBalance>,
+    /// Whether the project's `GoalRule` is currently satisfied.
+    pub goal_reached: bool,
+    /// Escrow value in the quote currency for `QuoteValue` goals, 0 otherwise.
+    pub quote_value: i128,
+}
+
+/// Optional descriptive metadata attached to an allowlisted token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    /// Ticker shown by frontends (e.g. `USDC`).
+    pub symbol: Symbol,
+    /// Number of decimal places used by the token.
+    pub decimals: u32,
+    /// Smallest amount accepted by `deposit` for this token; `0` disables the check.
+    pub min_deposit: i128,
+}
+
+/// Governed protocol limits, changed by admins through `set_params`.
+///
+/// Every change is stored as a new numbered version. Projects record the
+/// version they were registered under so that later parameter changes never
+/// alter the rules of an existing project.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolParams {
+    /// Maximum length of `accepted_tokens`.
+    pub max_accepted_tokens: u32,
+    /// Largest permitted funding goal.
+    pub max_goal: i128,
+    /// Minimum seconds between registration and deadline.
+    pub min_deadline_duration: u64,
+    /// Maximum seconds between registration and deadline.
+    pub max_deadline_duration: u64,
+    /// Ledgers by which instance storage TTL is extended.
+    pub instance_bump_amount: u32,
+    /// Instance storage is extended when its TTL falls below this many ledgers.
+    pub instance_lifetime_threshold: u32,
+    /// Ledgers by which persistent entry TTLs are extended.
+    pub persistent_bump_amount: u32,
+    /// Persistent entries are extended when their TTL falls below this many ledgers.
+    pub persistent_lifetime_threshold: u32,
+    /// Seconds after each deposit during which the donor may withdraw it.
+    /// `0` disables withdrawals.
+    pub cooling_off_period: u64,
+    /// Seconds donors have to vote on a deadline extension, during which
+    /// they may also claim a refund.
+    pub extension_window: u64,
+    /// Share of donor voting power, in basis points, whose objection rejects
+    /// a deadline extension that a majority has not approved.
+    pub extension_objection_bps: u32,
+    /// Seconds donors have to vote on a `DonorVote` project's proof.
+    pub release_vote_window: u64,
+    /// Share of donor voting power, in basis points, that must vote for a
+    /// release vote to count.
+    pub release_quorum_bps: u32,
+    /// Share of the votes cast, in basis points, that approvals must exceed
+    /// for funds to be released.
+    pub release_majority_bps: u32,
+}
+
+/// Net amount a donor has contributed to a project in one token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contribution {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// A donation recorded only by its commitment.
+///
+/// `commitment = sha256(donor.to_xdr() || secret)`; the donor stays unknown
+/// until they call `reveal_donation` with `secret`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommittedDonation {
+    pub token: Address,
+    pub amount: i128,
+    pub revealed: bool,
+}
+
+/// A single deposit still inside its cooling-off window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositRecord {
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Lifecycle of a [`Subscription`]; every state but `Active` is final and
+/// records why charging stopped.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubscriptionStatus {
+    /// Payments are still being charged.
+    Active,
+    /// All `max_periods` payments were made.
+    Completed,
+    /// The subscriber cancelled it.
+    Cancelled,
+    /// The project left `Funding`/`Active` or passed its deadline.
+    ProjectClosed,
+    /// The subscriber's allowance or balance no longer covers a payment.
+    InsufficientFunds,
+    /// The project refused the payment: its token is no longer accepted or
+    /// allowlisted, the payment is below the minimum deposit, or the
+    /// token's cap is reached.
+    PaymentRefused,
+}
+
+/// A recurring donation pulled from the subscriber's token allowance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub id: u64,
+    pub project_id: u64,
+    pub subscriber: Address,
+    pub token: Address,
+    pub amount_per_period: i128,
+    /// Seconds between payments.
+    pub period: u64,
+    pub max_periods: u32,
+    /// Payments made so far.
+    pub periods_charged: u32,
+    /// Earliest ledger timestamp at which the next payment can be executed.
+    pub next_due: u64,
+    pub status: SubscriptionStatus,
+}
+
+/// Lifecycle of a quadratic funding [`Round`].
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoundStatus {
+    /// Projects may join until `ends_at`; matching has not been paid.
+    Open,
+    /// `close_round` has credited the matches and returned the remainder.
+    Closed,
+}
+
+/// A quadratic funding matching round.
+///
+/// Donations in `matching_token` made between `starts_at` and `ends_at` to
+/// a participating project are tallied per donor. At close, `pool` is split
+/// in proportion to each project's `(Σ √contribution)² − Σ contribution`,
+/// with no project receiving more than `max_share_bps` of the pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Round {
+    pub id: u64,
+    /// Admin who escrowed the pool; receives any unallocated remainder.
+    pub funder: Address,
+    pub matching_token: Address,
+    pub pool: i128,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    /// Largest share of `pool` a single project can receive, in basis points.
+    pub max_share_bps: u32,
+    /// Participating projects, in joining order.
+    pub projects: Vec<u64>,
+    pub status: RoundStatus,
+}
+
+/// A project's tally within a [`Round`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundProject {
+    /// Sum of counted donor contributions.
+    pub contributions: i128,
+    /// Sum over donors of the integer square root of their contribution.
+    pub sum_sqrt: i128,
+    /// Matching credited at close; `0` while the round is open.
+    pub matched: i128,
+}
+
+/// A sponsor's pledge to match donations to one project.
+///
+/// The full `cap` is escrowed at creation. Each deposit in `token` before
+/// `expires_at` draws `amount × ratio_bps / 10_000` from `remaining` into
+/// the project's balance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchPledge {
+    pub id: u64,
+    pub sponsor: Address,
+    pub project_id: u64,
+    pub token: Address,
+    /// Matched amount per unit donated, in basis points (`10_000` is 1:1).
+    pub ratio_bps: u32,
+    pub cap: i128,
+    /// Escrowed funds not yet matched.
+    pub remaining: i128,
+    /// Funds credited to the project so far, net of reversed withdrawals.
+    pub matched: i128,
+    pub expires_at: u64,
+    /// Set once `remaining` has been returned to the sponsor.
+    pub settled: bool,
+}
+
+/// Outcome of a deadline extension proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExtensionStatus {
+    /// Donors may still vote and claim refunds.
+    Pending,
+    /// The new deadline is in effect.
+    Approved,
+    Rejected,
+}
+
+/// A creator's request to move a project's deadline, decided by a
+/// contribution-weighted donor vote.
+///
+/// Once `ends_at` has passed it is approved if donors holding a majority of
+/// the voting power approved, or if objections stayed at or below
+/// `objection_bps` of it. Either way the new deadline only takes effect
+/// after the window, so every donor can claim a refund first.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionProposal {
+    pub project_id: u64,
+    /// Sequence number of this proposal among the project's proposals.
+    pub seq: u32,
+    pub old_deadline: u64,
+    pub new_deadline: u64,
+    /// End of the voting and refund window.
+    pub ends_at: u64,
+    /// Objection threshold, from the project's protocol parameters.
+    pub objection_bps: u32,
+    /// Voting power of donors who approved.
+    pub approve_weight: i128,
+    /// Voting power of donors who objected.
+    pub object_weight: i128,
+    pub status: ExtensionStatus,
+}
+
+/// A donor's vote, at the weight it was counted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ballot {
+    pub approve: bool,
+    pub weight: i128,
+}
+
+/// Outcome of a donor release vote.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReleaseVoteStatus {
+    Open,
+    /// Funds were released to the creator.
+    Passed,
+    /// The project was cancelled and refunds opened.
+    Failed,
+}
+
+/// Donor vote on the proof of a [`VerificationMode::DonorVote`] project.
+///
+/// Passes if the votes cast reach `quorum_bps` of the donors' voting power
+/// and approvals exceed `majority_bps` of the votes cast.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseVote {
+    pub project_id: u64,
+    /// Proof the creator revealed for donors to inspect.
+    pub proof: ContentId,
+    pub ends_at: u64,
+    pub quorum_bps: u32,
+    pub majority_bps: u32,
+    pub approve_weight: i128,
+    pub reject_weight: i128,
+    pub status: ReleaseVoteStatus,
+}
+
+/// Proof of completed work submitted by a creator with `submit_proof`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofSubmission {
+    /// Content identifier of the evidence bundle.
+    pub evidence_hash: ContentId,
+    /// Hash of the URI where the evidence can be retrieved.
+    pub uri_hash: BytesN<32>,
+    pub submitted_at: u64,
+    /// Status the project returns to if the proof is rejected.
+    pub prior_status: ProjectStatus,
+}
+
+/// Where a project's latest proof submission stands.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProofReview {
+    /// No proof has been submitted.
+    None,
+    /// Awaiting the oracle; the project is `UnderReview`.
+    Pending(ProofSubmission),
+    /// Accepted by the oracle, which released the funds.
+    Accepted(ProofSubmission),
+    /// Sent back by the oracle, or closed by cancelling the project, with
+    /// the hash of the reason.
+    Rejected(ProofSubmission, BytesN<32>),
+}
+
+/// What an evidence log entry documents.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EvidenceKind {
+    /// Progress update from the creator.
+    Progress,
+    /// Photo or other media of the work.
+    Photo,
+    /// Audit or inspection report.
+    Audit,
+    /// Anything else.
+    Other,
+}
+
+/// One entry in a project's append-only evidence log.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EvidenceEntry {
+    pub project_id: u64,
+    /// Position in the log, starting at 0.
+    pub index: u32,
+    /// The creator or auditor who added the entry.
+    pub author: Address,
+    /// Content identifier of the evidence artifact.
+    pub content_hash: ContentId,
+    pub kind: EvidenceKind,
+    /// Ledger timestamp at which the entry was added.
+    pub timestamp: u64,
 }
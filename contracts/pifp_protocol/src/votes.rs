@@ -0,0 +1,157 @@
+//! # Donor Votes
+//!
+//! Contribution-weighted votes of a project's donors, on deadline
+//! extensions and, for `DonorVote` projects, on the release of funds. A
+//! donor's voting power is the sum of their recorded contributions in the
+//! project's accepted tokens, each scaled from the token's `decimals` to
+//! [`VOTE_DECIMALS`] so that a whole token weighs the same whatever its
+//! precision; the project's total voting power is the same sum over every
+//! donor. Tokens without metadata are taken to use [`VOTE_DECIMALS`].
+//! Anonymous committed donations carry no power until they are revealed.
+//!
+//! A vote is counted at the donor's power when it is cast. If that
+//! contribution later shrinks, through a cooling-off withdrawal or a refund,
+//! the counted extension vote is lowered to match so that funds which have
+//! left the project cannot keep voting. Contributions cannot change during a
+//! release vote, since deposits, withdrawals and reveals are closed while
+//! `Voting`.
+
+use soroban_sdk::{Address, Env};
+
+use crate::storage;
+use crate::types::{Ballot, ExtensionProposal, ExtensionStatus, ReleaseVote};
+
+/// Basis-point denominator for vote thresholds.
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Decimal places voting power is expressed in: those of a Stellar asset.
+pub const VOTE_DECIMALS: u32 = 7;
+
+/// Voting power of `donor` in `project_id`.
+pub fn voting_power(env: &Env, project_id: u64, donor: &Address) -> i128 {
+    let mut power: i128 = 0;
+    for token in storage::get_accepted_tokens(env, project_id).iter() {
+        let amount = storage::get_contribution(env, project_id, donor, &token);
+        power = power.saturating_add(normalise(env, &token, amount));
+    }
+    power.max(0)
+}
+
+/// Voting power of all donors of `project_id`.
+pub fn total_voting_power(env: &Env, project_id: u64) -> i128 {
+    let mut power: i128 = 0;
+    for token in storage::get_accepted_tokens(env, project_id).iter() {
+        let amount = storage::get_contributed_total(env, project_id, &token);
+        power = power.saturating_add(normalise(env, &token, amount));
+    }
+    power.max(0)
+}
+
+/// Scale `amount` of `token` from its own decimals to [`VOTE_DECIMALS`].
+fn normalise(env: &Env, token: &Address, amount: i128) -> i128 {
+    let decimals = storage::get_token_metadata(env, token).map_or(VOTE_DECIMALS, |m| m.decimals);
+    if decimals >= VOTE_DECIMALS {
+        match 10i128.checked_pow(decimals - VOTE_DECIMALS) {
+            Some(factor) => amount / factor,
+            None => 0,
+        }
+    } else {
+        amount.saturating_mul(10i128.pow(VOTE_DECIMALS - decimals))
+    }
+}
+
+/// Count `donor`'s vote on `proposal` at `weight`, replacing any earlier
+/// vote of theirs. The caller saves the proposal.
+pub fn cast_extension_vote(
+    env: &Env,
+    proposal: &mut ExtensionProposal,
+    donor: &Address,
+    approve: bool,
+    weight: i128,
+) {
+    let (project_id, seq) = (proposal.project_id, proposal.seq);
+    if let Some(previous) = storage::get_extension_vote(env, project_id, seq, donor) {
+        tally(proposal, previous.approve, -previous.weight);
+    }
+    tally(proposal, approve, weight);
+    storage::set_extension_vote(env, project_id, seq, donor, &Ballot { approve, weight });
+}
+
+/// Lower `donor`'s counted vote on the project's pending extension to their
+/// current voting power.
+pub fn sync_extension_vote(env: &Env, project_id: u64, donor: &Address) {
+    let Some(mut proposal) = storage::get_extension(env, project_id) else {
+        return;
+    };
+    if proposal.status != ExtensionStatus::Pending {
+        return;
+    }
+    let Some(mut vote) = storage::get_extension_vote(env, project_id, proposal.seq, donor) else {
+        return;
+    };
+    let power = voting_power(env, project_id, donor);
+    if power >= vote.weight {
+        return;
+    }
+
+    tally(&mut proposal, vote.approve, power - vote.weight);
+    vote.weight = power;
+    storage::set_extension_vote(env, project_id, proposal.seq, donor, &vote);
+    storage::save_extension(env, &proposal);
+}
+
+/// Decide a proposal whose window has ended, given the project's total
+/// voting power at that point.
+pub fn extension_outcome(proposal: &ExtensionProposal, total: i128) -> ExtensionStatus {
+    let majority = proposal.approve_weight.saturating_mul(2) > total;
+    let objected = proposal.object_weight.saturating_mul(BPS_DENOMINATOR)
+        > total.saturating_mul(proposal.objection_bps as i128);
+    if majority || !objected {
+        ExtensionStatus::Approved
+    } else {
+        ExtensionStatus::Rejected
+    }
+}
+
+/// Count `donor`'s ballot in a release vote at `weight`, replacing any
+/// earlier ballot of theirs. The caller saves the vote.
+pub fn cast_release_vote(
+    env: &Env,
+    vote: &mut ReleaseVote,
+    donor: &Address,
+    approve: bool,
+    weight: i128,
+) {
+    if let Some(previous) = storage::get_release_ballot(env, vote.project_id, donor) {
+        if previous.approve {
+            vote.approve_weight -= previous.weight;
+        } else {
+            vote.reject_weight -= previous.weight;
+        }
+    }
+    if approve {
+        vote.approve_weight += weight;
+    } else {
+        vote.reject_weight += weight;
+    }
+    storage::set_release_ballot(env, vote.project_id, donor, &Ballot { approve, weight });
+}
+
+/// Return true if a closed release vote met its quorum and majority, given
+/// the project's total voting power.
+pub fn release_passed(vote: &ReleaseVote, total: i128) -> bool {
+    let cast = vote.approve_weight.saturating_add(vote.reject_weight);
+    let quorum = cast > 0
+        && cast.saturating_mul(BPS_DENOMINATOR) >= total.saturating_mul(vote.quorum_bps as i128);
+    let majority = vote.approve_weight.saturating_mul(BPS_DENOMINATOR)
+        > cast.saturating_mul(vote.majority_bps as i128);
+    quorum && majority
+}
+
+fn tally(proposal: &mut ExtensionProposal, approve: bool, delta: i128) {
+    if approve {
+        proposal.approve_weight += delta;
+    } else {
+        proposal.object_weight += delta;
+    }
+}